@@ -0,0 +1,138 @@
+//! Command-line arguments for launching the editor into a reproducible setup.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use thiserror::Error;
+
+use crate::console_command::{
+    ConsoleCommandQueued,
+    commands::{run_autoexec, run_startup_script},
+};
+
+pub const USAGE: &str = "\
+Usage: bfs_editor [OPTIONS]
+
+Options:
+  --config-dir <path>   Use <path> instead of $HOME/.config/bevy_falling_sand
+  --world <name>        Open the world <name> for this session
+  --windowed            Start in a window instead of borderless fullscreen
+  --size <W>x<H>        Window size (implies --windowed)
+  --exec <command>      Run a console command at startup (repeatable)
+  --script <file>       Run each line of <file> as a console command at startup (repeatable)
+  -h, --help            Print this help and exit";
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("missing value for {0}")]
+    MissingValue(String),
+    #[error("invalid window size '{0}' (expected WxH, e.g. 1280x720)")]
+    InvalidSize(String),
+    #[error("unknown argument '{0}'")]
+    UnknownArgument(String),
+}
+
+/// A console command to run once startup has finished.
+#[derive(Clone, Debug)]
+pub enum StartupCommand {
+    Exec(String),
+    Script(PathBuf),
+}
+
+/// Parsed command-line arguments.
+#[derive(Clone, Default, Debug)]
+pub struct CliArgs {
+    pub config_dir: Option<PathBuf>,
+    pub world: Option<PathBuf>,
+    pub windowed: bool,
+    pub size: Option<UVec2>,
+    pub startup_commands: Vec<StartupCommand>,
+    pub help: bool,
+}
+
+impl CliArgs {
+    /// Parse the arguments this process was launched with.
+    pub fn from_env() -> Result<Self, CliError> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            // Support both `--flag value` and `--flag=value`
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(flag.clone()))
+            };
+
+            match flag.as_str() {
+                "--config-dir" => cli.config_dir = Some(PathBuf::from(value()?)),
+                "--world" => cli.world = Some(PathBuf::from(value()?)),
+                "--windowed" => cli.windowed = true,
+                "--size" => {
+                    let size = value()?;
+                    cli.size = Some(parse_size(&size).ok_or(CliError::InvalidSize(size))?);
+                    cli.windowed = true;
+                }
+                "--exec" => cli.startup_commands.push(StartupCommand::Exec(value()?)),
+                "--script" => cli
+                    .startup_commands
+                    .push(StartupCommand::Script(PathBuf::from(value()?))),
+                "-h" | "--help" => cli.help = true,
+                _ => return Err(CliError::UnknownArgument(flag.clone())),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+fn parse_size(size: &str) -> Option<UVec2> {
+    let (width, height) = size.split_once(['x', 'X'])?;
+    let size = UVec2::new(width.trim().parse().ok()?, height.trim().parse().ok()?);
+    (size.x > 0 && size.y > 0).then_some(size)
+}
+
+/// Queues console commands passed with `--exec` and `--script` once startup has finished, after
+/// the world's `autoexec.cfg`.
+pub struct CliPlugin {
+    pub startup_commands: Vec<StartupCommand>,
+}
+
+impl Plugin for CliPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StartupCommands(self.startup_commands.clone()))
+            .add_systems(Startup, queue_startup_commands.after(run_autoexec));
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct StartupCommands(Vec<StartupCommand>);
+
+fn queue_startup_commands(mut commands: Commands, startup_commands: Res<StartupCommands>) {
+    for startup_command in &startup_commands.0 {
+        match startup_command {
+            StartupCommand::Exec(input) => {
+                info!("Running startup command: {input}");
+                commands.write_message(ConsoleCommandQueued {
+                    input: input.clone(),
+                });
+            }
+            StartupCommand::Script(path) => {
+                let path = path.clone();
+                commands.queue(move |world: &mut World| run_startup_script(world, &path));
+            }
+        }
+    }
+    commands.remove_resource::<StartupCommands>();
+}
@@ -1,3 +1,5 @@
 mod resources;
+mod simulation;
 
 pub use resources::*;
+pub use simulation::*;
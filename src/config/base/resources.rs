@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::config::{ConfigMigration, SimulationConfig, VersionedConfig, add_schema_version};
+
 #[derive(Resource, Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize, Reflect)]
 pub struct ConfigPath(pub PathBuf);
 
@@ -17,21 +19,31 @@ pub struct ActiveWorldPath(pub PathBuf);
 #[derive(Resource, Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize, Reflect)]
 pub struct ParticleTypesFile(pub PathBuf);
 
-#[derive(Resource, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[derive(Resource, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct InitConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     pub settings_init_file: PathBuf,
     pub active_world_path: PathBuf,
+    #[serde(default)]
+    pub simulation: SimulationConfig,
 }
 
 impl Default for InitConfig {
     fn default() -> Self {
         Self {
+            schema_version: Self::SCHEMA_VERSION,
             settings_init_file: PathBuf::from("settings.toml"),
             active_world_path: PathBuf::from("default"),
+            simulation: SimulationConfig::default(),
         }
     }
 }
 
+impl VersionedConfig for InitConfig {
+    const MIGRATIONS: &'static [ConfigMigration] = &[add_schema_version];
+}
+
 impl InitConfig {
     pub fn active_world_path(&self) -> &PathBuf {
         &self.active_world_path
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Simulation parameters read from `init.toml`, applying to every world unless overridden.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    /// Side length of a chunk in particles. Must be a power of two.
+    pub chunk_size: u32,
+    /// Side length of the map in chunks. Must be a power of two.
+    pub map_size: u32,
+    pub frame_limit: FrameLimitConfig,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: 64,
+            map_size: 32,
+            frame_limit: FrameLimitConfig::default(),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Apply per-world overrides on top of these defaults.
+    pub fn with_overrides(&self, overrides: &WorldSimulationConfig) -> Self {
+        Self {
+            chunk_size: overrides.chunk_size.unwrap_or(self.chunk_size),
+            map_size: overrides.map_size.unwrap_or(self.map_size),
+            frame_limit: overrides
+                .frame_limit
+                .clone()
+                .unwrap_or_else(|| self.frame_limit.clone()),
+        }
+    }
+
+    /// Whether `size` can be used as a chunk or map size.
+    pub fn is_valid_size(size: u32) -> bool {
+        size.is_power_of_two()
+    }
+
+    /// This configuration with invalid chunk and map sizes replaced by the defaults.
+    pub fn sanitized(&self) -> Self {
+        let default = Self::default();
+        let valid_or = |size: u32, default: u32| {
+            if Self::is_valid_size(size) {
+                size
+            } else {
+                default
+            }
+        };
+        Self {
+            chunk_size: valid_or(self.chunk_size, default.chunk_size),
+            map_size: valid_or(self.map_size, default.map_size),
+            frame_limit: self.frame_limit.clone(),
+        }
+    }
+}
+
+/// TOML-friendly frame limiter setting, persisted as `enabled = false` when uncapped.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FrameLimitConfig {
+    pub enabled: bool,
+    pub fps: f64,
+}
+
+impl Default for FrameLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fps: 60.0,
+        }
+    }
+}
+
+impl FrameLimitConfig {
+    pub fn off() -> Self {
+        Self {
+            enabled: false,
+            ..default()
+        }
+    }
+
+    pub fn fps(fps: f64) -> Self {
+        Self { enabled: true, fps }
+    }
+}
+
+/// Per-world overrides for [`SimulationConfig`], read from the world's `world.toml`.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldSimulationConfig {
+    pub chunk_size: Option<u32>,
+    pub map_size: Option<u32>,
+    pub frame_limit: Option<FrameLimitConfig>,
+}
+
+/// Runtime copy of the simulation configuration for the settings UI and console.
+#[derive(Resource, Clone, Debug)]
+pub struct SimulationSettings {
+    /// Defaults from `init.toml`.
+    pub defaults: SimulationConfig,
+    /// Overrides from the active world's `world.toml`.
+    pub world: WorldSimulationConfig,
+    /// The configuration the simulation plugin was built with. Chunk and map size changes only
+    /// take effect after a restart.
+    pub startup: SimulationConfig,
+}
+
+impl SimulationSettings {
+    pub fn new(defaults: SimulationConfig, world: WorldSimulationConfig) -> Self {
+        let startup = defaults.with_overrides(&world).sanitized();
+        Self {
+            defaults,
+            world,
+            startup,
+        }
+    }
+
+    /// The configuration currently in effect, with world overrides applied.
+    pub fn effective(&self) -> SimulationConfig {
+        self.defaults.with_overrides(&self.world).sanitized()
+    }
+
+    /// Whether the chunk or map size differs from what the simulation was built with.
+    pub fn requires_restart(&self) -> bool {
+        let effective = self.effective();
+        effective.chunk_size != self.startup.chunk_size
+            || effective.map_size != self.startup.map_size
+    }
+
+    /// Set the frame limit on whichever layer currently provides it.
+    pub fn set_frame_limit(&mut self, frame_limit: FrameLimitConfig) {
+        if self.world.frame_limit.is_some() {
+            self.world.frame_limit = Some(frame_limit);
+        } else {
+            self.defaults.frame_limit = frame_limit;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod base;
+mod schema;
 mod settings;
 pub mod setup;
 mod world;
@@ -8,12 +9,15 @@ use std::path::PathBuf;
 use bevy::prelude::*;
 
 pub use base::*;
+pub use schema::*;
 pub use settings::*;
 pub use setup::*;
 pub use world::*;
 
 pub struct ConfigPlugin {
     pub config_path: PathBuf,
+    /// Open this world instead of the one recorded in `init.toml`, without changing `init.toml`.
+    pub active_world: Option<PathBuf>,
 }
 
 impl Default for ConfigPlugin {
@@ -21,7 +25,17 @@ impl Default for ConfigPlugin {
         let config_path = PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".to_string()))
             .join(".config")
             .join("bevy_falling_sand");
-        Self { config_path }
+        Self {
+            config_path,
+            active_world: None,
+        }
+    }
+}
+
+impl ConfigPlugin {
+    /// The simulation configuration for the active world, for constructing simulation plugins.
+    pub fn simulation_config(&self) -> SimulationConfig {
+        read_simulation_config(&self.config_path, self.active_world.as_deref())
     }
 }
 
@@ -30,6 +44,7 @@ impl Plugin for ConfigPlugin {
         app.add_plugins((
             ConfigSetupPlugin {
                 config_path: self.config_path.clone(),
+                active_world: self.active_world.clone(),
             },
             WorldPersistencePlugin,
             SettingsPersistencePlugin,
@@ -0,0 +1,501 @@
+//! Schema versioning, migration and validation for the editor's TOML config files.
+//!
+//! Each versioned config carries a `schema_version` field and an ordered chain of migrations.
+//! Before a config file is handed to `bevy_persistent`, [`prepare_config_file`] upgrades it to
+//! the current schema (backing up the original first) and repairs any fields that would
+//! otherwise fail to deserialize, logging each problem with its line number.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Upgrades a raw config table from schema version `n` to `n + 1`.
+pub type ConfigMigration = fn(&mut toml::Table);
+
+/// Name of the schema version key present at the root of every versioned config file.
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// A config file with an explicit schema version and a chain of migrations.
+pub trait VersionedConfig: Serialize + DeserializeOwned + Default {
+    /// Migrations in order. `MIGRATIONS[n]` upgrades a file from version `n` to `n + 1`.
+    ///
+    /// Files written before schema versioning existed have no `schema_version` key and are
+    /// treated as version 0.
+    const MIGRATIONS: &'static [ConfigMigration];
+
+    /// The current schema version, derived from the number of migrations.
+    const SCHEMA_VERSION: u32 = Self::MIGRATIONS.len() as u32;
+}
+
+/// A problem found while validating a config file.
+#[derive(Clone, Debug)]
+struct ConfigDiagnostic {
+    key_path: Vec<String>,
+    message: String,
+}
+
+/// Migrate and validate the config file at `path` so it can be loaded as `T`.
+///
+/// - Files with an older `schema_version` are backed up to `<file>.v<version>.bak`, migrated
+///   and rewritten.
+/// - Fields that fail to deserialize are reset to their defaults and logged with their line
+///   number. The original file is backed up to `<file>.invalid.bak` before it is rewritten.
+/// - Unknown fields are logged and left untouched.
+/// - Files that are not valid TOML are backed up and replaced with defaults.
+///
+/// Missing files are left alone so the caller can create them with defaults.
+pub fn prepare_config_file<T: VersionedConfig>(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let file = path.display();
+
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            error!("Failed to read config file {file}: {error}");
+            return;
+        }
+    };
+
+    let mut table = match toml::from_str::<toml::Table>(&text) {
+        Ok(table) => table,
+        Err(error) => {
+            let line = error
+                .span()
+                .map(|span| line_of_offset(&text, span.start))
+                .unwrap_or(1);
+            error!(
+                "{file}:{line}: {}. Replacing with defaults.",
+                error.message().trim()
+            );
+            backup_config_file(path, "invalid");
+            write_config_file(path, &T::default());
+            return;
+        }
+    };
+
+    let version = table
+        .get(SCHEMA_VERSION_KEY)
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(0)
+        .max(0) as u32;
+
+    let migrated = if version < T::SCHEMA_VERSION {
+        backup_config_file(path, &format!("v{version}"));
+        for migration in &T::MIGRATIONS[version as usize..] {
+            migration(&mut table);
+        }
+        table.insert(
+            SCHEMA_VERSION_KEY.to_string(),
+            toml::Value::Integer(T::SCHEMA_VERSION.into()),
+        );
+        info!(
+            "Migrated {file} from schema version {version} to {}",
+            T::SCHEMA_VERSION
+        );
+        true
+    } else {
+        if version > T::SCHEMA_VERSION {
+            warn!(
+                "{file} has schema version {version}, but this editor only understands up to {}. \
+                 Loading it on a best-effort basis.",
+                T::SCHEMA_VERSION
+            );
+        }
+        false
+    };
+
+    let (config, invalid) = deserialize_with_repairs::<T>(&table);
+    for diagnostic in &invalid {
+        log_diagnostic(&file.to_string(), &text, diagnostic);
+    }
+    for diagnostic in unknown_fields(&table, &config, &invalid) {
+        log_diagnostic(&file.to_string(), &text, &diagnostic);
+    }
+
+    if !invalid.is_empty() && !migrated {
+        backup_config_file(path, "invalid");
+    }
+    if migrated || !invalid.is_empty() {
+        write_config_file(path, &config);
+    }
+}
+
+/// Deserialize `table` into `T`, replacing any field that fails to deserialize with its default.
+///
+/// Starting from the serialized defaults, each value from `table` is applied in turn and kept
+/// only if the result still deserializes. Tables are first tried as a whole (so enum variants
+/// and other multi-key values are accepted together) and only split into their fields if that
+/// fails.
+fn deserialize_with_repairs<T: VersionedConfig>(table: &toml::Table) -> (T, Vec<ConfigDiagnostic>) {
+    if let Ok(config) = toml::Value::Table(table.clone()).try_into::<T>() {
+        return (config, Vec::new());
+    }
+
+    let mut merged = match toml::Value::try_from(T::default()) {
+        Ok(toml::Value::Table(defaults)) => defaults,
+        _ => toml::Table::new(),
+    };
+    let mut diagnostics = Vec::new();
+    apply_with_repairs::<T>(&mut merged, &mut Vec::new(), table, &mut diagnostics);
+
+    let config = toml::Value::Table(merged)
+        .try_into::<T>()
+        .unwrap_or_default();
+    (config, diagnostics)
+}
+
+fn apply_with_repairs<T: VersionedConfig>(
+    merged: &mut toml::Table,
+    key_path: &mut Vec<String>,
+    user: &toml::Table,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    for (key, value) in user {
+        key_path.push(key.clone());
+
+        let previous = get_at(merged, key_path).cloned();
+        set_at(merged, key_path, value.clone());
+
+        if let Err(error) = toml::Value::Table(merged.clone()).try_into::<T>() {
+            restore_at(merged, key_path, previous.clone());
+            match (value, previous) {
+                (toml::Value::Table(user_child), Some(toml::Value::Table(_))) => {
+                    apply_with_repairs::<T>(merged, key_path, user_child, diagnostics);
+                }
+                _ => diagnostics.push(ConfigDiagnostic {
+                    key_path: key_path.clone(),
+                    message: format!(
+                        "invalid value for `{}` ({}), using the default",
+                        key_path.join("."),
+                        error.message().trim()
+                    ),
+                }),
+            }
+        }
+
+        key_path.pop();
+    }
+}
+
+/// Find keys in `table` that were dropped when deserializing into `config`.
+fn unknown_fields<T: VersionedConfig>(
+    table: &toml::Table,
+    config: &T,
+    already_reported: &[ConfigDiagnostic],
+) -> Vec<ConfigDiagnostic> {
+    let Ok(toml::Value::Table(known)) = toml::Value::try_from(config) else {
+        return Vec::new();
+    };
+    let mut diagnostics = Vec::new();
+    collect_unknown_fields(table, &known, &mut Vec::new(), &mut diagnostics);
+    diagnostics.retain(|diagnostic| {
+        !already_reported
+            .iter()
+            .any(|reported| diagnostic.key_path.starts_with(&reported.key_path))
+    });
+    diagnostics
+}
+
+fn collect_unknown_fields(
+    table: &toml::Table,
+    known: &toml::Table,
+    key_path: &mut Vec<String>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    for (key, value) in table {
+        key_path.push(key.clone());
+        match (value, known.get(key)) {
+            (_, None) => diagnostics.push(ConfigDiagnostic {
+                key_path: key_path.clone(),
+                message: format!("unknown field `{}` is ignored", key_path.join(".")),
+            }),
+            (toml::Value::Table(child), Some(toml::Value::Table(known_child))) => {
+                collect_unknown_fields(child, known_child, key_path, diagnostics);
+            }
+            _ => {}
+        }
+        key_path.pop();
+    }
+}
+
+fn log_diagnostic(file: &str, text: &str, diagnostic: &ConfigDiagnostic) {
+    match line_of_key_path(text, &diagnostic.key_path) {
+        Some(line) => warn!("{file}:{line}: {}", diagnostic.message),
+        None => warn!("{file}: {}", diagnostic.message),
+    }
+}
+
+fn get_at<'a>(table: &'a toml::Table, key_path: &[String]) -> Option<&'a toml::Value> {
+    let (key, parents) = key_path.split_last()?;
+    let mut current = table;
+    for parent in parents {
+        current = current.get(parent)?.as_table()?;
+    }
+    current.get(key)
+}
+
+fn set_at(table: &mut toml::Table, key_path: &[String], value: toml::Value) {
+    let Some((key, parents)) = key_path.split_last() else {
+        return;
+    };
+    let mut current = table;
+    for parent in parents {
+        let entry = current
+            .entry(parent.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        current = entry.as_table_mut().expect("entry was just made a table");
+    }
+    current.insert(key.clone(), value);
+}
+
+fn restore_at(table: &mut toml::Table, key_path: &[String], previous: Option<toml::Value>) {
+    match previous {
+        Some(value) => set_at(table, key_path, value),
+        None => {
+            let Some((key, parents)) = key_path.split_last() else {
+                return;
+            };
+            let mut current = table;
+            for parent in parents {
+                match current.get_mut(parent).and_then(toml::Value::as_table_mut) {
+                    Some(child) => current = child,
+                    None => return,
+                }
+            }
+            current.remove(key);
+        }
+    }
+}
+
+/// Migration from version 0 for configs whose first versioned schema only adds `schema_version`.
+pub fn add_schema_version(_table: &mut toml::Table) {}
+
+/// Move the value at `from` to `to` if `to` is not already set. Used by migrations to rename keys.
+pub fn rename_key(table: &mut toml::Table, from: &[&str], to: &[&str]) {
+    let from: Vec<String> = from.iter().map(|key| key.to_string()).collect();
+    let to: Vec<String> = to.iter().map(|key| key.to_string()).collect();
+    let Some(value) = get_at(table, &from).cloned() else {
+        return;
+    };
+    restore_at(table, &from, None);
+    if get_at(table, &to).is_none() {
+        set_at(table, &to, value);
+    }
+}
+
+/// Best-effort lookup of the line a key path is defined on, falling back to its parent tables.
+fn line_of_key_path(text: &str, key_path: &[String]) -> Option<usize> {
+    (1..=key_path.len())
+        .rev()
+        .find_map(|len| line_of_key(text, &key_path[..len]))
+}
+
+fn line_of_key(text: &str, key_path: &[String]) -> Option<usize> {
+    let (key, parents) = key_path.split_last()?;
+    let mut current_table: Vec<String> = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            let header = line
+                .split(']')
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches('[');
+            current_table = header.split('.').map(normalize_key).collect();
+            if current_table == key_path {
+                return Some(index + 1);
+            }
+            continue;
+        }
+        if current_table == parents
+            && let Some((lhs, _)) = line.split_once('=')
+        {
+            let lhs: Vec<String> = lhs.split('.').map(normalize_key).collect();
+            if lhs.first() == Some(key) {
+                return Some(index + 1);
+            }
+        }
+    }
+    None
+}
+
+fn normalize_key(key: &str) -> String {
+    key.trim().trim_matches('"').trim_matches('\'').to_string()
+}
+
+fn line_of_offset(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+fn backup_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{suffix}.bak"));
+    path.with_file_name(file_name)
+}
+
+fn backup_config_file(path: &Path, suffix: &str) {
+    let backup = backup_path(path, suffix);
+    match fs::copy(path, &backup) {
+        Ok(_) => info!("Backed up {} to {}", path.display(), backup.display()),
+        Err(error) => error!(
+            "Failed to back up {} to {}: {error}",
+            path.display(),
+            backup.display()
+        ),
+    }
+}
+
+fn write_config_file<T: Serialize>(path: &Path, config: &T) {
+    let result = toml::to_string_pretty(config)
+        .map_err(|error| error.to_string())
+        .and_then(|text| fs::write(path, text).map_err(|error| error.to_string()));
+    if let Err(error) = result {
+        error!("Failed to write config file {}: {error}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    #[serde(default)]
+    struct TestConfig {
+        schema_version: u32,
+        speed: u32,
+        view: TestView,
+    }
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    #[serde(default)]
+    struct TestView {
+        zoom: f32,
+    }
+
+    impl VersionedConfig for TestConfig {
+        const MIGRATIONS: &'static [ConfigMigration] = &[v0_rename_rate_to_speed];
+    }
+
+    fn v0_rename_rate_to_speed(table: &mut toml::Table) {
+        rename_key(table, &["rate"], &["speed"]);
+    }
+
+    /// A config file path in the temp directory that is removed along with its backups on drop.
+    struct TempConfigFile(PathBuf);
+
+    impl TempConfigFile {
+        fn new(name: &str, text: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("schema-test-{}-{name}.toml", std::process::id()));
+            fs::write(&path, text).unwrap();
+            Self(path)
+        }
+
+        fn read(&self) -> TestConfig {
+            toml::from_str(&fs::read_to_string(&self.0).unwrap()).unwrap()
+        }
+    }
+
+    impl Drop for TempConfigFile {
+        fn drop(&mut self) {
+            for suffix in ["v0", "invalid"] {
+                let _ = fs::remove_file(backup_path(&self.0, suffix));
+            }
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn table(text: &str) -> toml::Table {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn rename_key_moves_nested_values() {
+        let mut config = table("[brush]\nbtype = \"circle\"\nsize = 3");
+        rename_key(&mut config, &["brush", "btype"], &["painter", "shape"]);
+        assert_eq!(
+            config,
+            table("[brush]\nsize = 3\n[painter]\nshape = \"circle\"")
+        );
+    }
+
+    #[test]
+    fn rename_key_keeps_existing_destination() {
+        let mut config = table("old = 1\nnew = 2");
+        rename_key(&mut config, &["old"], &["new"]);
+        assert_eq!(config, table("new = 2"));
+    }
+
+    #[test]
+    fn rename_key_ignores_missing_source() {
+        let mut config = table("new = 2");
+        rename_key(&mut config, &["old"], &["new"]);
+        assert_eq!(config, table("new = 2"));
+    }
+
+    #[test]
+    fn prepare_config_file_migrates_and_backs_up() {
+        let file = TempConfigFile::new("migrate", "rate = 5\n[view]\nzoom = 2.0\n");
+        prepare_config_file::<TestConfig>(&file.0);
+
+        assert_eq!(
+            file.read(),
+            TestConfig {
+                schema_version: 1,
+                speed: 5,
+                view: TestView { zoom: 2.0 },
+            }
+        );
+        assert!(backup_path(&file.0, "v0").is_file());
+    }
+
+    #[test]
+    fn prepare_config_file_repairs_invalid_fields() {
+        let file = TempConfigFile::new(
+            "repair",
+            "schema_version = 1\nspeed = \"fast\"\n[view]\nzoom = 2.0\n",
+        );
+        prepare_config_file::<TestConfig>(&file.0);
+
+        assert_eq!(
+            file.read(),
+            TestConfig {
+                schema_version: 1,
+                speed: 0,
+                view: TestView { zoom: 2.0 },
+            }
+        );
+        assert!(backup_path(&file.0, "invalid").is_file());
+    }
+
+    #[test]
+    fn prepare_config_file_leaves_current_files_alone() {
+        let text = "schema_version = 1\nspeed = 5\n";
+        let file = TempConfigFile::new("current", text);
+        prepare_config_file::<TestConfig>(&file.0);
+
+        assert_eq!(fs::read_to_string(&file.0).unwrap(), text);
+        assert!(!backup_path(&file.0, "v0").exists());
+    }
+
+    #[test]
+    fn prepare_config_file_replaces_unparseable_files() {
+        let file = TempConfigFile::new("unparseable", "speed = [\n");
+        prepare_config_file::<TestConfig>(&file.0);
+
+        assert_eq!(file.read(), TestConfig::default());
+        assert!(backup_path(&file.0, "invalid").is_file());
+    }
+}
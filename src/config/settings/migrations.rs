@@ -0,0 +1,69 @@
+use crate::config::{ConfigMigration, SettingsConfig, VersionedConfig, rename_key};
+
+impl VersionedConfig for SettingsConfig {
+    const MIGRATIONS: &'static [ConfigMigration] = &[v0_rename_brush_to_painter];
+}
+
+/// Version 0 predates the painter tool rename, when the painter was called the brush.
+fn v0_rename_brush_to_painter(table: &mut toml::Table) {
+    rename_key(table, &["brush"], &["painter"]);
+    rename_key(table, &["painter", "btype"], &["painter", "shape"]);
+    rename_key(table, &["keys", "brush"], &["keys", "painter"]);
+    rename_key(
+        table,
+        &["keys", "painter", "toggle_brush_mode"],
+        &["keys", "painter", "toggle_mode"],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(text: &str) -> toml::Table {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn v0_renames_brush_to_painter() {
+        let mut config = table(
+            r#"
+            [brush]
+            btype = "Circle"
+            size = 2.0
+
+            [keys.brush]
+            toggle_brush_mode = "KeyM"
+            "#,
+        );
+        v0_rename_brush_to_painter(&mut config);
+        assert_eq!(
+            config,
+            table(
+                r#"
+                [painter]
+                shape = "Circle"
+                size = 2.0
+
+                [keys.painter]
+                toggle_mode = "KeyM"
+                "#,
+            )
+        );
+    }
+
+    #[test]
+    fn v0_keeps_painter_settings_that_already_exist() {
+        let mut config = table(
+            r#"
+            [brush]
+            size = 2.0
+
+            [painter]
+            size = 4.0
+            "#,
+        );
+        v0_rename_brush_to_painter(&mut config);
+        assert_eq!(config, table("[painter]\nsize = 4.0"));
+    }
+}
@@ -1,4 +1,7 @@
-use std::fmt;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
 
 use avian2d::{debug_render::ContactGizmoScale, prelude::PhysicsGizmos};
 use bevy::prelude::*;
@@ -7,6 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     camera::CameraKeyBindings,
+    config::VersionedConfig,
     tools::{
         brush::ToolBrushSize,
         earthquake::{EarthquakeConfiguration, EarthquakeFractureShape, EarthquakeShape},
@@ -61,20 +65,47 @@ impl From<MouseButton> for InputButton {
     }
 }
 
-#[derive(Resource, Default, Serialize, Deserialize)]
+#[derive(Resource, Serialize, Deserialize)]
 pub struct SettingsConfig {
-    #[serde(alias = "brush")]
+    #[serde(default)]
+    pub schema_version: u32,
     pub painter: PainterConfig,
     #[serde(default)]
     pub earthquake: EarthquakeConfig,
     pub bfs_debug: BevyFallingSandDebugConfig,
     pub avian_debug: AvianDebugConfig,
     pub keys: Keybindings,
+    /// Console aliases, mapping each alias name to the command line it expands to.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    #[serde(default)]
+    pub particle_editor: ParticleEditorConfig,
+}
+
+impl Default for SettingsConfig {
+    fn default() -> Self {
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            painter: PainterConfig::default(),
+            earthquake: EarthquakeConfig::default(),
+            bfs_debug: BevyFallingSandDebugConfig::default(),
+            avian_debug: AvianDebugConfig::default(),
+            keys: Keybindings::default(),
+            aliases: BTreeMap::new(),
+            particle_editor: ParticleEditorConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ParticleEditorConfig {
+    /// Names of the particle types pinned to the top of the particle editor list.
+    #[serde(default)]
+    pub favorites: BTreeSet<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PainterConfig {
-    #[serde(alias = "btype")]
     pub shape: PainterShape,
     pub mode: PainterSpawnState,
     pub size: ToolBrushSize,
@@ -141,7 +172,6 @@ impl Default for BevyFallingSandDebugConfig {
 pub struct Keybindings {
     pub camera: CameraKeyBindings,
     pub ui: UiKeyBindings,
-    #[serde(alias = "brush")]
     pub painter: PainterKeyBindings,
 }
 
@@ -149,7 +179,8 @@ pub struct Keybindings {
 ///
 /// Each `Option<T>` field from `PhysicsGizmos` is split into an `enabled` bool
 /// and a value, so that `None` can be persisted as `enabled = false` in TOML.
-#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[derive(Resource, Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
 pub struct AvianDebugConfig {
     pub axis_lengths: OptionalVec2,
     pub aabb_color: OptionalColor,
@@ -301,7 +332,7 @@ impl AvianDebugConfig {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
 pub struct OptionalColor {
     pub enabled: bool,
     #[serde(default = "default_color")]
@@ -355,7 +386,7 @@ impl Default for OptionalColor {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
 pub struct OptionalVec2 {
     pub enabled: bool,
     #[serde(default = "default_vec2")]
@@ -389,7 +420,7 @@ impl OptionalVec2 {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
 pub struct OptionalColorMultiplier {
     pub enabled: bool,
     #[serde(default = "default_color_multiplier")]
@@ -419,7 +450,7 @@ impl OptionalColorMultiplier {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
 pub enum ContactNormalScale {
     Constant(f32),
     Scaled(f32),
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
 use bevy_falling_sand::debug::{ChunkColor, DebugDirtyRects, DebugParticleMap, DirtyRectColor};
 use bevy_persistent::Persistent;
@@ -5,9 +7,11 @@ use bevy_persistent::Persistent;
 use crate::{
     camera::CameraKeyBindings,
     config::{
-        AvianDebugConfig, BevyFallingSandDebugConfig, EarthquakeConfig, Keybindings, OptionalColor,
-        PainterConfig, SettingsConfig,
+        AvianDebugConfig, BevyFallingSandDebugConfig, EarthquakeConfig, InitConfig, Keybindings,
+        OptionalColor, PainterConfig, ParticleEditorConfig, SettingsConfig, SimulationSettings,
+        VersionedConfig,
     },
+    console_command::ConsoleCommandRegistry,
     tools::{
         brush::ToolBrushSize,
         earthquake::{
@@ -18,7 +22,7 @@ use crate::{
             PainterBrush, PainterConfiguration, PainterKeyBindings, PainterShape, PainterSpawnState,
         },
     },
-    ui::UiKeyBindings,
+    ui::{FavoriteParticles, UiKeyBindings},
 };
 
 pub(super) struct SavePlugin;
@@ -31,6 +35,9 @@ impl Plugin for SavePlugin {
             .add_observer(on_prepare_save_bfs_debug)
             .add_observer(on_prepare_save_avian_debug)
             .add_observer(on_prepare_save_keys)
+            .add_observer(on_prepare_save_aliases)
+            .add_observer(on_prepare_save_particle_editor)
+            .add_observer(on_save_init_simulation)
             .add_observer(on_prepare_save_settings)
             .add_observer(on_save_settings);
     }
@@ -51,6 +58,8 @@ pub struct SaveSettingsBuilder {
     pub bfs_debug: Option<BevyFallingSandDebugConfig>,
     pub avian_debug: Option<AvianDebugConfig>,
     pub keys: Option<Keybindings>,
+    pub aliases: Option<BTreeMap<String, String>>,
+    pub particle_editor: Option<ParticleEditorConfig>,
 }
 
 fn on_prepare_save_brush(
@@ -141,6 +150,42 @@ fn on_prepare_save_keys(
     });
 }
 
+fn on_prepare_save_aliases(
+    _trigger: On<PrepareSaveSettingsEvent>,
+    registry: Res<ConsoleCommandRegistry>,
+    mut builder: ResMut<SaveSettingsBuilder>,
+) {
+    builder.aliases = Some(
+        registry
+            .aliases()
+            .map(|(name, expansion)| (name.clone(), expansion.clone()))
+            .collect(),
+    );
+}
+
+fn on_prepare_save_particle_editor(
+    _trigger: On<PrepareSaveSettingsEvent>,
+    favorites: Res<FavoriteParticles>,
+    mut builder: ResMut<SaveSettingsBuilder>,
+) {
+    builder.particle_editor = Some(ParticleEditorConfig {
+        favorites: favorites.0.clone(),
+    });
+}
+
+/// Simulation defaults live in `init.toml` rather than `settings.toml`, so they are written
+/// directly instead of going through [`SaveSettingsBuilder`].
+fn on_save_init_simulation(
+    _trigger: On<PrepareSaveSettingsEvent>,
+    simulation: Res<SimulationSettings>,
+    mut persistent: ResMut<Persistent<InitConfig>>,
+) {
+    let defaults = simulation.defaults.clone();
+    persistent
+        .update(|init| init.simulation = defaults.clone())
+        .expect("Failed to write init config to disk");
+}
+
 fn on_prepare_save_settings(_trigger: On<PrepareSaveSettingsEvent>, mut commands: Commands) {
     commands.trigger(SaveSettingsEvent);
 }
@@ -152,6 +197,7 @@ fn on_save_settings(
 ) {
     persistent
         .set(SettingsConfig {
+            schema_version: SettingsConfig::SCHEMA_VERSION,
             painter: builder.painter.take().expect("painter config not set"),
             earthquake: builder
                 .earthquake
@@ -163,6 +209,11 @@ fn on_save_settings(
                 .take()
                 .expect("avian debug config not set"),
             keys: builder.keys.take().expect("Keybindings not set"),
+            aliases: builder.aliases.take().expect("aliases not set"),
+            particle_editor: builder
+                .particle_editor
+                .take()
+                .expect("particle editor config not set"),
         })
         .expect("Failed to save settings");
     persistent
@@ -1,4 +1,7 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use crate::{config::MediaPath, setup::SetupSystems};
 use bevy::prelude::*;
@@ -6,7 +9,8 @@ use bevy_falling_sand::prelude::ParticlePersistenceConfig;
 use bevy_persistent::{Persistent, StorageFormat};
 
 use super::{
-    ActiveSettingsPath, ActiveWorldPath, ConfigPath, InitConfig, SettingsConfig, WorldConfig,
+    ActiveSettingsPath, ActiveWorldPath, ConfigPath, InitConfig, SettingsConfig, SimulationConfig,
+    SimulationSettings, WorldConfig, prepare_config_file,
 };
 
 const MEDIA_PATH: &str = "media";
@@ -18,11 +22,19 @@ const WORLD_TOML_FILE: &str = "world.toml";
 
 pub struct ConfigSetupPlugin {
     pub config_path: PathBuf,
+    pub active_world: Option<PathBuf>,
 }
 
+/// The world to open for this session in place of `init.toml`'s `active_world_path`.
+#[derive(Resource, Clone, Debug)]
+struct ActiveWorldOverride(PathBuf);
+
 impl Plugin for ConfigSetupPlugin {
     fn build(&self, app: &mut App) {
         let config_path = self.config_path.clone();
+        if let Some(active_world) = &self.active_world {
+            app.insert_resource(ActiveWorldOverride(active_world.clone()));
+        }
 
         // Create base config path
         app.add_systems(
@@ -46,6 +58,8 @@ impl Plugin for ConfigSetupPlugin {
                 load_active_world_path,
                 // Load world.toml for the active world configuration
                 load_world_config_file,
+                // Resolve simulation defaults from init.toml with the world's overrides
+                load_simulation_settings,
                 // Configure bfs persistence to update from fallback path to active world path
                 configure_bfs_persistence,
             )
@@ -78,24 +92,27 @@ fn load_media_path(mut commands: Commands, config_path: Res<ConfigPath>) {
     commands.insert_resource(MediaPath(media_path));
 }
 
-/// Load init.toml to an `InitConfig` resource.
+/// Load init.toml to an `InitConfig` resource, migrating it to the current schema first.
 ///
 /// # Panics
 ///
 /// Panics if `init.toml` fails to load or be created
 fn load_init_config_file(mut commands: Commands, config_path: Res<ConfigPath>) {
+    let init_file_path = config_path.0.join(INIT_TOML_FILE);
+    prepare_config_file::<InitConfig>(&init_file_path);
+
     commands.insert_resource(
         Persistent::<InitConfig>::builder()
             .name("init")
             .format(StorageFormat::Toml)
-            .path(config_path.0.clone().join(INIT_TOML_FILE))
+            .path(init_file_path)
             .default(InitConfig::default())
             .build()
             .expect("Failed to load {INIT_TOML_FILE}"),
     );
 }
 
-/// Try to load the `settings.toml` file
+/// Try to load the `settings.toml` file, migrating it to the current schema first.
 ///
 /// # Panics
 ///
@@ -106,6 +123,7 @@ fn load_settings_config_file(
     init_config: Res<Persistent<InitConfig>>,
 ) {
     let settings_file_path = config_path.0.join(init_config.get().settings_init_file());
+    prepare_config_file::<SettingsConfig>(&settings_file_path);
 
     commands.insert_resource(
         Persistent::<SettingsConfig>::builder()
@@ -119,7 +137,7 @@ fn load_settings_config_file(
     commands.insert_resource(ActiveSettingsPath(settings_file_path));
 }
 
-/// Try to load the config data for this world.
+/// Try to load the config data for this world, preferring the world given on the command line.
 ///
 /// # Panics
 ///
@@ -128,11 +146,12 @@ fn load_active_world_path(
     mut commands: Commands,
     config_path: Res<ConfigPath>,
     init_config: Res<Persistent<InitConfig>>,
+    active_world_override: Option<Res<ActiveWorldOverride>>,
 ) {
-    let active_world_path = config_path
-        .0
-        .join(WORLD_PATH)
-        .join(init_config.get().active_world_path());
+    let active_world = active_world_override
+        .map(|active_world| active_world.0.clone())
+        .unwrap_or_else(|| init_config.get().active_world_path().clone());
+    let active_world_path = config_path.0.join(WORLD_PATH).join(active_world);
 
     fs::create_dir_all(&active_world_path).unwrap_or_else(|_| {
         panic!("Failed to create active world directory {active_world_path:?}",)
@@ -145,23 +164,75 @@ fn load_active_world_path(
     commands.insert_resource(ActiveWorldPath(active_world_path));
 }
 
-/// Try to load the `world.toml` file
+/// Try to load the `world.toml` file, migrating it to the current schema first.
 ///
 /// # Panics
 ///
 /// Panics if the `world.toml` file fails to load or be created.
 fn load_world_config_file(mut commands: Commands, active_world_path: Res<ActiveWorldPath>) {
+    let world_file_path = active_world_path.0.join(WORLD_TOML_FILE);
+    prepare_config_file::<WorldConfig>(&world_file_path);
+
     commands.insert_resource(
         Persistent::<WorldConfig>::builder()
             .name("world_meta")
             .format(StorageFormat::Toml)
-            .path(active_world_path.0.join(WORLD_TOML_FILE))
+            .path(world_file_path)
             .default(WorldConfig::default())
             .build()
             .expect("Failed to load {WORLD_TOML_FILE}"),
     );
 }
 
+fn load_simulation_settings(
+    mut commands: Commands,
+    init_config: Res<Persistent<InitConfig>>,
+    world_config: Res<Persistent<WorldConfig>>,
+) {
+    // The simulation was already built with the defaults in place of these, by
+    // `read_simulation_config`.
+    let effective = init_config
+        .get()
+        .simulation
+        .with_overrides(&world_config.get().simulation);
+    let sanitized = effective.sanitized();
+    if sanitized != effective {
+        warn!(
+            "Chunk and map size must be powers of two, got {} and {}; using {} and {}",
+            effective.chunk_size, effective.map_size, sanitized.chunk_size, sanitized.map_size
+        );
+    }
+    commands.insert_resource(SimulationSettings::new(
+        init_config.get().simulation.clone(),
+        world_config.get().simulation.clone(),
+    ));
+}
+
+/// Read the simulation configuration for the active world directly from disk.
+///
+/// Chunk and map size must be known before the falling sand plugin is constructed, which happens
+/// before any startup system runs. Unreadable files and invalid sizes fall back to defaults here;
+/// they are reported when the config files are loaded at startup.
+pub fn read_simulation_config(config_path: &Path, active_world: Option<&Path>) -> SimulationConfig {
+    fn read<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+        toml::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    let init_config = read::<InitConfig>(&config_path.join(INIT_TOML_FILE)).unwrap_or_default();
+    let world_config = read::<WorldConfig>(
+        &config_path
+            .join(WORLD_PATH)
+            .join(active_world.unwrap_or(init_config.active_world_path().as_path()))
+            .join(WORLD_TOML_FILE),
+    )
+    .unwrap_or_default();
+
+    init_config
+        .simulation
+        .with_overrides(&world_config.simulation)
+        .sanitized()
+}
+
 /// Set `bevy_falling_sand` persistence to use the active world's data path
 fn configure_bfs_persistence(
     active_world_path: Res<ActiveWorldPath>,
@@ -0,0 +1,44 @@
+use crate::{
+    config::{ConfigMigration, VersionedConfig, WorldConfig, add_schema_version},
+    particles::PARTICLE_MANIFEST_SCHEMA_VERSION,
+};
+
+impl VersionedConfig for WorldConfig {
+    const MIGRATIONS: &'static [ConfigMigration] =
+        &[add_schema_version, v1_keep_particle_manifest_schema];
+}
+
+/// Version 1 predates particle manifest schema v2. Those worlds keep writing v1 manifests, so
+/// tools that read them don't break.
+fn v1_keep_particle_manifest_schema(table: &mut toml::Table) {
+    table
+        .entry("particle_manifest_schema")
+        .or_insert(toml::Value::Integer(
+            PARTICLE_MANIFEST_SCHEMA_VERSION.into(),
+        ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_keeps_writing_v1_manifests() {
+        let mut config = toml::Table::new();
+        v1_keep_particle_manifest_schema(&mut config);
+        assert_eq!(
+            config.get("particle_manifest_schema"),
+            Some(&toml::Value::Integer(1))
+        );
+    }
+
+    #[test]
+    fn v1_keeps_a_chosen_manifest_schema() {
+        let mut config: toml::Table = toml::from_str("particle_manifest_schema = 2").unwrap();
+        v1_keep_particle_manifest_schema(&mut config);
+        assert_eq!(
+            config.get("particle_manifest_schema"),
+            Some(&toml::Value::Integer(2))
+        );
+    }
+}
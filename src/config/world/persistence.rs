@@ -3,7 +3,10 @@ use std::path::PathBuf;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::camera::ZoomSpeed;
+use crate::{
+    camera::ZoomSpeed, config::WorldSimulationConfig,
+    particles::PARTICLE_MANIFEST_V2_SCHEMA_VERSION,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CameraConfig {
@@ -27,15 +30,43 @@ impl Default for CameraConfig {
 
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
 pub struct WorldConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     pub camera: CameraConfig,
     pub particle_types_file: PathBuf,
+    #[serde(default)]
+    pub simulation: WorldSimulationConfig,
+    /// Schema version of the particle manifest written beside the particle types file: 1 for each
+    /// particle's id, name and category, or 2 to add the properties tools outside the editor
+    /// need. Worlds from before version 2 are migrated to keep writing version 1 until this is
+    /// changed.
+    #[serde(default = "default_particle_manifest_schema")]
+    pub particle_manifest_schema: u32,
+    /// Particle types assigned to hotbar slots.
+    #[serde(default)]
+    pub hotbar: Vec<HotbarSlotConfig>,
+}
+
+/// A hotbar slot and the name of the particle type assigned to it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotbarSlotConfig {
+    pub slot: usize,
+    pub particle: String,
+}
+
+fn default_particle_manifest_schema() -> u32 {
+    PARTICLE_MANIFEST_V2_SCHEMA_VERSION
 }
 
 impl Default for WorldConfig {
     fn default() -> Self {
         Self {
+            schema_version: Self::SCHEMA_VERSION,
             camera: CameraConfig::default(),
             particle_types_file: PathBuf::from("default.scn.ron"),
+            simulation: WorldSimulationConfig::default(),
+            particle_manifest_schema: PARTICLE_MANIFEST_V2_SCHEMA_VERSION,
+            hotbar: Vec::new(),
         }
     }
 }
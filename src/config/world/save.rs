@@ -6,7 +6,11 @@ use bevy_persistent::Persistent;
 
 use crate::{
     camera::{MainCamera, ZoomSpeed},
-    config::{CameraConfig, ParticleTypesFile, WorldConfig},
+    config::{
+        CameraConfig, HotbarSlotConfig, ParticleTypesFile, SimulationSettings, VersionedConfig,
+        WorldConfig, WorldSimulationConfig,
+    },
+    ui::Hotbar,
 };
 
 pub(super) struct SavePlugin;
@@ -16,6 +20,8 @@ impl Plugin for SavePlugin {
         app.init_resource::<WorldConfigBuilder>()
             .add_observer(on_prepare_save_camera)
             .add_observer(on_prepare_save_particle_types)
+            .add_observer(on_prepare_save_simulation)
+            .add_observer(on_prepare_save_hotbar)
             .add_observer(on_save_world)
             .add_observer(on_prepare_save_world);
     }
@@ -33,6 +39,8 @@ pub struct SaveWorldConfigEvent;
 pub struct WorldConfigBuilder {
     pub camera: Option<CameraConfig>,
     pub particle_types_file: Option<PathBuf>,
+    pub simulation: Option<WorldSimulationConfig>,
+    pub hotbar: Option<Vec<HotbarSlotConfig>>,
 }
 
 fn on_prepare_save_camera(
@@ -68,6 +76,22 @@ fn on_prepare_save_particle_types(
     ));
 }
 
+fn on_prepare_save_simulation(
+    _trigger: On<PrepareSaveWorldConfigEvent>,
+    mut builder: ResMut<WorldConfigBuilder>,
+    simulation: Res<SimulationSettings>,
+) {
+    builder.simulation = Some(simulation.world.clone());
+}
+
+fn on_prepare_save_hotbar(
+    _trigger: On<PrepareSaveWorldConfigEvent>,
+    mut builder: ResMut<WorldConfigBuilder>,
+    hotbar: Res<Hotbar>,
+) {
+    builder.hotbar = Some(hotbar.to_config());
+}
+
 fn on_prepare_save_world(_trigger: On<PrepareSaveWorldConfigEvent>, mut commands: Commands) {
     commands.trigger(SaveWorldConfigEvent);
 }
@@ -78,11 +102,18 @@ fn on_save_world(
     mut persistent: ResMut<Persistent<WorldConfig>>,
 ) {
     let world_config = WorldConfig {
+        schema_version: WorldConfig::SCHEMA_VERSION,
         camera: builder.camera.take().expect("Camera config not set"),
         particle_types_file: builder
             .particle_types_file
             .take()
             .expect("Particle types file not set"),
+        simulation: builder
+            .simulation
+            .take()
+            .expect("Simulation config not set"),
+        particle_manifest_schema: persistent.get().particle_manifest_schema,
+        hotbar: builder.hotbar.take().expect("Hotbar config not set"),
     };
 
     persistent
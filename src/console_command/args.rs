@@ -0,0 +1,674 @@
+//! Declarative argument schemas for console commands.
+//!
+//! A command that returns an [`ArgSchema`] from [`ConsoleCommand::args`] has its arguments
+//! validated before it runs, gets a generated usage line in `help`, and gets completions for
+//! choices and flags for free.
+//!
+//! [`ConsoleCommand::args`]: super::ConsoleCommand::args
+
+use std::{
+    fmt::{self, Write},
+    ops::{Bound, RangeBounds},
+};
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use thiserror::Error;
+
+use super::commands::helpers::parse_position;
+
+/// Range for values that must be strictly greater than zero.
+pub const POSITIVE: (Bound<f64>, Bound<f64>) = (Bound::Excluded(0.0), Bound::Unbounded);
+
+/// Range for values strictly between zero and one.
+pub const UNIT_EXCLUSIVE: (Bound<f64>, Bound<f64>) = (Bound::Excluded(0.0), Bound::Excluded(1.0));
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ArgError {
+    #[error("missing required argument <{0}>")]
+    Missing(&'static str),
+    #[error("invalid {name} '{value}': expected {expected}")]
+    Invalid {
+        name: &'static str,
+        value: String,
+        expected: String,
+    },
+    #[error("{name} must be {range}, got {value}")]
+    OutOfRange {
+        name: &'static str,
+        value: f64,
+        range: String,
+    },
+    #[error("<{name}> needs at least {min} values, got {count}")]
+    TooFew {
+        name: &'static str,
+        min: usize,
+        count: usize,
+    },
+    #[error("{0} requires a value")]
+    MissingFlagValue(&'static str),
+    #[error("unexpected argument '{0}'")]
+    Unexpected(String),
+}
+
+/// The type of a single argument.
+#[derive(Clone, Debug)]
+pub enum ArgKind {
+    /// Integer coordinates written as `x,y` (or as two separate values).
+    IPosition,
+    /// Float coordinates written as `x,y` (or as two separate values).
+    Position,
+    Int(NumberRange),
+    Float(NumberRange),
+    /// One of a fixed set of values, matched case-insensitively.
+    Choice(&'static [&'static str]),
+    Text,
+}
+
+#[derive(Clone, Debug)]
+pub struct NumberRange {
+    start: Bound<f64>,
+    end: Bound<f64>,
+}
+
+impl NumberRange {
+    fn new(range: impl RangeBounds<f64>) -> Self {
+        Self {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        }
+    }
+
+    fn contains(&self, value: f64) -> bool {
+        (self.start, self.end).contains(&value)
+    }
+}
+
+impl fmt::Display for NumberRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.start, self.end) {
+            (Bound::Excluded(min), Bound::Unbounded) => write!(f, "greater than {min}"),
+            (Bound::Included(min), Bound::Unbounded) => write!(f, "at least {min}"),
+            (Bound::Unbounded, Bound::Excluded(max)) => write!(f, "less than {max}"),
+            (Bound::Unbounded, Bound::Included(max)) => write!(f, "at most {max}"),
+            (Bound::Excluded(min), Bound::Excluded(max)) => {
+                write!(f, "greater than {min} and less than {max}")
+            }
+            (Bound::Included(min), Bound::Included(max)) => write!(f, "between {min} and {max}"),
+            (Bound::Included(min), Bound::Excluded(max)) => {
+                write!(f, "at least {min} and less than {max}")
+            }
+            (Bound::Excluded(min), Bound::Included(max)) => {
+                write!(f, "greater than {min} and at most {max}")
+            }
+            (Bound::Unbounded, Bound::Unbounded) => write!(f, "any number"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PositionalSpec {
+    name: &'static str,
+    kind: ArgKind,
+    required: bool,
+    /// Collects every remaining argument, at least `min` of them.
+    variadic: bool,
+    min: usize,
+    /// Remaining words are taken as given, even if they look like flags of this command.
+    verbatim: bool,
+}
+
+#[derive(Clone, Debug)]
+struct FlagSpec {
+    name: &'static str,
+    value: Option<ArgKind>,
+}
+
+/// Declares the positional arguments and flags a command accepts.
+#[derive(Clone, Debug, Default)]
+pub struct ArgSchema {
+    positional: Vec<PositionalSpec>,
+    flags: Vec<FlagSpec>,
+}
+
+impl ArgSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(mut self, name: &'static str, kind: ArgKind, required: bool) -> Self {
+        self.positional.push(PositionalSpec {
+            name,
+            kind,
+            required,
+            variadic: false,
+            min: 0,
+            verbatim: false,
+        });
+        self
+    }
+
+    /// A required positional argument.
+    pub fn arg(self, name: &'static str, kind: ArgKind) -> Self {
+        self.push(name, kind, true)
+    }
+
+    /// An optional positional argument. Optional arguments must come after required ones.
+    pub fn optional(self, name: &'static str, kind: ArgKind) -> Self {
+        self.push(name, kind, false)
+    }
+
+    /// A trailing argument that collects all remaining values, such as the vertices of a
+    /// polygon. It is optional if `min` is zero.
+    pub fn repeated(mut self, name: &'static str, kind: ArgKind, min: usize) -> Self {
+        self.positional.push(PositionalSpec {
+            name,
+            kind,
+            required: min > 0,
+            variadic: true,
+            min,
+            verbatim: false,
+        });
+        self
+    }
+
+    /// A required trailing argument that collects all remaining words.
+    pub fn rest(self, name: &'static str) -> Self {
+        self.repeated(name, ArgKind::Text, 1)
+    }
+
+    /// A required trailing command line, such as the command run by `after`. Flags of this
+    /// command are only recognized before the command starts, so the command keeps its own.
+    ///
+    /// Arguments before the command are assumed to be single words.
+    pub fn command(mut self, name: &'static str) -> Self {
+        self = self.rest(name);
+        if let Some(spec) = self.positional.last_mut() {
+            spec.verbatim = true;
+        }
+        self
+    }
+
+    /// A boolean flag such as `--overwrite`.
+    pub fn flag(mut self, name: &'static str) -> Self {
+        self.flags.push(FlagSpec { name, value: None });
+        self
+    }
+
+    /// A flag that takes a value, such as `--align center`.
+    pub fn flag_with_value(mut self, name: &'static str, kind: ArgKind) -> Self {
+        self.flags.push(FlagSpec {
+            name,
+            value: Some(kind),
+        });
+        self
+    }
+
+    /// The argument part of the usage line, e.g. `<x>,<y> <radius> [--overwrite]`.
+    pub fn usage(&self) -> String {
+        let mut usage = String::new();
+        for spec in &self.positional {
+            let placeholder = match &spec.kind {
+                ArgKind::IPosition | ArgKind::Position if spec.name == "position" => {
+                    "<x>,<y>".to_string()
+                }
+                ArgKind::IPosition | ArgKind::Position => {
+                    format!("<{0}_x>,<{0}_y>", spec.name)
+                }
+                ArgKind::Choice(choices) => format!("<{}>", choices.join("|")),
+                _ => format!("<{}>", spec.name),
+            };
+            let placeholder = match (&spec.kind, spec.variadic) {
+                (ArgKind::Text, true) => format!("<{}...>", spec.name),
+                (_, true) => format!("{placeholder}..."),
+                (_, false) => placeholder,
+            };
+            let placeholder = if spec.required {
+                placeholder
+            } else {
+                format!("[{placeholder}]")
+            };
+            let _ = write!(usage, " {placeholder}");
+        }
+        for flag in &self.flags {
+            match &flag.value {
+                Some(ArgKind::Choice(choices)) => {
+                    let _ = write!(usage, " [{} {}]", flag.name, choices.join("|"));
+                }
+                Some(_) => {
+                    let _ = write!(usage, " [{} <value>]", flag.name);
+                }
+                None => {
+                    let _ = write!(usage, " [{}]", flag.name);
+                }
+            }
+        }
+        usage.trim_start().to_string()
+    }
+
+    /// Validate `args` against this schema.
+    pub fn parse(&self, args: &[String]) -> Result<ParsedArgs, ArgError> {
+        let mut parsed = ParsedArgs::default();
+        let mut positional = Vec::with_capacity(args.len());
+
+        let verbatim_start = self.positional.iter().position(|spec| spec.verbatim);
+        let mut tokens = args.iter();
+        while let Some(token) = tokens.next() {
+            if verbatim_start.is_some_and(|start| positional.len() > start) {
+                positional.push(token.clone());
+                continue;
+            }
+            let Some(flag) = self.flags.iter().find(|flag| flag.name == token) else {
+                positional.push(token.clone());
+                continue;
+            };
+            let value = match &flag.value {
+                Some(kind) => {
+                    let value = tokens.next().ok_or(ArgError::MissingFlagValue(flag.name))?;
+                    Some(parse_value(flag.name, kind, std::slice::from_ref(value))?)
+                }
+                None => None,
+            };
+            parsed.flags.insert(flag.name, value);
+        }
+
+        let mut remaining = positional.as_slice();
+        for spec in &self.positional {
+            if remaining.is_empty() {
+                if spec.required {
+                    return Err(ArgError::Missing(spec.name));
+                }
+                continue;
+            }
+            if spec.variadic {
+                let mut values = Vec::new();
+                while !remaining.is_empty() {
+                    let taken = tokens_for(&spec.kind, remaining);
+                    values.push(parse_value(spec.name, &spec.kind, &remaining[..taken])?);
+                    remaining = &remaining[taken..];
+                }
+                if values.len() < spec.min {
+                    return Err(ArgError::TooFew {
+                        name: spec.name,
+                        min: spec.min,
+                        count: values.len(),
+                    });
+                }
+                parsed.values.insert(spec.name, ArgValue::List(values));
+                continue;
+            }
+            let taken = tokens_for(&spec.kind, remaining);
+            let value = parse_value(spec.name, &spec.kind, &remaining[..taken])?;
+            parsed.values.insert(spec.name, value);
+            remaining = &remaining[taken..];
+        }
+
+        match remaining.first() {
+            Some(unexpected) => Err(ArgError::Unexpected(unexpected.clone())),
+            None => Ok(parsed),
+        }
+    }
+
+    /// Suggest values for the next argument from choices and flags.
+    pub fn complete(&self, args: &[String]) -> Vec<String> {
+        if let Some(flag) = args
+            .last()
+            .and_then(|last| self.flags.iter().find(|flag| flag.name == last))
+        {
+            return match &flag.value {
+                Some(ArgKind::Choice(choices)) => {
+                    choices.iter().map(|choice| choice.to_string()).collect()
+                }
+                _ => Vec::new(),
+            };
+        }
+
+        let positional_count = args
+            .iter()
+            .filter(|arg| !self.flags.iter().any(|flag| flag.name == arg.as_str()))
+            .count();
+        let mut completions: Vec<String> = self
+            .flags
+            .iter()
+            .filter(|flag| !args.iter().any(|arg| arg == flag.name))
+            .map(|flag| flag.name.to_string())
+            .collect();
+        if let Some(PositionalSpec {
+            kind: ArgKind::Choice(choices),
+            ..
+        }) = self.positional.get(positional_count)
+        {
+            completions.extend(choices.iter().map(|choice| choice.to_string()));
+        }
+        completions
+    }
+}
+
+/// Positions may be written as one `x,y` token or as two tokens.
+fn tokens_for(kind: &ArgKind, remaining: &[String]) -> usize {
+    match kind {
+        ArgKind::IPosition | ArgKind::Position
+            if !remaining[0].contains(',')
+                && remaining
+                    .get(1)
+                    .is_some_and(|next| next.parse::<f64>().is_ok()) =>
+        {
+            2
+        }
+        _ => 1,
+    }
+}
+
+fn parse_value(
+    name: &'static str,
+    kind: &ArgKind,
+    tokens: &[String],
+) -> Result<ArgValue, ArgError> {
+    let invalid = |expected: &str| ArgError::Invalid {
+        name,
+        value: tokens.join(" "),
+        expected: expected.to_string(),
+    };
+    let check_range = |value: f64, range: &NumberRange| {
+        if range.contains(value) {
+            Ok(())
+        } else {
+            Err(ArgError::OutOfRange {
+                name,
+                value,
+                range: range.to_string(),
+            })
+        }
+    };
+
+    match kind {
+        ArgKind::IPosition => parse_position::<IVec2>(tokens)
+            .map(ArgValue::IPosition)
+            .map_err(|_| invalid("integer coordinates like 10,-4")),
+        ArgKind::Position => parse_position::<Vec2>(tokens)
+            .map(ArgValue::Position)
+            .map_err(|_| invalid("coordinates like 10.5,-4")),
+        ArgKind::Int(range) => {
+            let value: i64 = tokens[0].parse().map_err(|_| invalid("an integer"))?;
+            check_range(value as f64, range)?;
+            Ok(ArgValue::Int(value))
+        }
+        ArgKind::Float(range) => {
+            let value: f64 = tokens[0].parse().map_err(|_| invalid("a number"))?;
+            check_range(value, range)?;
+            Ok(ArgValue::Float(value))
+        }
+        ArgKind::Choice(choices) => choices
+            .iter()
+            .find(|choice| choice.eq_ignore_ascii_case(&tokens[0]))
+            .map(|choice| ArgValue::Text(choice.to_string()))
+            .ok_or_else(|| invalid(&format!("one of {}", choices.join(", ")))),
+        ArgKind::Text => Ok(ArgValue::Text(tokens.join(" "))),
+    }
+}
+
+impl ArgKind {
+    pub fn int(range: impl RangeBounds<i64>) -> Self {
+        let to_f64 = |bound: Bound<&i64>| bound.map(|value| *value as f64);
+        Self::Int(NumberRange {
+            start: to_f64(range.start_bound()),
+            end: to_f64(range.end_bound()),
+        })
+    }
+
+    pub fn float(range: impl RangeBounds<f64>) -> Self {
+        Self::Float(NumberRange::new(range))
+    }
+}
+
+/// A validated argument value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgValue {
+    IPosition(IVec2),
+    Position(Vec2),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    /// The values of a repeated argument.
+    List(Vec<ArgValue>),
+}
+
+/// Conversion from a validated [`ArgValue`] into the type a command wants.
+pub trait FromArgValue: Sized {
+    fn from_arg_value(value: &ArgValue) -> Option<Self>;
+}
+
+impl FromArgValue for IVec2 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::IPosition(position) => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for Vec2 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Position(position) => Some(*position),
+            ArgValue::IPosition(position) => Some(position.as_vec2()),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for f64 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Float(value) => Some(*value),
+            ArgValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for f32 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        f64::from_arg_value(value).map(|value| value as f32)
+    }
+}
+
+impl FromArgValue for i64 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl FromArgValue for u32 {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        i64::from_arg_value(value).and_then(|value| u32::try_from(value).ok())
+    }
+}
+
+impl FromArgValue for usize {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        i64::from_arg_value(value).and_then(|value| usize::try_from(value).ok())
+    }
+}
+
+/// The words of a trailing argument are joined with spaces.
+impl FromArgValue for String {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Text(text) => Some(text.clone()),
+            ArgValue::List(values) => values
+                .iter()
+                .map(String::from_arg_value)
+                .collect::<Option<Vec<_>>>()
+                .map(|words| words.join(" ")),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromArgValue> FromArgValue for Vec<T> {
+    fn from_arg_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::List(values) => values.iter().map(T::from_arg_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments validated against an [`ArgSchema`].
+#[derive(Clone, Debug, Default)]
+pub struct ParsedArgs {
+    values: HashMap<&'static str, ArgValue>,
+    flags: HashMap<&'static str, Option<ArgValue>>,
+}
+
+impl ParsedArgs {
+    /// Get an argument, or `None` if it is optional and was not given.
+    pub fn get<T: FromArgValue>(&self, name: &str) -> Option<T> {
+        self.values.get(name).and_then(T::from_arg_value)
+    }
+
+    /// Get a required argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is not declared as required in the schema with a matching type.
+    pub fn req<T: FromArgValue>(&self, name: &str) -> T {
+        self.get(name)
+            .unwrap_or_else(|| panic!("required argument '{name}' is missing or has another type"))
+    }
+
+    /// Whether a flag was given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// The value given for a flag that takes one.
+    pub fn flag_value<T: FromArgValue>(&self, name: &str) -> Option<T> {
+        self.flags
+            .get(name)
+            .and_then(Option::as_ref)
+            .and_then(T::from_arg_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parse_positions_and_flags() {
+        let schema = ArgSchema::new()
+            .arg("position", ArgKind::Position)
+            .arg("radius", ArgKind::float(POSITIVE))
+            .flag("--overwrite");
+
+        let parsed = schema.parse(&args("--overwrite 10.5,-4 3")).unwrap();
+        assert_eq!(parsed.req::<Vec2>("position"), Vec2::new(10.5, -4.0));
+        assert_eq!(parsed.req::<f32>("radius"), 3.0);
+        assert!(parsed.flag("--overwrite"));
+
+        let parsed = schema.parse(&args("10 -4 3")).unwrap();
+        assert_eq!(parsed.req::<Vec2>("position"), Vec2::new(10.0, -4.0));
+        assert!(!parsed.flag("--overwrite"));
+    }
+
+    #[test]
+    fn parse_reports_missing_and_unexpected_arguments() {
+        let schema = ArgSchema::new()
+            .arg("count", ArgKind::int(1..))
+            .optional("name", ArgKind::Text);
+
+        assert_eq!(schema.parse(&[]).unwrap_err(), ArgError::Missing("count"));
+        assert!(
+            schema
+                .parse(&args("2"))
+                .unwrap()
+                .get::<String>("name")
+                .is_none()
+        );
+        assert_eq!(
+            schema.parse(&args("2 sand extra")).unwrap_err(),
+            ArgError::Unexpected("extra".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_validates_values() {
+        let schema = ArgSchema::new()
+            .arg("count", ArgKind::int(1..))
+            .optional("align", ArgKind::Choice(&["left", "center"]));
+
+        assert!(matches!(
+            schema.parse(&args("many")).unwrap_err(),
+            ArgError::Invalid { name: "count", .. }
+        ));
+        assert!(matches!(
+            schema.parse(&args("0")).unwrap_err(),
+            ArgError::OutOfRange { name: "count", .. }
+        ));
+        assert_eq!(
+            schema
+                .parse(&args("1 CENTER"))
+                .unwrap()
+                .req::<String>("align"),
+            "center"
+        );
+        assert!(matches!(
+            schema.parse(&args("1 right")).unwrap_err(),
+            ArgError::Invalid { name: "align", .. }
+        ));
+    }
+
+    #[test]
+    fn parse_flag_values() {
+        let schema = ArgSchema::new().flag_with_value("--size", ArgKind::int(1..));
+
+        let parsed = schema.parse(&args("--size 4")).unwrap();
+        assert_eq!(parsed.flag_value::<u32>("--size"), Some(4));
+        assert_eq!(
+            schema.parse(&args("--size")).unwrap_err(),
+            ArgError::MissingFlagValue("--size")
+        );
+    }
+
+    #[test]
+    fn parse_repeated_arguments() {
+        let schema = ArgSchema::new().repeated("vertex", ArgKind::Position, 3);
+
+        let parsed = schema.parse(&args("0,0 10 0 5,5")).unwrap();
+        assert_eq!(
+            parsed.req::<Vec<Vec2>>("vertex"),
+            vec![Vec2::ZERO, Vec2::new(10.0, 0.0), Vec2::new(5.0, 5.0)]
+        );
+        assert_eq!(
+            schema.parse(&args("0,0 1,1")).unwrap_err(),
+            ArgError::TooFew {
+                name: "vertex",
+                min: 3,
+                count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_command_keeps_its_own_flags() {
+        let schema = ArgSchema::new()
+            .flag("--pausable")
+            .arg("delay", ArgKind::Text)
+            .command("command");
+
+        let parsed = schema
+            .parse(&args("--pausable 5s earthquake --pausable"))
+            .unwrap();
+        assert!(parsed.flag("--pausable"));
+        assert_eq!(parsed.req::<String>("delay"), "5s");
+        assert_eq!(parsed.req::<String>("command"), "earthquake --pausable");
+    }
+}
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy_persistent::Persistent;
+
+use super::join_command_words;
+use crate::{
+    config::SettingsConfig,
+    console_command::{
+        CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand,
+        ConsoleCommandRegistry, Table,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+};
+
+#[derive(Default)]
+pub struct AliasConsoleCommand;
+
+impl ConsoleCommand for AliasConsoleCommand {
+    fn name(&self) -> &'static str {
+        "alias"
+    }
+
+    fn description(&self) -> &'static str {
+        "Define or show an alias, or list aliases with no arguments. Use $1..$9 or $@ for arguments, and ';' inside quotes to chain commands."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().optional("name", ArgKind::Text).repeated(
+            "expansion",
+            ArgKind::Text,
+            0,
+        ))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name = args.get::<String>("name");
+        let expansion = args
+            .get::<Vec<String>>("expansion")
+            .map(|words| join_command_words(&words));
+        let mut registry = world.resource_mut::<ConsoleCommandRegistry>();
+        match (name, expansion) {
+            (None, _) => {
+                let mut aliases: Vec<_> = registry.aliases().collect();
+                if aliases.is_empty() {
+                    return Ok(CommandOutput::message("No aliases defined"));
+                }
+                aliases.sort();
+                let mut table = Table::new(["Alias", "Expansion"]);
+                for (name, expansion) in &aliases {
+                    table.push_row([name, expansion]);
+                }
+                Ok(
+                    CommandOutput::message(format!("{} alias(es) defined", aliases.len()))
+                        .with_payload(CommandPayload::Table(table)),
+                )
+            }
+            (Some(name), None) => match registry.get(&name).and_then(|node| node.alias.as_ref()) {
+                Some(expansion) => Ok(CommandOutput::message(format!("{name} = {expansion}"))),
+                None => Err(CommandError::new(format!("No alias named '{name}'"))),
+            },
+            (Some(name), Some(expansion)) => {
+                registry
+                    .register_alias(&name, &expansion)
+                    .map_err(|error| CommandError::new(error.to_string()))?;
+                Ok(CommandOutput::message(format!("{name} = {expansion}")))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct UnaliasConsoleCommand;
+
+impl ConsoleCommand for UnaliasConsoleCommand {
+    fn name(&self) -> &'static str {
+        "unalias"
+    }
+
+    fn description(&self) -> &'static str {
+        "Remove an alias"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("name", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        let mut registry = world.resource_mut::<ConsoleCommandRegistry>();
+        match registry.remove_alias(&name) {
+            Some(_) => Ok(CommandOutput::message(format!("Removed alias '{name}'"))),
+            None => Err(CommandError::new(format!("No alias named '{name}'"))),
+        }
+    }
+}
+
+/// Register the aliases saved in `settings.toml`.
+pub(super) fn load_aliases(
+    mut registry: ResMut<ConsoleCommandRegistry>,
+    settings_config: Res<Persistent<SettingsConfig>>,
+) {
+    for (name, expansion) in &settings_config.get().aliases {
+        if let Err(error) = registry.register_alias(name, expansion) {
+            warn!("Skipping saved alias: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `alias <words>` and return the stored expansion.
+    fn define_alias(words: &[&str]) -> String {
+        let mut world = World::new();
+        world.init_resource::<ConsoleCommandRegistry>();
+        let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        let args = AliasConsoleCommand.args().unwrap().parse(&words).unwrap();
+        AliasConsoleCommand
+            .run_with_args(&args, &mut world)
+            .unwrap();
+        world
+            .resource::<ConsoleCommandRegistry>()
+            .get(&words[0])
+            .and_then(|node| node.alias.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn alias_keeps_quoted_words() {
+        // `alias ws particles spawn "Wet Sand"`
+        assert_eq!(
+            define_alias(&["ws", "particles", "spawn", "Wet Sand"]),
+            "particles spawn 'Wet Sand'"
+        );
+    }
+
+    #[test]
+    fn alias_keeps_a_quoted_expansion() {
+        // `alias reset "sim pause; particles reset"`
+        assert_eq!(
+            define_alias(&["reset", "sim pause; particles reset"]),
+            "sim pause; particles reset"
+        );
+    }
+
+    #[test]
+    fn alias_keeps_parameters_bare() {
+        assert_eq!(
+            define_alias(&["spawn", "particles", "spawn", "$1", "$@"]),
+            "particles spawn $1 $@"
+        );
+    }
+}
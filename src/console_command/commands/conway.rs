@@ -1,4 +1,4 @@
-use crate::console_command::ConsoleCommand;
+use crate::console_command::{CommandOutput, CommandResult, ConsoleCommand};
 use crate::game_of_life::GolToggleEvent;
 use bevy::prelude::*;
 
@@ -31,7 +31,8 @@ impl ConsoleCommand for ConwayToggleConsoleCommand {
         "Toggle the Conway simulation on or off"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(GolToggleEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(GolToggleEvent);
+        Ok(CommandOutput::none())
     }
 }
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
-use super::parse_position;
 use crate::{
-    console_command::ConsoleCommand,
+    console_command::{
+        CommandError, CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, POSITIVE, ParsedArgs},
+    },
     tools::earthquake::{
         Earthquake, EarthquakeFractureShape, EarthquakeRegion, EarthquakeShape,
         SetEarthquakeFractureShape, SetEarthquakeShape,
@@ -18,35 +20,126 @@ impl ConsoleCommand for EarthquakeConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Trigger an earthquake. Usage: earthquake circle <x>,<y> <radius> | earthquake rect <x>,<y> <w>,<h> [degrees] | earthquake poly <x1>,<y1> <x2>,<y2> <x3>,<y3> ..."
+        "Trigger an earthquake. With no shape, triggers a circular earthquake."
     }
 
     fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
-        vec![Box::new(EarthquakeSetConsoleCommand)]
-    }
-
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if let Some(kind) = args.first().map(String::as_str) {
-            match kind {
-                "circle" => {
-                    run_circle_earthquake(&args[1..], commands);
-                    return;
-                }
-                "rect" | "rectangle" => {
-                    run_rect_earthquake(&args[1..], commands);
-                    return;
-                }
-                "poly" | "polygon" => {
-                    run_polygon_earthquake(&args[1..], commands);
-                    return;
-                }
-                _ => {}
-            }
+        vec![
+            Box::new(EarthquakeCircleConsoleCommand),
+            Box::new(EarthquakeRectConsoleCommand),
+            Box::new(EarthquakePolyConsoleCommand),
+            Box::new(EarthquakeSetConsoleCommand),
+        ]
+    }
+
+    // Backward-compatible shorthand for the old circle-only command.
+    // Equivalent to `earthquake circle <x>,<y> <radius>`.
+    fn args(&self) -> Option<ArgSchema> {
+        EarthquakeCircleConsoleCommand.args()
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        EarthquakeCircleConsoleCommand.run_with_args(args, world)
+    }
+}
+
+#[derive(Default)]
+pub struct EarthquakeCircleConsoleCommand;
+
+impl ConsoleCommand for EarthquakeCircleConsoleCommand {
+    fn name(&self) -> &'static str {
+        "circle"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trigger a circular earthquake"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("position", ArgKind::Position)
+                .arg("radius", ArgKind::float(POSITIVE)),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center: Vec2 = args.req("position");
+        let radius: f32 = args.req("radius");
+        world.trigger(Earthquake {
+            region: EarthquakeRegion::circle(center, radius),
+        });
+        Ok(CommandOutput::message(format!(
+            "Triggered earthquake at {center} with radius {radius}"
+        )))
+    }
+}
+
+#[derive(Default)]
+pub struct EarthquakeRectConsoleCommand;
+
+impl ConsoleCommand for EarthquakeRectConsoleCommand {
+    fn name(&self) -> &'static str {
+        "rect"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trigger a rectangular earthquake, optionally rotated by some degrees"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("position", ArgKind::Position)
+                .arg("size", ArgKind::Position)
+                .optional("degrees", ArgKind::float(..)),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center: Vec2 = args.req("position");
+        let size: Vec2 = args.req("size");
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return Err(CommandError::new(
+                "Invalid rectangle size: width and height must be positive",
+            ));
         }
+        let rotation = args.get::<f32>("degrees").unwrap_or(0.0).to_radians();
+
+        world.trigger(Earthquake {
+            region: EarthquakeRegion::rect(center, size * 0.5, rotation),
+        });
+        Ok(CommandOutput::message(format!(
+            "Triggered rectangular earthquake at {center} with size {size}"
+        )))
+    }
+}
 
-        // Backward-compatible shorthand for the old circle-only command.
-        // Equivalent to `earthquake circle <x>,<y> <radius>`.
-        run_circle_earthquake(args, commands);
+#[derive(Default)]
+pub struct EarthquakePolyConsoleCommand;
+
+impl ConsoleCommand for EarthquakePolyConsoleCommand {
+    fn name(&self) -> &'static str {
+        "poly"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trigger an earthquake in a polygon of at least three vertices"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().repeated("vertex", ArgKind::Position, 3))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let vertices: Vec<Vec2> = args.req("vertex");
+        let count = vertices.len();
+        world.trigger(Earthquake {
+            region: EarthquakeRegion::polygon(vertices),
+        });
+        Ok(CommandOutput::message(format!(
+            "Triggered polygon earthquake with {count} vertices"
+        )))
     }
 }
 
@@ -82,13 +175,17 @@ impl ConsoleCommand for EarthquakeSetShapeConsoleCommand {
         "Change earthquake brush shape"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Some(shape) = args.first().and_then(|value| parse_earthquake_shape(value)) else {
-            error!("Shape value required (usage: earthquake set shape <circle|rect|polygon>)");
-            return;
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg(
+            "shape",
+            ArgKind::Choice(&["circle", "rect", "rectangle", "poly", "polygon"]),
+        ))
+    }
 
-        commands.trigger(SetEarthquakeShape(shape));
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let shape = parse_earthquake_shape(&args.req::<String>("shape"))?;
+        world.trigger(SetEarthquakeShape(shape));
+        Ok(CommandOutput::none())
     }
 }
 
@@ -104,133 +201,48 @@ impl ConsoleCommand for EarthquakeSetFractureConsoleCommand {
         "Change earthquake fracture shape"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Some(shape) = args
-            .first()
-            .and_then(|value| parse_earthquake_fracture(value))
-        else {
-            error!("Fracture value required (usage: earthquake set fracture <exact|convex>)");
-            return;
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg(
+            "fracture",
+            ArgKind::Choice(&[
+                "exact",
+                "convex",
+                "voronoi",
+                "exact-voronoi",
+                "exact_voronoi",
+                "hull",
+                "convex-hull",
+                "convex_hull",
+            ]),
+        ))
+    }
 
-        commands.trigger(SetEarthquakeFractureShape(shape));
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let fracture = parse_earthquake_fracture(&args.req::<String>("fracture"))?;
+        world.trigger(SetEarthquakeFractureShape(fracture));
+        Ok(CommandOutput::none())
     }
 }
 
-fn parse_earthquake_shape(value: &str) -> Option<EarthquakeShape> {
+fn parse_earthquake_shape(value: &str) -> Result<EarthquakeShape, CommandError> {
     match value.to_lowercase().as_str() {
-        "circle" => Some(EarthquakeShape::Circle),
-        "rect" | "rectangle" => Some(EarthquakeShape::Rect),
-        "poly" | "polygon" => Some(EarthquakeShape::Polygon),
-        _ => {
-            error!("Invalid earthquake shape. Specify one of 'circle', 'rect', 'polygon'");
-            None
-        }
+        "circle" => Ok(EarthquakeShape::Circle),
+        "rect" | "rectangle" => Ok(EarthquakeShape::Rect),
+        "poly" | "polygon" => Ok(EarthquakeShape::Polygon),
+        _ => Err(CommandError::new(
+            "Invalid earthquake shape. Specify one of 'circle', 'rect', 'polygon'",
+        )),
     }
 }
 
-fn parse_earthquake_fracture(value: &str) -> Option<EarthquakeFractureShape> {
+fn parse_earthquake_fracture(value: &str) -> Result<EarthquakeFractureShape, CommandError> {
     match value.to_lowercase().as_str() {
         "exact" | "voronoi" | "exact-voronoi" | "exact_voronoi" => {
-            Some(EarthquakeFractureShape::Concave)
-        }
-        "convex" | "hull" | "convex-hull" | "convex_hull" => Some(EarthquakeFractureShape::Convex),
-        _ => {
-            error!("Invalid earthquake fracture. Specify one of 'exact', 'convex'");
-            None
-        }
-    }
-}
-
-fn run_circle_earthquake(args: &[String], commands: &mut Commands) {
-    if args.len() < 2 {
-        warn!("Usage: earthquake circle <x>,<y> <radius>");
-        return;
-    }
-
-    let center = match parse_position::<Vec2>(&args[0..1]) {
-        Ok(c) => c,
-        Err(e) => {
-            warn!("Invalid position: {e}");
-            return;
-        }
-    };
-
-    let radius: f32 = match args[1].parse() {
-        Ok(v) if v > 0.0 => v,
-        _ => {
-            warn!("Invalid radius: must be a positive number");
-            return;
-        }
-    };
-
-    info!("Triggering earthquake at {center} with radius {radius}");
-    commands.trigger(Earthquake {
-        region: EarthquakeRegion::circle(center, radius),
-    });
-}
-
-fn run_rect_earthquake(args: &[String], commands: &mut Commands) {
-    if args.len() < 2 {
-        warn!("Usage: earthquake rect <x>,<y> <w>,<h> [degrees]");
-        return;
-    }
-
-    let center = match parse_position::<Vec2>(&args[0..1]) {
-        Ok(c) => c,
-        Err(e) => {
-            warn!("Invalid rectangle center: {e}");
-            return;
-        }
-    };
-
-    let size = match parse_position::<Vec2>(&args[1..2]) {
-        Ok(s) if s.x > 0.0 && s.y > 0.0 => s,
-        _ => {
-            warn!("Invalid rectangle size: width and height must be positive");
-            return;
+            Ok(EarthquakeFractureShape::Concave)
         }
-    };
-
-    let rotation = match args.get(2) {
-        Some(value) => match value.parse::<f32>() {
-            Ok(degrees) => degrees.to_radians(),
-            Err(_) => {
-                warn!("Invalid rectangle rotation: must be degrees");
-                return;
-            }
-        },
-        None => 0.0,
-    };
-
-    info!("Triggering rectangular earthquake at {center} with size {size}");
-    commands.trigger(Earthquake {
-        region: EarthquakeRegion::rect(center, size * 0.5, rotation),
-    });
-}
-
-fn run_polygon_earthquake(args: &[String], commands: &mut Commands) {
-    if args.len() < 3 {
-        warn!("Usage: earthquake poly <x1>,<y1> <x2>,<y2> <x3>,<y3> ...");
-        return;
+        "convex" | "hull" | "convex-hull" | "convex_hull" => Ok(EarthquakeFractureShape::Convex),
+        _ => Err(CommandError::new(
+            "Invalid earthquake fracture. Specify one of 'exact', 'convex'",
+        )),
     }
-
-    let mut vertices = Vec::with_capacity(args.len());
-    for arg in args {
-        match parse_position::<Vec2>(std::slice::from_ref(arg)) {
-            Ok(vertex) => vertices.push(vertex),
-            Err(e) => {
-                warn!("Invalid polygon vertex: {e}");
-                return;
-            }
-        }
-    }
-
-    info!(
-        "Triggering polygon earthquake with {} vertices",
-        vertices.len()
-    );
-    commands.trigger(Earthquake {
-        region: EarthquakeRegion::polygon(vertices),
-    });
 }
@@ -0,0 +1,133 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+
+use super::resolve_console_path;
+use crate::{
+    config::{ActiveWorldPath, ConfigPath},
+    console_command::{
+        CommandError, CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+        commands::ConsoleScheduler,
+    },
+    setup::SetupSystems,
+};
+
+/// Script run from the active world's directory when the world is loaded.
+pub const AUTOEXEC_FILE: &str = "autoexec.cfg";
+
+pub struct ExecConsoleCommandPlugin;
+
+impl Plugin for ExecConsoleCommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, run_autoexec.after(SetupSystems::Debug));
+    }
+}
+
+#[derive(Default)]
+pub struct ExecConsoleCommand;
+
+impl ConsoleCommand for ExecConsoleCommand {
+    fn name(&self) -> &'static str {
+        "exec"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run a file of console commands. Relative paths are resolved against the active world directory, falling back to the config directory. With --stop-on-error, the script stops at the first command that fails."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("file", ArgKind::Text)
+                .flag("--stop-on-error"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let file: String = args.req("file");
+        let path = find_console_script(world, &file)?;
+        queue_console_script(world, &path, args.flag("--stop-on-error"))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.iter().any(|arg| !arg.starts_with("--")) {
+            return Vec::new();
+        }
+        let search_dirs = [
+            world
+                .get_resource::<ActiveWorldPath>()
+                .map(|path| path.0.clone()),
+            world
+                .get_resource::<ConfigPath>()
+                .map(|path| path.0.clone()),
+        ];
+        search_dirs
+            .into_iter()
+            .flatten()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect()
+    }
+}
+
+/// Read a console script, one command per line, skipping blank lines and `#` comments.
+pub fn read_console_script(path: &Path) -> io::Result<Vec<String>> {
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Queue the commands in the script at `path` to run in order, pausing at `wait` lines.
+pub fn queue_console_script(world: &mut World, path: &Path, stop_on_error: bool) -> CommandResult {
+    let lines = read_console_script(path).map_err(|error| {
+        CommandError::new(format!("Failed to read script {}: {error}", path.display()))
+    })?;
+    let id = world.resource_mut::<ConsoleScheduler>().schedule_script(
+        path.to_path_buf(),
+        lines,
+        stop_on_error,
+    );
+    Ok(CommandOutput::message(format!(
+        "Job {id}: running script {}",
+        path.display()
+    )))
+}
+
+/// Find a script by path. Relative paths are resolved against the active world directory, falling
+/// back to the config directory, which holds the console history `cmds.log`.
+pub(super) fn find_console_script(world: &World, file: &str) -> Result<PathBuf, CommandError> {
+    let in_config_dir = world
+        .get_resource::<ConfigPath>()
+        .filter(|_| Path::new(file).is_relative())
+        .map(|config_path| config_path.0.join(file));
+    std::iter::once(resolve_console_path(world, file))
+        .chain(in_config_dir)
+        .find(|path| path.is_file())
+        .ok_or_else(|| CommandError::new(format!("Script '{file}' not found")))
+}
+
+/// Run the active world's `autoexec.cfg`, if it has one.
+pub fn run_autoexec(mut commands: Commands, active_world_path: Res<ActiveWorldPath>) {
+    let path = active_world_path.0.join(AUTOEXEC_FILE);
+    if path.is_file() {
+        commands.queue(move |world: &mut World| run_startup_script(world, &path));
+    }
+}
+
+/// Queue a script run at startup, logging the outcome since there is no command to report it.
+pub fn run_startup_script(world: &mut World, path: &Path) {
+    match queue_console_script(world, path, false) {
+        Ok(output) => info!("{}", output.message.unwrap_or_default()),
+        Err(error) => error!("{error}"),
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{console_command::ConsoleCommand, exit::ExitApplicationEvent};
+use crate::{
+    console_command::{CommandOutput, CommandResult, ConsoleCommand},
+    exit::ExitApplicationEvent,
+};
 
 #[derive(Default)]
 pub struct ExitConsoleCommand;
@@ -14,7 +17,8 @@ impl ConsoleCommand for ExitConsoleCommand {
         "Exit the application"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(ExitApplicationEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(ExitApplicationEvent);
+        Ok(CommandOutput::none())
     }
 }
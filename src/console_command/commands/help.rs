@@ -1,19 +1,9 @@
 use bevy::prelude::*;
 
-use crate::console_command::{ConsoleCommand, ConsoleCommandNode, ConsoleCommandRegistry};
-
-pub struct HelpConsoleCommandPlugin;
-
-impl Plugin for HelpConsoleCommandPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_observer(on_show_help);
-    }
-}
-
-#[derive(Event)]
-pub struct ShowHelpEvent {
-    pub target_command: Option<String>,
-}
+use crate::console_command::{
+    CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand, ConsoleCommandNode,
+    ConsoleCommandRegistry, TreeNode,
+};
 
 #[derive(Default, Debug)]
 pub struct HelpConsoleCommand;
@@ -27,41 +17,55 @@ impl ConsoleCommand for HelpConsoleCommand {
         "Display help information for console commands"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let target_command = args.first().cloned();
-        commands.trigger(ShowHelpEvent { target_command });
-    }
-}
-
-fn on_show_help(trigger: On<ShowHelpEvent>, registry: Res<ConsoleCommandRegistry>) {
-    let event = trigger.event();
+    fn run(&self, args: &[String], world: &mut World) -> CommandResult {
+        let registry = world.resource::<ConsoleCommandRegistry>();
 
-    if let Some(target_cmd) = &event.target_command {
-        if let Some(root_node) = registry.commands().get(target_cmd) {
-            show_command_tree_help(root_node, vec![target_cmd.clone()]);
-        } else {
-            error!("Console command '{}' does not exist", target_cmd);
-        }
-    } else {
-        info!("Available console commands:");
-        for (name, node) in registry.commands() {
-            let mut line = format!("  {} - {}", name, node.description);
-            if !node.children.is_empty() {
-                let subs: Vec<_> = node.children.keys().cloned().collect();
-                line.push_str(&format!(" (subcommands: {})", subs.join(", ")));
-            }
-            info!("{}", line);
+        if let Some(target_cmd) = args.first() {
+            let Some(root_node) = registry.commands().get(target_cmd) else {
+                return Err(CommandError::new(format!(
+                    "Console command '{target_cmd}' does not exist"
+                )));
+            };
+            let mut lines = Vec::new();
+            command_tree_help(root_node, vec![target_cmd.clone()], &mut lines);
+            let summary = lines.remove(0);
+            return Ok(CommandOutput::message(summary).with_payload(CommandPayload::List(lines)));
         }
+
+        let mut commands: Vec<TreeNode> = registry
+            .commands()
+            .iter()
+            .map(|(name, node)| {
+                let mut subcommands: Vec<TreeNode> = node
+                    .children
+                    .iter()
+                    .map(|(name, child)| TreeNode::new(format!("{name} - {}", child.description)))
+                    .collect();
+                subcommands.sort_by(|a, b| a.label.cmp(&b.label));
+                TreeNode::new(format!("{name} - {}", node.description)).with_children(subcommands)
+            })
+            .collect();
+        commands.sort_by(|a, b| a.label.cmp(&b.label));
+        Ok(CommandOutput::message("Available console commands:")
+            .with_payload(CommandPayload::Tree(commands)))
     }
 }
 
-fn show_command_tree_help(node: &ConsoleCommandNode, path: Vec<String>) {
-    info!("{} - {}", path.join(" "), node.description);
+fn command_tree_help(node: &ConsoleCommandNode, path: Vec<String>, lines: &mut Vec<String>) {
+    lines.push(format!("{} - {}", path.join(" "), node.description));
+    if let Some(usage) = &node.usage {
+        lines.push(format!("  Usage: {usage}"));
+    }
 
     if !node.children.is_empty() {
-        info!("  Subcommands:");
-        for (name, child) in &node.children {
-            info!("    {} - {}", name, child.description);
+        lines.push("  Subcommands:".to_string());
+        let mut children: Vec<_> = node.children.iter().collect();
+        children.sort_by_key(|(name, _)| *name);
+        for (name, child) in children {
+            lines.push(format!("    {} - {}", name, child.description));
+            if let Some(usage) = &child.usage {
+                lines.push(format!("      Usage: {usage}"));
+            }
         }
     }
 }
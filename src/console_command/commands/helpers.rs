@@ -1,15 +1,23 @@
-use std::str::FromStr;
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-use bevy::math::{IVec2, UVec2, Vec2};
+use bevy::{
+    math::{IVec2, UVec2, Vec2},
+    prelude::World,
+};
 use thiserror::Error;
 
+use crate::{config::ActiveWorldPath, particles::ParticleName};
+
 #[derive(Debug, Error)]
-pub(super) enum PositionParseError {
+pub(crate) enum PositionParseError {
     #[error("invalid position {0}.")]
     Invalid(String),
 }
 
-pub(super) trait Position: Sized {
+pub(crate) trait Position: Sized {
     type Scalar: FromStr;
     fn new(x: Self::Scalar, y: Self::Scalar) -> Self;
     fn filter_chars(c: char) -> bool;
@@ -45,7 +53,7 @@ impl Position for IVec2 {
     }
 }
 
-pub(super) fn parse_position<P: Position>(position: &[String]) -> Result<P, PositionParseError> {
+pub(crate) fn parse_position<P: Position>(position: &[String]) -> Result<P, PositionParseError> {
     let parse_coord = |s: &str| -> Result<P::Scalar, PositionParseError> {
         let filtered: String = s.chars().filter(|&c| P::filter_chars(c)).collect();
         filtered
@@ -66,3 +74,51 @@ pub(super) fn parse_position<P: Position>(position: &[String]) -> Result<P, Posi
 
     Ok(P::new(x, y))
 }
+
+/// Names of all particle types, for argument completion.
+pub(super) fn particle_type_names(world: &mut World) -> Vec<String> {
+    world
+        .query::<&ParticleName>()
+        .iter(world)
+        .map(|name| name.0.clone())
+        .collect()
+}
+
+/// Complete a fixed set of values for the first argument.
+pub(super) fn complete_first_arg(args: &[String], values: &[&str]) -> Vec<String> {
+    if args.is_empty() {
+        values.iter().map(|value| value.to_string()).collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Resolve a path given to a console command. Relative paths are resolved against the active
+/// world directory, so commands read and write the same files regardless of the working directory.
+pub(super) fn resolve_console_path(world: &World, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    match world.get_resource::<ActiveWorldPath>() {
+        Some(active_world_path) if path.is_relative() => active_world_path.0.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Join the words of a trailing command argument back into one command line. A single word is
+/// already a whole command line, such as `'sim pause; sim resume'` after the console strips its
+/// quotes, so it is used as is. Several words are quoted as needed so each stays one word, except
+/// alias parameters like `$1` and `$@`, which must stay bare to be substituted.
+pub(super) fn join_command_words(words: &[String]) -> String {
+    if let [command] = words {
+        return command.clone();
+    }
+    words
+        .iter()
+        .map(|word| match word.as_bytes() {
+            [b'$', b'1'..=b'9' | b'@'] => word.clone(),
+            _ => shlex::try_quote(word)
+                .map(|quoted| quoted.into_owned())
+                .unwrap_or_else(|_| word.clone()),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
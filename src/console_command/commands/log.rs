@@ -0,0 +1,135 @@
+use std::{fs, io::Write};
+
+use bevy::prelude::*;
+
+use super::resolve_console_path;
+use crate::{
+    console_command::{
+        CommandError, CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    ui::ConsoleInformationAreaState,
+};
+
+#[derive(Default)]
+pub struct LogConsoleCommand;
+
+impl ConsoleCommand for LogConsoleCommand {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manage the console log"
+    }
+
+    fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
+        vec![
+            Box::new(LogSaveConsoleCommand),
+            Box::new(LogClearConsoleCommand),
+            Box::new(LogLimitConsoleCommand),
+        ]
+    }
+}
+
+#[derive(Default)]
+pub struct LogSaveConsoleCommand;
+
+impl ConsoleCommand for LogSaveConsoleCommand {
+    fn name(&self) -> &'static str {
+        "save"
+    }
+
+    fn description(&self) -> &'static str {
+        "Write the console log to a file. Relative paths are resolved against the active world directory."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("file", ArgKind::Text)
+                .flag("--filtered"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let file: String = args.req("file");
+        let path = resolve_console_path(world, &file);
+
+        let state = world.resource::<ConsoleInformationAreaState>();
+        let lines: Vec<String> = if args.flag("--filtered") {
+            state.visible_lines().map(ToString::to_string).collect()
+        } else {
+            state.log_history.iter().map(ToString::to_string).collect()
+        };
+
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = fs::File::create(&path)?;
+            for line in &lines {
+                writeln!(file, "{line}")?;
+            }
+            Ok(())
+        };
+        write().map_err(|error| {
+            CommandError::new(format!("Failed to write {}: {error}", path.display()))
+        })?;
+
+        Ok(CommandOutput::message(format!(
+            "Saved {} line(s) to {}",
+            lines.len(),
+            path.display()
+        )))
+    }
+}
+
+#[derive(Default)]
+pub struct LogClearConsoleCommand;
+
+impl ConsoleCommand for LogClearConsoleCommand {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn description(&self) -> &'static str {
+        "Clear the console log"
+    }
+
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world
+            .resource_mut::<ConsoleInformationAreaState>()
+            .log_history
+            .clear();
+        Ok(CommandOutput::none())
+    }
+}
+
+#[derive(Default)]
+pub struct LogLimitConsoleCommand;
+
+impl ConsoleCommand for LogLimitConsoleCommand {
+    fn name(&self) -> &'static str {
+        "limit"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show or set how many lines the console log keeps"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().optional("lines", ArgKind::int(1..)))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let mut state = world.resource_mut::<ConsoleInformationAreaState>();
+        if let Some(limit) = args.get::<usize>("lines") {
+            state.set_history_limit(limit);
+        }
+        Ok(CommandOutput::message(format!(
+            "Console log keeps {} line(s)",
+            state.history_limit
+        )))
+    }
+}
@@ -1,32 +1,50 @@
+mod alias;
 mod conway;
 mod earthquake;
+mod exec;
 mod exit;
 mod help;
-mod helpers;
+pub(crate) mod helpers;
+mod log;
 mod painter;
+mod particle_catalog;
+mod particle_import;
+mod particle_type;
 mod particles;
+mod reflect;
 mod rigid_body;
 mod save;
 pub(crate) mod scene;
+mod schedule;
 mod select;
 mod setup;
+mod sim;
 mod tools;
 mod ui;
 
 use bevy::prelude::*;
 
+pub use alias::*;
 pub use conway::*;
 pub use earthquake::*;
+pub use exec::*;
 pub use exit::*;
 pub use help::*;
 use helpers::*;
+pub use log::*;
 pub use painter::*;
+pub use particle_catalog::*;
+pub use particle_import::*;
+pub use particle_type::*;
 pub use particles::*;
+pub use reflect::*;
 pub use rigid_body::*;
 pub use save::*;
 pub use scene::*;
+pub use schedule::*;
 pub use select::*;
 use setup::SetupPlugin;
+pub use sim::*;
 pub use tools::*;
 pub use ui::*;
 
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    console_command::ConsoleCommand,
+    console_command::{
+        CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
     tools::painter::{PainterModeState, PainterShape, SetPainterMode, SetPainterShape},
 };
 
@@ -54,13 +57,19 @@ impl ConsoleCommand for PainterSetShapeConsoleCommand {
         "Change painter shape"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Some(shape) = args.first().and_then(|value| parse_painter_shape(value)) else {
-            error!("Shape value required (usage: painter set shape <circle|line|cursor>)");
-            return;
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("shape", ArgKind::Choice(&["circle", "line", "cursor"])))
+    }
 
-        commands.trigger(SetPainterShape(shape));
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let shape = match args.req::<String>("shape").as_str() {
+            "circle" => PainterShape::Circle,
+            "line" => PainterShape::Line,
+            "cursor" => PainterShape::Cursor,
+            _ => unreachable!("validated by the argument schema"),
+        };
+        world.trigger(SetPainterShape(shape));
+        Ok(CommandOutput::none())
     }
 }
 
@@ -76,35 +85,17 @@ impl ConsoleCommand for PainterSetModeConsoleCommand {
         "Change painter mode"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Some(mode) = args.first().and_then(|value| parse_painter_mode(value)) else {
-            error!("Mode value required (usage: painter set mode <particles|conway>)");
-            return;
-        };
-
-        commands.trigger(SetPainterMode(mode));
-    }
-}
-
-fn parse_painter_shape(value: &str) -> Option<PainterShape> {
-    match value.to_lowercase().as_str() {
-        "circle" => Some(PainterShape::Circle),
-        "line" => Some(PainterShape::Line),
-        "cursor" => Some(PainterShape::Cursor),
-        _ => {
-            error!("Invalid painter shape. Specify one of 'circle', 'line', 'cursor'");
-            None
-        }
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("mode", ArgKind::Choice(&["particles", "conway"])))
     }
-}
 
-fn parse_painter_mode(value: &str) -> Option<PainterModeState> {
-    match value.to_lowercase().as_str() {
-        "particles" => Some(PainterModeState::Particles),
-        "conway" => Some(PainterModeState::Conway),
-        _ => {
-            error!("Invalid painter mode. Specify one of 'particles', 'conway'");
-            None
-        }
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let mode = match args.req::<String>("mode").as_str() {
+            "particles" => PainterModeState::Particles,
+            "conway" => PainterModeState::Conway,
+            _ => unreachable!("validated by the argument schema"),
+        };
+        world.trigger(SetPainterMode(mode));
+        Ok(CommandOutput::none())
     }
 }
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use super::resolve_console_path;
+use crate::{
+    console_command::{
+        CommandError, CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    particles::{CatalogFormat, write_particle_catalog},
+};
+
+const FORMATS: &[&str] = &["html", "markdown"];
+
+#[derive(Default)]
+pub struct ParticlesCatalogConsoleCommand;
+
+impl ConsoleCommand for ParticlesCatalogConsoleCommand {
+    fn name(&self) -> &'static str {
+        "catalog"
+    }
+
+    fn description(&self) -> &'static str {
+        "Write a browsable catalog of the particle set into a directory, with an index and a page per category showing each type's colors, properties and transformations. --format is html (default) or markdown. Relative paths are resolved against the active world directory."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("dir", ArgKind::Text)
+                .flag_with_value("--format", ArgKind::Choice(FORMATS)),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let dir: String = args.req("dir");
+        let path = resolve_console_path(world, &dir);
+        let format = match args.flag_value::<String>("--format").as_deref() {
+            Some("markdown") => CatalogFormat::Markdown,
+            _ => CatalogFormat::Html,
+        };
+
+        let catalog = write_particle_catalog(world, &path, format).map_err(CommandError::new)?;
+        Ok(CommandOutput::message(format!(
+            "Wrote {} particle type(s) across {} page(s) to {}",
+            catalog.particle_types,
+            catalog.pages.len(),
+            catalog.pages.first().map_or_else(
+                || path.display().to_string(),
+                |index| index.display().to_string()
+            )
+        )))
+    }
+}
@@ -0,0 +1,168 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::resolve_console_path;
+use crate::{
+    config::ActiveWorldPath,
+    console_command::{
+        CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand, Table,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    particles::{
+        ImportResolution, existing_particle_type, import_particle_types, particle_set_files,
+        read_particle_set, rename_conflicting_types, taken_particle_names,
+    },
+};
+
+const ON_CONFLICT: &[&str] = &["skip", "rename", "overwrite"];
+
+#[derive(Default)]
+pub struct ParticlesImportConsoleCommand;
+
+impl ConsoleCommand for ParticlesImportConsoleCommand {
+    fn name(&self) -> &'static str {
+        "import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Import particle types from another particle set file without replacing the current set. Relative paths are resolved against the active world directory. Names in --only are comma-separated. Types whose name is taken are skipped, renamed or overwritten according to --on-conflict (default skip). With --list, only show what the file contains."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("file", ArgKind::Text)
+                .flag_with_value("--only", ArgKind::Text)
+                .flag_with_value("--on-conflict", ArgKind::Choice(ON_CONFLICT))
+                .flag("--list"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let file: String = args.req("file");
+        let path = find_particle_set(world, &file)?;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let types = read_particle_set(&path, &type_registry.read()).map_err(CommandError::new)?;
+
+        if args.flag("--list") {
+            let mut table = Table::new(["Name", "Category", "Status"]);
+            for imported in &types {
+                let status = if existing_particle_type(world, &imported.name).is_some() {
+                    "name taken"
+                } else {
+                    "new"
+                };
+                table.push_row([
+                    imported.name.clone(),
+                    imported.category.clone(),
+                    status.to_string(),
+                ]);
+            }
+            return Ok(CommandOutput::message(format!(
+                "{} particle type(s) in {}",
+                types.len(),
+                path.display()
+            ))
+            .with_payload(CommandPayload::Table(table)));
+        }
+
+        let only: Option<Vec<String>> = args.flag_value::<String>("--only").map(|only| {
+            only.split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect()
+        });
+        if let Some(only) = &only
+            && let Some(missing) = only
+                .iter()
+                .find(|name| !types.iter().any(|t| t.name.to_lowercase() == **name))
+        {
+            return Err(CommandError::new(format!(
+                "No particle type named '{missing}' in {}",
+                path.display()
+            )));
+        }
+
+        let on_conflict = args
+            .flag_value::<String>("--on-conflict")
+            .unwrap_or_else(|| String::from("skip"));
+        let selected: Vec<usize> = types
+            .iter()
+            .enumerate()
+            .filter(|(_, imported)| {
+                only.as_ref()
+                    .is_none_or(|only| only.contains(&imported.name.to_lowercase()))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let renames = rename_conflicting_types(&taken_particle_names(world), &types, &selected);
+        let selection: Vec<(usize, ImportResolution)> = selected
+            .into_iter()
+            .map(|index| {
+                let resolution = match on_conflict.as_str() {
+                    "rename" => ImportResolution::Rename(
+                        renames
+                            .get(&index)
+                            .cloned()
+                            .unwrap_or_else(|| types[index].name.clone()),
+                    ),
+                    "overwrite" => ImportResolution::Overwrite,
+                    _ => ImportResolution::Skip,
+                };
+                (index, resolution)
+            })
+            .collect();
+
+        let import = import_particle_types(world, &types, &selection).map_err(CommandError::new)?;
+        let mut table = Table::new(["Name", "Result"]);
+        for (names, result) in [
+            (&import.imported, "imported"),
+            (&import.overwritten, "overwritten"),
+            (&import.skipped, "skipped"),
+        ] {
+            for name in names {
+                table.push_row([name.clone(), result.to_string()]);
+            }
+        }
+        Ok(CommandOutput::message(format!(
+            "Imported {}, overwrote {} and skipped {} particle type(s) from {}; removed {} reference(s) to types that aren't present",
+            import.imported.len(),
+            import.overwritten.len(),
+            import.skipped.len(),
+            path.display(),
+            import.dropped_references
+        ))
+        .with_payload(CommandPayload::Table(table)))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        let Some(active_world_path) = world.get_resource::<ActiveWorldPath>() else {
+            return Vec::new();
+        };
+        particle_set_files(&active_world_path.0)
+            .into_iter()
+            .map(
+                |(label, path)| match path.strip_prefix(&active_world_path.0) {
+                    Ok(relative) => relative.to_string_lossy().into_owned(),
+                    Err(_) => Path::new("..").join(label).to_string_lossy().into_owned(),
+                },
+            )
+            .collect()
+    }
+}
+
+/// Find a particle set file by path, resolving relative paths against the active world directory.
+fn find_particle_set(world: &World, file: &str) -> Result<PathBuf, CommandError> {
+    let path = resolve_console_path(world, file);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(CommandError::new(format!(
+            "Particle set '{file}' not found"
+        )))
+    }
+}
@@ -0,0 +1,427 @@
+use bevy::{
+    prelude::*,
+    reflect::{PartialReflect, TypeRegistry},
+};
+
+use super::{
+    field, field_mut, find_particle_type, format_value, parse_value, particle_type_names,
+    push_field_paths,
+};
+use crate::{
+    console_command::{
+        CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand, Table,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    particles::{
+        ParticleName, delete_particle_type, find_particle_type_references, rename_particle_type,
+        spawn_particle_type, spawn_particle_type_from,
+    },
+};
+
+#[derive(Default)]
+pub struct ParticlesTypeConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeConsoleCommand {
+    fn name(&self) -> &'static str {
+        "type"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create, rename, delete and edit particle types"
+    }
+
+    fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
+        vec![
+            Box::new(ParticlesTypeNewConsoleCommand),
+            Box::new(ParticlesTypeRenameConsoleCommand),
+            Box::new(ParticlesTypeDeleteConsoleCommand),
+            Box::new(ParticlesTypeRefsConsoleCommand),
+            Box::new(ParticlesTypeSetConsoleCommand),
+        ]
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesTypeNewConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeNewConsoleCommand {
+    fn name(&self) -> &'static str {
+        "new"
+    }
+
+    fn description(&self) -> &'static str {
+        "Create a particle type, optionally copying the components of an existing one"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("name", ArgKind::Text)
+                .flag_with_value("--from", ArgKind::Text),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        ensure_name_available(world, &name)?;
+
+        let template = args
+            .flag_value::<String>("--from")
+            .map(|template| find_particle_type(world, &template))
+            .transpose()?;
+
+        let mut commands = world.commands();
+        let message = match template {
+            Some((source, _, template)) => {
+                let (_, id) = spawn_particle_type_from(&mut commands, source, name.clone());
+                format!(
+                    "Created particle type '{name}' (id {}) from '{template}'",
+                    id.get()
+                )
+            }
+            None => {
+                spawn_particle_type(&mut commands, name.clone());
+                format!("Created particle type '{name}'")
+            }
+        };
+        world.flush();
+        Ok(CommandOutput::message(message))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.last().is_some_and(|arg| arg == "--from") {
+            particle_type_names(world)
+        } else {
+            self.args()
+                .map(|schema| schema.complete(args))
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesTypeRenameConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeRenameConsoleCommand {
+    fn name(&self) -> &'static str {
+        "rename"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rename a particle type"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("old", ArgKind::Text)
+                .arg("new", ArgKind::Text),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let old: String = args.req("old");
+        let new: String = args.req("new");
+        let (entity, _, old) = find_particle_type(world, &old)?;
+        if !old.eq_ignore_ascii_case(&new) {
+            ensure_name_available(world, &new)?;
+        }
+
+        rename_particle_type(world, entity, new.clone());
+        Ok(CommandOutput::message(format!(
+            "Renamed particle type '{old}' to '{new}'"
+        )))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() {
+            particle_type_names(world)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesTypeDeleteConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeDeleteConsoleCommand {
+    fn name(&self) -> &'static str {
+        "delete"
+    }
+
+    fn description(&self) -> &'static str {
+        "Delete a particle type. If other types refer to it, pass --replace <type> to rewrite those references and convert its live particles, or --force to remove the references and despawn its particles."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("name", ArgKind::Text)
+                .flag_with_value("--replace", ArgKind::Text)
+                .flag("--force"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        let (entity, id, name) = find_particle_type(world, &name)?;
+        let replacement = args
+            .flag_value::<String>("--replace")
+            .map(|replacement| find_particle_type(world, &replacement))
+            .transpose()?;
+        if replacement
+            .as_ref()
+            .is_some_and(|(replacement, ..)| *replacement == entity)
+        {
+            return Err(CommandError::new(
+                "A particle type cannot be replaced by itself",
+            ));
+        }
+
+        let references = find_particle_type_references(world, id);
+        if !references.is_empty() && replacement.is_none() && !args.flag("--force") {
+            return Err(CommandError::new(format!(
+                "'{name}' is referenced {} time(s) (see 'particles type refs'); pass --replace <type> or --force",
+                references.len()
+            )));
+        }
+
+        let deletion =
+            delete_particle_type(world, entity, replacement.as_ref().map(|(_, id, _)| *id));
+        Ok(CommandOutput::message(match replacement {
+            Some((_, _, replacement)) => format!(
+                "Deleted particle type '{name}', rewired {} reference(s) and converted {} particle(s) to '{replacement}'",
+                deletion.references, deletion.particles
+            ),
+            None => format!(
+                "Deleted particle type '{name}', removed {} reference(s) and despawned {} particle(s)",
+                deletion.references, deletion.particles
+            ),
+        }))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() || args.last().is_some_and(|arg| arg == "--replace") {
+            particle_type_names(world)
+        } else {
+            self.args()
+                .map(|schema| schema.complete(args))
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesTypeRefsConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeRefsConsoleCommand {
+    fn name(&self) -> &'static str {
+        "refs"
+    }
+
+    fn description(&self) -> &'static str {
+        "List the particle types that refer to a particle type"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().rest("name"))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        let (_, id, name) = find_particle_type(world, &name)?;
+        let references = find_particle_type_references(world, id);
+        if references.is_empty() {
+            return Ok(CommandOutput::message(format!(
+                "No particle types refer to '{name}'"
+            )));
+        }
+
+        let mut table = Table::new(["Particle Type", "Reference"]);
+        for reference in &references {
+            table.push_row([reference.name.clone(), reference.kind.to_string()]);
+        }
+        Ok(CommandOutput::message(format!(
+            "'{name}' is referenced {} time(s)",
+            references.len()
+        ))
+        .with_payload(CommandPayload::Table(table)))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() {
+            particle_type_names(world)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesTypeSetConsoleCommand;
+
+impl ConsoleCommand for ParticlesTypeSetConsoleCommand {
+    fn name(&self) -> &'static str {
+        "set"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set a field of a component on a particle type, e.g. 'particles type set Sand Density.0 1400'. Values are written in RON."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("name", ArgKind::Text)
+                .arg("path", ArgKind::Text)
+                .rest("value"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        let path: String = args.req("path");
+        let text: String = args.req("value");
+        let (entity, _, name) = find_particle_type(world, &name)?;
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let (component_name, field_path) = path.split_once('.').unwrap_or((&path, ""));
+        if let Some((_, reason)) = PROTECTED_COMPONENTS
+            .iter()
+            .find(|(protected, _)| protected.eq_ignore_ascii_case(component_name))
+        {
+            return Err(CommandError::new(format!(
+                "Cannot set '{component_name}': {reason}"
+            )));
+        }
+        let reflect_component =
+            find_component(&type_registry, world.entity(entity), component_name).ok_or_else(
+                || {
+                    CommandError::new(format!(
+                        "'{name}' has no component named '{component_name}'"
+                    ))
+                },
+            )?;
+
+        let mut entity_mut = world.entity_mut(entity);
+        let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) else {
+            return Err(CommandError::new(format!(
+                "'{name}' has no component named '{component_name}'"
+            )));
+        };
+        let target = field_mut(component.as_partial_reflect_mut(), field_path)?;
+        let registration = target
+            .get_represented_type_info()
+            .and_then(|info| type_registry.get(info.type_id()))
+            .ok_or_else(|| CommandError::new(format!("'{path}' has an unregistered type")))?;
+        let value = parse_value(&text, registration, &type_registry)?;
+        target
+            .try_apply(value.as_ref())
+            .map_err(|error| CommandError::new(format!("Cannot set '{path}': {error}")))?;
+
+        Ok(CommandOutput::message(format!(
+            "{name}: {path} = {}",
+            format_value(target, &type_registry)
+        )))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        let Some(name) = args.first() else {
+            return particle_type_names(world);
+        };
+        let Ok((entity, ..)) = find_particle_type(world, name) else {
+            return Vec::new();
+        };
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let entity_ref = world.entity(entity);
+        match &args[1..] {
+            [] => {
+                let mut paths = Vec::new();
+                for (component_name, component) in reflected_components(&type_registry, entity_ref)
+                {
+                    push_field_paths(component, component_name.to_string(), 0, &mut paths);
+                }
+                paths.sort();
+                paths
+            }
+            // Offer the current value as a starting point for editing.
+            [path] => {
+                let (component_name, field_path) = path.split_once('.').unwrap_or((path, ""));
+                reflected_components(&type_registry, entity_ref)
+                    .find(|(name, _)| name.eq_ignore_ascii_case(component_name))
+                    .and_then(|(_, component)| field(component, field_path).ok())
+                    .map(|value| format_value(value, &type_registry))
+                    .into_iter()
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn ensure_name_available(world: &mut World, name: &str) -> Result<(), CommandError> {
+    if name.trim().is_empty() {
+        return Err(CommandError::new("Particle type names cannot be empty"));
+    }
+    if world
+        .query::<&ParticleName>()
+        .iter(world)
+        .any(|existing| existing.0.eq_ignore_ascii_case(name))
+    {
+        return Err(CommandError::new(format!(
+            "A particle type named '{name}' already exists"
+        )));
+    }
+    Ok(())
+}
+
+/// Components `particles type set` refuses to edit in place, with the reason. Other particle
+/// types, live particles and saved settings refer to particle types through them.
+const PROTECTED_COMPONENTS: [(&str, &str); 3] = [
+    (
+        "ParticleType",
+        "the id of a particle type can't be changed, its particles and references depend on it",
+    ),
+    ("ParticleName", "use 'particles type rename' instead"),
+    (
+        "ParticleTemplate",
+        "set the template in the particle editor instead",
+    ),
+];
+
+/// Find the reflected component on `entity` whose short type name matches `name`, ignoring case.
+fn find_component<'a>(
+    type_registry: &'a TypeRegistry,
+    entity: EntityRef,
+    name: &str,
+) -> Option<&'a ReflectComponent> {
+    type_registry
+        .iter()
+        .filter(|registration| {
+            registration
+                .type_info()
+                .type_path_table()
+                .short_path()
+                .eq_ignore_ascii_case(name)
+        })
+        .filter_map(|registration| registration.data::<ReflectComponent>())
+        .find(|reflect_component| reflect_component.contains(entity))
+}
+
+/// Every reflected component on `entity`, with its short type name.
+fn reflected_components<'a>(
+    type_registry: &'a TypeRegistry,
+    entity: EntityRef<'a>,
+) -> impl Iterator<Item = (&'a str, &'a dyn PartialReflect)> {
+    type_registry.iter().filter_map(move |registration| {
+        let component = registration.data::<ReflectComponent>()?.reflect(entity)?;
+        Some((
+            registration.type_info().type_path_table().short_path(),
+            component.as_partial_reflect(),
+        ))
+    })
+}
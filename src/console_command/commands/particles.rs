@@ -1,32 +1,32 @@
-use bevy::prelude::*;
-use bevy_falling_sand::prelude::{
-    DespawnAllParticlesSignal, DespawnDynamicParticlesSignal, DespawnParticleTypeChildrenSignal,
-    DespawnStaticParticlesSignal, ParticleType,
+use std::fs;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_falling_sand::{
+    core::particle::{AttachedToParticleType, Particle, ParticleTypeId},
+    prelude::{
+        DespawnAllParticlesSignal, DespawnDynamicParticlesSignal,
+        DespawnParticleTypeChildrenSignal, DespawnStaticParticlesSignal, GridPosition, Movement,
+        ParticleType,
+    },
 };
 
-use super::parse_position;
+use super::{
+    ParticlesCatalogConsoleCommand, ParticlesImportConsoleCommand, ParticlesTypeConsoleCommand,
+    format_value, particle_type_names, resolve_console_path,
+};
 use crate::{
-    console_command::ConsoleCommand,
+    console_command::{
+        CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand, Table,
+        args::{ArgKind, ArgSchema, POSITIVE, ParsedArgs, UNIT_EXCLUSIVE},
+    },
     particles::{
-        ParticleName, SpawnBarnsleyEvent, SpawnTextEvent, TextAlignment,
-        carpet::SpawnSierpinskiCarpetEvent, triangle::SpawnSierpinskiTriangleEvent,
+        ParticleCategory, ParticleGraph, ParticleName, Severity, SpawnBarnsleyEvent,
+        SpawnTextEvent, TextAlignment, carpet::SpawnSierpinskiCarpetEvent, movement_class,
+        refresh_particle_diagnostics, triangle::SpawnSierpinskiTriangleEvent,
     },
+    ui::UiToggleReactionGraphEvent,
 };
 
-pub struct ParticlesConsoleCommandPlugin;
-
-impl Plugin for ParticlesConsoleCommandPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_message::<DespawnParticleTypeByNameCommand>()
-            .add_systems(Update, msgr_despawn_particle_type_by_name_command);
-    }
-}
-
-#[derive(Message, Clone, Debug)]
-struct DespawnParticleTypeByNameCommand {
-    name: String,
-}
-
 #[derive(Default)]
 pub struct ParticlesConsoleCommand;
 
@@ -44,6 +44,14 @@ impl ConsoleCommand for ParticlesConsoleCommand {
             Box::new(ParticlesResetConsoleCommand),
             Box::new(ParticlesDespawnConsoleCommand),
             Box::new(ParticlesSpawnConsoleCommand),
+            Box::new(ParticlesListConsoleCommand),
+            Box::new(ParticlesInfoConsoleCommand),
+            Box::new(ParticlesCountConsoleCommand),
+            Box::new(ParticlesTypeConsoleCommand),
+            Box::new(ParticlesValidateConsoleCommand),
+            Box::new(ParticlesGraphConsoleCommand),
+            Box::new(ParticlesImportConsoleCommand),
+            Box::new(ParticlesCatalogConsoleCommand),
         ]
     }
 }
@@ -114,8 +122,10 @@ impl ConsoleCommand for ParticlesResetWallAllConsoleCommand {
         "Reset all wall particles"
     }
 
-    fn run(&self, _args: &[String], _commands: &mut Commands) {
-        info!("Resetting all wall particles to parent data");
+    fn run(&self, _args: &[String], _world: &mut World) -> CommandResult {
+        Ok(CommandOutput::message(
+            "Resetting all wall particles to parent data",
+        ))
     }
 }
 
@@ -131,8 +141,10 @@ impl ConsoleCommand for ParticlesResetDynamicAllConsoleCommand {
         "Reset all dynamic particles"
     }
 
-    fn run(&self, _args: &[String], _commands: &mut Commands) {
-        info!("Resetting all dynamic particles to parent data")
+    fn run(&self, _args: &[String], _world: &mut World) -> CommandResult {
+        Ok(CommandOutput::message(
+            "Resetting all dynamic particles to parent data",
+        ))
     }
 }
 
@@ -170,9 +182,11 @@ impl ConsoleCommand for ParticlesDespawnDynamicConsoleCommand {
         "Despawn dynamic particles from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all dynamic particles from the world");
-        commands.trigger(DespawnDynamicParticlesSignal);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnDynamicParticlesSignal);
+        Ok(CommandOutput::message(
+            "Despawned all dynamic particles from the world",
+        ))
     }
 }
 
@@ -188,9 +202,11 @@ impl ConsoleCommand for ParticlesDespawnStaticConsoleCommand {
         "Despawn static particles from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all static particles from the world");
-        commands.trigger(DespawnStaticParticlesSignal);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnStaticParticlesSignal);
+        Ok(CommandOutput::message(
+            "Despawned all static particles from the world",
+        ))
     }
 }
 
@@ -206,9 +222,11 @@ impl ConsoleCommand for ParticlesDespawnAllConsoleCommand {
         "Despawn all particles from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all particles from the world");
-        commands.trigger(DespawnAllParticlesSignal);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnAllParticlesSignal);
+        Ok(CommandOutput::message(
+            "Despawned all particles from the world",
+        ))
     }
 }
 
@@ -224,60 +242,37 @@ impl ConsoleCommand for ParticlesDespawnNamedConsoleCommand {
         "Despawn all particles of a specified particle type"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Some(name) = particle_type_name_arg(args) else {
-            warn!("Usage: particles despawn named <particle_type_name>");
-            return;
-        };
-        commands.write_message(DespawnParticleTypeByNameCommand { name });
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().rest("particle_type_name"))
     }
-}
 
-fn particle_type_name_arg(args: &[String]) -> Option<String> {
-    let name = args.join(" ");
-    let name = name.trim();
-    (!name.is_empty()).then(|| name.to_string())
-}
-
-fn msgr_despawn_particle_type_by_name_command(
-    mut messages: MessageReader<DespawnParticleTypeByNameCommand>,
-    particle_types: Query<(&ParticleType, Option<&ParticleName>)>,
-    mut commands: Commands,
-) {
-    for message in messages.read() {
-        let matches: Vec<_> = particle_types
-            .iter()
-            .filter_map(|(particle_type, name)| {
-                let name = name?;
-                name.0
-                    .eq_ignore_ascii_case(&message.name)
-                    .then_some((particle_type.id(), name.0.clone()))
-            })
-            .collect();
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("particle_type_name");
+        let (_, id, name) = find_particle_type(world, &name)?;
+        world.trigger(DespawnParticleTypeChildrenSignal::from_particle_type(id));
+        Ok(CommandOutput::message(format!(
+            "Despawned all particles of type '{name}'"
+        )))
+    }
 
-        match matches.as_slice() {
-            [] => {
-                warn!("No particle type named '{}' found", message.name);
-            }
-            [(id, name)] => {
-                info!("Despawning all particles of type '{name}'");
-                commands.trigger(DespawnParticleTypeChildrenSignal::from_particle_type(*id));
-            }
-            _ => {
-                let names = matches
-                    .iter()
-                    .map(|(_, name)| name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                warn!(
-                    "Particle type name '{}' is ambiguous: {names}",
-                    message.name
-                );
-            }
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() {
+            particle_type_names(world)
+        } else {
+            Vec::new()
         }
     }
 }
 
+/// Default recursion depth for sierpinski fractals.
+const DEFAULT_SIERPINSKI_DEPTH: u32 = 6;
+
+fn sierpinski_args() -> ArgSchema {
+    ArgSchema::new()
+        .arg("center", ArgKind::IPosition)
+        .optional("depth", ArgKind::int(0..=12))
+}
+
 #[derive(Default)]
 pub struct ParticlesSpawnConsoleCommand;
 
@@ -308,7 +303,7 @@ impl ConsoleCommand for ParticlesSpawnSierpinskiConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn a sierpinski carpet. Usage: sierpinski <x>,<y> [depth]"
+        "Spawn a sierpinski fractal"
     }
 
     fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
@@ -328,24 +323,20 @@ impl ConsoleCommand for ParticlesSpawnSierpinskiCarpetConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn a sierpinski carpet. Usage: sierpinski <x>,<y> [depth]"
+        "Spawn a sierpinski carpet"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let (position_args, depth) = match args.last().and_then(|s| s.parse::<u32>().ok()) {
-            Some(d) => (&args[..args.len() - 1], d),
-            None => (args, 6),
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(sierpinski_args())
+    }
 
-        match parse_position::<IVec2>(position_args) {
-            Ok(center) => {
-                info!("Spawning sierpinski carpet at {center} with depth {depth}");
-                commands.trigger(SpawnSierpinskiCarpetEvent { center, depth });
-            }
-            Err(e) => {
-                warn!("{e}");
-            }
-        }
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center = args.req("center");
+        let depth = args.get("depth").unwrap_or(DEFAULT_SIERPINSKI_DEPTH);
+        world.trigger(SpawnSierpinskiCarpetEvent { center, depth });
+        Ok(CommandOutput::message(format!(
+            "Spawned sierpinski carpet at {center} with depth {depth}"
+        )))
     }
 }
 
@@ -358,24 +349,20 @@ impl ConsoleCommand for ParticlesSpawnSierpinskiTriangleConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn a sierpinski triangle. Usage: sierpinski <x>,<y> [depth]"
+        "Spawn a sierpinski triangle"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let (position_args, depth) = match args.last().and_then(|s| s.parse::<u32>().ok()) {
-            Some(d) => (&args[..args.len() - 1], d),
-            None => (args, 6),
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(sierpinski_args())
+    }
 
-        match parse_position::<IVec2>(position_args) {
-            Ok(center) => {
-                info!("Spawning sierpinski carpet at {center} with depth {depth}");
-                commands.trigger(SpawnSierpinskiTriangleEvent { center, depth });
-            }
-            Err(e) => {
-                warn!("{e}");
-            }
-        }
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center = args.req("center");
+        let depth = args.get("depth").unwrap_or(DEFAULT_SIERPINSKI_DEPTH);
+        world.trigger(SpawnSierpinskiTriangleEvent { center, depth });
+        Ok(CommandOutput::message(format!(
+            "Spawned sierpinski triangle at {center} with depth {depth}"
+        )))
     }
 }
 
@@ -388,90 +375,37 @@ impl ConsoleCommand for ParticlesSpawnBarnsleyConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn a barnsley fern. Usage: barnsley <center_x>,<center_y> <size_x,size_y> <num_iterations> <f1> <f2> <f3>"
+        "Spawn a barnsley fern. f1 < f2 < f3 split the probabilities of the fern's transforms."
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if args.len() < 6 {
-            warn!(
-                "Usage: barnsley <center_x>,<center_y> <size_x>,<size_y> <num_iterations> <f1> <f2> <f3>"
-            );
-            return;
-        }
-
-        let center = match parse_position::<IVec2>(&args[0..1]) {
-            Ok(c) => c,
-            Err(e) => {
-                warn!("Invalid center: {e}");
-                return;
-            }
-        };
-
-        let size = match parse_position::<IVec2>(&args[1..2]) {
-            Ok(s) => s,
-            Err(e) => {
-                warn!("Invalid size: {e}");
-                return;
-            }
-        };
-
-        let num_iterations: u32 = match args[2].parse() {
-            Ok(v) => v,
-            Err(_) => {
-                warn!("Invalid num_iterations: must be a positive integer");
-                return;
-            }
-        };
-
-        let f1: f32 = match args[3].parse() {
-            Ok(v) => v,
-            Err(_) => {
-                warn!("Invalid f1: must be a floating point number");
-                return;
-            }
-        };
-
-        let f2: f32 = match args[4].parse() {
-            Ok(v) => v,
-            Err(_) => {
-                warn!("Invalid f2: must be a floating point number");
-                return;
-            }
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("center", ArgKind::IPosition)
+                .arg("size", ArgKind::IPosition)
+                .arg("num_iterations", ArgKind::int(1..=i64::from(u32::MAX)))
+                .arg("f1", ArgKind::float(UNIT_EXCLUSIVE))
+                .arg("f2", ArgKind::float(UNIT_EXCLUSIVE))
+                .arg("f3", ArgKind::float(UNIT_EXCLUSIVE)),
+        )
+    }
 
-        let f3: f32 = match args[5].parse() {
-            Ok(v) => v,
-            Err(_) => {
-                warn!("Invalid f3: must be a floating point number");
-                return;
-            }
-        };
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center: IVec2 = args.req("center");
+        let size: IVec2 = args.req("size");
+        let num_iterations: u32 = args.req("num_iterations");
+        let f1: f32 = args.req("f1");
+        let f2: f32 = args.req("f2");
+        let f3: f32 = args.req("f3");
 
-        if f1 <= 0.0 || f1 >= 1.0 {
-            warn!("f1 must be greater than 0 and less than 1");
-            return;
-        }
-        if f2 <= 0.0 || f2 >= 1.0 {
-            warn!("f2 must be greater than 0 and less than 1");
-            return;
-        }
-        if f3 <= 0.0 || f3 >= 1.0 {
-            warn!("f3 must be greater than 0 and less than 1");
-            return;
-        }
         if f2 <= f1 {
-            warn!("f2 must be greater than f1");
-            return;
+            return Err(CommandError::new("f2 must be greater than f1"));
         }
         if f3 <= f2 {
-            warn!("f3 must be greater than f2");
-            return;
+            return Err(CommandError::new("f3 must be greater than f2"));
         }
 
-        info!(
-            "Spawning barnsley fern at {center} with size {size}, num_iterations={num_iterations}, f1={f1}, f2={f2}, f3={f3}"
-        );
-        commands.trigger(SpawnBarnsleyEvent {
+        world.trigger(SpawnBarnsleyEvent {
             center,
             size,
             num_iterations,
@@ -479,6 +413,9 @@ impl ConsoleCommand for ParticlesSpawnBarnsleyConsoleCommand {
             f2,
             f3,
         });
+        Ok(CommandOutput::message(format!(
+            "Spawned barnsley fern at {center} with size {size}, num_iterations={num_iterations}, f1={f1}, f2={f2}, f3={f3}"
+        )))
     }
 }
 
@@ -491,71 +428,423 @@ impl ConsoleCommand for ParticlesSpawnTextConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn text as particles. Usage: text <x>,<y> <font_size> [--align left|center|right] <text...>. Use \\n for line breaks."
+        "Spawn text as particles. Use \\n for line breaks."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("center", ArgKind::IPosition)
+                .arg("font_size", ArgKind::float(POSITIVE))
+                .rest("text")
+                .flag_with_value("--align", ArgKind::Choice(&["left", "center", "right"])),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let center: IVec2 = args.req("center");
+        let font_size: f32 = args.req("font_size");
+        let alignment = match args.flag_value::<String>("--align").as_deref() {
+            Some("center") => TextAlignment::Center,
+            Some("right") => TextAlignment::Right,
+            _ => TextAlignment::Left,
+        };
+
+        // Users write literal \n for line breaks. Single-quote the text so shlex
+        // preserves the backslash: 'HELLO\nWORLD'. Replace the two-char sequence
+        // with a real newline before passing it along.
+        let text = args.req::<String>("text").replace(r"\n", "\n");
+
+        let message = format!(
+            "Spawned text \"{text}\" at {center} with font size {font_size}, alignment {alignment:?}"
+        );
+        world.trigger(SpawnTextEvent {
+            center,
+            text,
+            font_size,
+            alignment,
+        });
+        Ok(CommandOutput::message(message))
     }
+}
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if args.len() < 3 {
-            warn!(
-                "Usage: particles spawn text <x>,<y> <font_size> [--align left|center|right] <text...>"
-            );
-            return;
+/// Find a particle type by name, ignoring case, returning its entity, id and name.
+pub(super) fn find_particle_type(
+    world: &mut World,
+    name: &str,
+) -> Result<(Entity, ParticleTypeId, String), CommandError> {
+    let matches: Vec<_> = world
+        .query::<(Entity, &ParticleType, &ParticleName)>()
+        .iter(world)
+        .filter(|(_, _, particle_name)| particle_name.0.eq_ignore_ascii_case(name))
+        .map(|(entity, particle_type, particle_name)| {
+            (entity, particle_type.id(), particle_name.0.clone())
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [] => Err(CommandError::new(format!(
+            "No particle type named '{name}' found"
+        ))),
+        [found] => Ok(found.clone()),
+        _ => {
+            let names = matches
+                .iter()
+                .map(|(_, _, name)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(CommandError::new(format!(
+                "Particle type name '{name}' is ambiguous: {names}"
+            )))
         }
+    }
+}
 
-        let center = match parse_position::<IVec2>(&args[0..1]) {
-            Ok(c) => c,
-            Err(e) => {
-                warn!("Invalid position: {e}");
-                return;
-            }
-        };
+/// Number of live particles attached to each particle type entity.
+fn live_particle_counts(world: &mut World) -> HashMap<Entity, usize> {
+    let mut counts = HashMap::default();
+    for attached in world
+        .query_filtered::<&AttachedToParticleType, With<Particle>>()
+        .iter(world)
+    {
+        *counts.entry(attached.0).or_default() += 1;
+    }
+    counts
+}
+
+#[derive(Default)]
+pub struct ParticlesListConsoleCommand;
+
+impl ConsoleCommand for ParticlesListConsoleCommand {
+    fn name(&self) -> &'static str {
+        "list"
+    }
+
+    fn description(&self) -> &'static str {
+        "List particle types with their ids, categories and live particle counts"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().flag_with_value("--category", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let category: Option<String> = args.flag_value("--category");
+        let counts = live_particle_counts(world);
+
+        let mut types: Vec<_> = world
+            .query::<(
+                Entity,
+                &ParticleType,
+                Option<&ParticleName>,
+                Option<&ParticleCategory>,
+                Option<&Movement>,
+            )>()
+            .iter(world)
+            .filter(|(.., particle_category, _)| {
+                category.as_ref().is_none_or(|category| {
+                    particle_category.is_some_and(|c| c.0.eq_ignore_ascii_case(category))
+                })
+            })
+            .map(
+                |(entity, particle_type, name, particle_category, movement)| {
+                    (
+                        particle_type.id().get(),
+                        name.map(|name| name.0.clone()).unwrap_or_default(),
+                        particle_category
+                            .map(|c| c.0.clone())
+                            .unwrap_or_else(|| "Other".to_string()),
+                        movement_class(movement),
+                        counts.get(&entity).copied().unwrap_or(0),
+                    )
+                },
+            )
+            .collect();
+        types.sort_by_key(|(id, ..)| *id);
+
+        if types.is_empty() {
+            return Ok(CommandOutput::message(match category {
+                Some(category) => format!("No particle types in category '{category}'"),
+                None => "No particle types defined".to_string(),
+            }));
+        }
+
+        let mut table = Table::new(["Id", "Name", "Category", "Movement", "Live"]);
+        for (id, name, category, movement, live) in &types {
+            table.push_row([
+                id.to_string(),
+                name.clone(),
+                category.clone(),
+                movement.to_string(),
+                live.to_string(),
+            ]);
+        }
+        Ok(
+            CommandOutput::message(format!("{} particle type(s)", types.len()))
+                .with_payload(CommandPayload::Table(table)),
+        )
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.last().is_some_and(|arg| arg == "--category") {
+            let mut categories: Vec<String> = world
+                .query::<&ParticleCategory>()
+                .iter(world)
+                .map(|category| category.0.clone())
+                .collect();
+            categories.sort();
+            categories.dedup();
+            categories
+        } else {
+            self.args()
+                .map(|schema| schema.complete(args))
+                .unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesInfoConsoleCommand;
+
+impl ConsoleCommand for ParticlesInfoConsoleCommand {
+    fn name(&self) -> &'static str {
+        "info"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show every component configured on a particle type"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().rest("particle_type_name"))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("particle_type_name");
+        let (entity, id, name) = find_particle_type(world, &name)?;
+        let live = live_particle_counts(world)
+            .get(&entity)
+            .copied()
+            .unwrap_or(0);
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let entity_ref = world.entity(entity);
+        let mut table = Table::new(["Component", "Value"]);
+        for registration in type_registry.iter() {
+            let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+                continue;
+            };
+            let Some(component) = reflect_component.reflect(entity_ref) else {
+                continue;
+            };
+            table.push_row([
+                registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_string(),
+                format_value(component.as_partial_reflect(), &type_registry),
+            ]);
+        }
+        table.sort_by(0, false);
+
+        Ok(
+            CommandOutput::message(format!("{name} (id {}, {live} live particle(s))", id.get()))
+                .with_payload(CommandPayload::Table(table)),
+        )
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() {
+            particle_type_names(world)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesCountConsoleCommand;
+
+impl ConsoleCommand for ParticlesCountConsoleCommand {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn description(&self) -> &'static str {
+        "Count live particles by type, optionally within the rectangle between two corners"
+    }
 
-        let font_size: f32 = match args[1].parse() {
-            Ok(v) if v > 0.0 => v,
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .optional("from", ArgKind::IPosition)
+                .optional("to", ArgKind::IPosition),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let region = match (args.get::<IVec2>("from"), args.get::<IVec2>("to")) {
+            (Some(from), Some(to)) => Some(IRect::from_corners(from, to)),
+            (None, None) => None,
             _ => {
-                warn!("Invalid font_size: must be a positive number");
-                return;
+                return Err(CommandError::new(
+                    "A region needs two corners, e.g. 'particles count 0,0 100,100'",
+                ));
             }
         };
 
-        let mut text_start = 2;
-        let mut alignment = TextAlignment::Left;
-
-        if args.get(2).map(|s| s.as_str()) == Some("--align") {
-            match args.get(3).map(|s| s.as_str()) {
-                Some("left") => alignment = TextAlignment::Left,
-                Some("center") => alignment = TextAlignment::Center,
-                Some("right") => alignment = TextAlignment::Right,
-                Some(other) => {
-                    warn!("Unknown alignment \"{other}\". Expected: left, center, right");
-                    return;
-                }
-                None => {
-                    warn!("--align requires a value: left, center, or right");
-                    return;
-                }
+        let mut counts: HashMap<Entity, usize> = HashMap::default();
+        for (attached, position) in world
+            .query_filtered::<(&AttachedToParticleType, &GridPosition), With<Particle>>()
+            .iter(world)
+        {
+            // Regions are inclusive of both corners.
+            if region.is_none_or(|region| {
+                position.0.cmpge(region.min).all() && position.0.cmple(region.max).all()
+            }) {
+                *counts.entry(attached.0).or_default() += 1;
             }
-            text_start = 4;
         }
 
-        if args.len() <= text_start {
-            warn!("No text provided");
-            return;
+        let mut rows: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(entity, count)| {
+                let name = world
+                    .get::<ParticleName>(entity)
+                    .map(|name| name.0.clone())
+                    .unwrap_or_else(|| format!("{entity}"));
+                (name, count)
+            })
+            .collect();
+        rows.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+
+        let total: usize = rows.iter().map(|(_, count)| count).sum();
+        let mut table = Table::new(["Name", "Count"]);
+        for (name, count) in &rows {
+            table.push_row([name.clone(), count.to_string()]);
         }
+        let message = match region {
+            Some(region) => format!(
+                "{total} particle(s) between {},{} and {},{}",
+                region.min.x, region.min.y, region.max.x, region.max.y
+            ),
+            None => format!("{total} particle(s)"),
+        };
+        Ok(CommandOutput::message(message).with_payload(CommandPayload::Table(table)))
+    }
+}
 
-        // Users write literal \n for line breaks. Single-quote the text so shlex
-        // preserves the backslash: 'HELLO\nWORLD'. Replace the two-char sequence
-        // with a real newline before passing it along.
-        let text = args[text_start..].join(" ").replace(r"\n", "\n");
+#[derive(Default)]
+pub struct ParticlesValidateConsoleCommand;
+
+impl ConsoleCommand for ParticlesValidateConsoleCommand {
+    fn name(&self) -> &'static str {
+        "validate"
+    }
 
-        info!(
-            "Spawning text \"{text}\" at {center} with font size {font_size}, alignment {alignment:?}"
+    fn description(&self) -> &'static str {
+        "Check particle type definitions for dangling references, empty colors, missing textures and other mistakes"
+    }
+
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        let diagnostics = refresh_particle_diagnostics(world);
+        let message = format!(
+            "{} error(s), {} warning(s)",
+            diagnostics.count(Severity::Error),
+            diagnostics.count(Severity::Warning)
         );
-        commands.trigger(SpawnTextEvent {
-            center,
-            text,
-            font_size,
-            alignment,
-        });
+        if diagnostics.diagnostics.is_empty() {
+            return Ok(CommandOutput::message(message));
+        }
+
+        let mut table = Table::new(["Severity", "Particle Type", "Problem"]);
+        for diagnostic in &diagnostics.diagnostics {
+            table.push_row([
+                diagnostic.severity.to_string(),
+                diagnostic.particle.clone(),
+                diagnostic.message.clone(),
+            ]);
+        }
+        Ok(CommandOutput::message(message).with_payload(CommandPayload::Table(table)))
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesGraphConsoleCommand;
+
+impl ConsoleCommand for ParticlesGraphConsoleCommand {
+    fn name(&self) -> &'static str {
+        "graph"
+    }
+
+    fn description(&self) -> &'static str {
+        "The graph of how particle types turn into one another"
+    }
+
+    fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
+        vec![
+            Box::new(ParticlesGraphShowConsoleCommand),
+            Box::new(ParticlesGraphExportConsoleCommand),
+        ]
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesGraphShowConsoleCommand;
+
+impl ConsoleCommand for ParticlesGraphShowConsoleCommand {
+    fn name(&self) -> &'static str {
+        "show"
+    }
+
+    fn description(&self) -> &'static str {
+        "Toggle the reaction graph window"
+    }
+
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleReactionGraphEvent);
+        Ok(CommandOutput::none())
+    }
+}
+
+#[derive(Default)]
+pub struct ParticlesGraphExportConsoleCommand;
+
+impl ConsoleCommand for ParticlesGraphExportConsoleCommand {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    fn description(&self) -> &'static str {
+        "Write the reaction graph as Graphviz DOT. Relative paths are resolved against the active world directory."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("file", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let file: String = args.req("file");
+        let path = resolve_console_path(world, &file);
+
+        let graph = ParticleGraph::build(world);
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, graph.to_dot())
+        };
+        write().map_err(|error| {
+            CommandError::new(format!("Failed to write {}: {error}", path.display()))
+        })?;
+
+        Ok(CommandOutput::message(format!(
+            "Wrote {} particle type(s) and {} transformation(s) to {}",
+            graph.nodes.len(),
+            graph.edges.len(),
+            path.display()
+        )))
     }
 }
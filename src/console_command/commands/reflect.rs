@@ -0,0 +1,255 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    reflect::{
+        GetPath, PartialReflect, ReflectRef, TypeRegistration, TypeRegistry,
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    },
+};
+use serde::de::DeserializeSeed;
+
+use crate::console_command::{
+    CommandError, CommandOutput, CommandResult, ConsoleCommand,
+    args::{ArgKind, ArgSchema, ParsedArgs},
+};
+
+/// How many levels of nested fields are offered as completions.
+const MAX_COMPLETION_DEPTH: usize = 3;
+
+#[derive(Default)]
+pub struct GetConsoleCommand;
+
+impl ConsoleCommand for GetConsoleCommand {
+    fn name(&self) -> &'static str {
+        "get"
+    }
+
+    fn description(&self) -> &'static str {
+        "Print a reflected resource or one of its fields, e.g. 'get EarthquakeConfiguration.voronoi_max_sites'"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("path", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let path: String = args.req("path");
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        let (name, field_path) = split_resource_path(&path);
+        let reflect_resource = find_resource(&type_registry, name)?;
+        let resource = reflect_resource
+            .reflect(&*world)
+            .map_err(|_| CommandError::new(format!("Resource '{name}' is not present")))?;
+        let value = field(resource.as_partial_reflect(), field_path)?;
+
+        Ok(CommandOutput::message(format!(
+            "{path} = {}",
+            format_value(value, &type_registry)
+        )))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if args.is_empty() {
+            resource_paths(world)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SetConsoleCommand;
+
+impl ConsoleCommand for SetConsoleCommand {
+    fn name(&self) -> &'static str {
+        "set"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set a field of a reflected resource, e.g. 'set EarthquakeConfiguration.voronoi_max_sites 512'. Values are written in RON."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("path", ArgKind::Text).rest("value"))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let path: String = args.req("path");
+        let text: String = args.req("value");
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        let (name, field_path) = split_resource_path(&path);
+        let reflect_resource = find_resource(&type_registry, name)?;
+        let mut resource = reflect_resource
+            .reflect_mut(&mut *world)
+            .map_err(|_| CommandError::new(format!("Resource '{name}' is not present")))?;
+        let target = field_mut(resource.as_partial_reflect_mut(), field_path)?;
+
+        let registration = target
+            .get_represented_type_info()
+            .and_then(|info| type_registry.get(info.type_id()))
+            .ok_or_else(|| CommandError::new(format!("'{path}' has an unregistered type")))?;
+        let value = parse_value(&text, registration, &type_registry)?;
+        target
+            .try_apply(value.as_ref())
+            .map_err(|error| CommandError::new(format!("Cannot set '{path}': {error}")))?;
+
+        Ok(CommandOutput::message(format!(
+            "{path} = {}",
+            format_value(target, &type_registry)
+        )))
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        match args {
+            [] => resource_paths(world),
+            // Offer the current value as a starting point for editing.
+            [path] => current_value(path, world).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Split `Resource.field.path` into the resource name and the path within it.
+fn split_resource_path(path: &str) -> (&str, &str) {
+    path.split_once('.').unwrap_or((path, ""))
+}
+
+/// Find a reflected resource by its short type name, ignoring case.
+fn find_resource<'a>(
+    type_registry: &'a TypeRegistry,
+    name: &str,
+) -> Result<&'a ReflectResource, CommandError> {
+    type_registry
+        .iter()
+        .filter(|registration| {
+            registration
+                .type_info()
+                .type_path_table()
+                .short_path()
+                .eq_ignore_ascii_case(name)
+        })
+        .find_map(|registration| registration.data::<ReflectResource>())
+        .ok_or_else(|| CommandError::new(format!("No reflected resource named '{name}'")))
+}
+
+pub(super) fn field<'a>(
+    value: &'a dyn PartialReflect,
+    path: &str,
+) -> Result<&'a dyn PartialReflect, CommandError> {
+    if path.is_empty() {
+        return Ok(value);
+    }
+    value
+        .reflect_path(path)
+        .map_err(|error| CommandError::new(error.to_string()))
+}
+
+pub(super) fn field_mut<'a>(
+    value: &'a mut dyn PartialReflect,
+    path: &str,
+) -> Result<&'a mut dyn PartialReflect, CommandError> {
+    if path.is_empty() {
+        return Ok(value);
+    }
+    value
+        .reflect_path_mut(path)
+        .map_err(|error| CommandError::new(error.to_string()))
+}
+
+/// Format a value as RON, falling back to its debug representation.
+pub(super) fn format_value(value: &dyn PartialReflect, type_registry: &TypeRegistry) -> String {
+    ron::to_string(&TypedReflectSerializer::new(value, type_registry))
+        .unwrap_or_else(|_| format!("{value:?}"))
+}
+
+/// Parse `text` as RON into a value of the registered type. Strings may be written unquoted.
+pub(super) fn parse_value(
+    text: &str,
+    registration: &TypeRegistration,
+    type_registry: &TypeRegistry,
+) -> Result<Box<dyn PartialReflect>, CommandError> {
+    if registration.type_id() == TypeId::of::<String>() {
+        return Ok(Box::new(text.to_string()));
+    }
+
+    let invalid = |error: &dyn std::fmt::Display| {
+        CommandError::new(format!(
+            "Invalid value '{text}' for {}: {error}",
+            registration.type_info().type_path_table().short_path()
+        ))
+    };
+    let mut deserializer = ron::Deserializer::from_str(text).map_err(|error| invalid(&error))?;
+    TypedReflectDeserializer::new(registration, type_registry)
+        .deserialize(&mut deserializer)
+        .map_err(|error| invalid(&error))
+}
+
+fn current_value(path: &str, world: &mut World) -> Option<String> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let (name, field_path) = split_resource_path(path);
+    let resource = find_resource(&type_registry, name)
+        .ok()?
+        .reflect(&*world)
+        .ok()?;
+    let value = field(resource.as_partial_reflect(), field_path).ok()?;
+    Some(format_value(value, &type_registry))
+}
+
+/// Paths of every reflected resource in the world and their nested fields, for completion.
+fn resource_paths(world: &mut World) -> Vec<String> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let mut paths = Vec::new();
+    for registration in type_registry.iter() {
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+        let Ok(resource) = reflect_resource.reflect(&*world) else {
+            continue;
+        };
+        let name = registration.type_info().type_path_table().short_path();
+        push_field_paths(
+            resource.as_partial_reflect(),
+            name.to_string(),
+            0,
+            &mut paths,
+        );
+    }
+    paths.sort();
+    paths
+}
+
+pub(super) fn push_field_paths(
+    value: &dyn PartialReflect,
+    path: String,
+    depth: usize,
+    paths: &mut Vec<String>,
+) {
+    if depth < MAX_COMPLETION_DEPTH {
+        match value.reflect_ref() {
+            ReflectRef::Struct(fields) => {
+                for index in 0..fields.field_len() {
+                    if let (Some(name), Some(field)) =
+                        (fields.name_at(index), fields.field_at(index))
+                    {
+                        push_field_paths(field, format!("{path}.{name}"), depth + 1, paths);
+                    }
+                }
+            }
+            ReflectRef::TupleStruct(fields) => {
+                for (index, field) in fields.iter_fields().enumerate() {
+                    push_field_paths(field, format!("{path}.{index}"), depth + 1, paths);
+                }
+            }
+            _ => {}
+        }
+    }
+    paths.push(path);
+}
@@ -5,9 +5,12 @@ use bevy_falling_sand::prelude::{
     StaticRigidBodyParticleCollider,
 };
 
-use super::parse_position;
 use crate::{
-    console_command::ConsoleCommand, tools::earthquake::RemoveFractureBodyCellAtWorldPosition,
+    console_command::{
+        CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    tools::earthquake::RemoveFractureBodyCellAtWorldPosition,
 };
 
 pub(super) struct RigidBodyConsoleCommandPlugin;
@@ -50,13 +53,14 @@ impl ConsoleCommand for RigidBodyRemoveCellConsoleCommand {
         "Remove one source cell from a particle collider rigid body"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        let Ok(position) = parse_position::<IVec2>(args) else {
-            error!("Usage: rigid_body remove_cell <x>,<y>");
-            return;
-        };
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("position", ArgKind::IPosition))
+    }
 
-        commands.trigger(RemoveFractureBodyCellAtWorldPosition { position });
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let position = args.req("position");
+        world.trigger(RemoveFractureBodyCellAtWorldPosition { position });
+        Ok(CommandOutput::none())
     }
 }
 
@@ -93,9 +97,9 @@ impl ConsoleCommand for RigidBodyDespawnAllConsoleCommand {
         "Despawn all rigid bodies from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all rigid bodies from the world");
-        commands.trigger(DespawnRigidBodiesEvent::all());
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnRigidBodiesEvent::all());
+        Ok(CommandOutput::none())
     }
 }
 
@@ -111,9 +115,9 @@ impl ConsoleCommand for RigidBodyDespawnDynamicConsoleCommand {
         "Despawn dynamic rigid bodies from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all dynamic rigid bodies from the world");
-        commands.trigger(DespawnRigidBodiesEvent::dynamic());
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnRigidBodiesEvent::dynamic());
+        Ok(CommandOutput::none())
     }
 }
 
@@ -129,9 +133,9 @@ impl ConsoleCommand for RigidBodyDespawnStaticConsoleCommand {
         "Despawn static rigid bodies from the world"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        info!("Despawning all static rigid bodies from the world");
-        commands.trigger(DespawnRigidBodiesEvent::static_bodies());
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(DespawnRigidBodiesEvent::static_bodies());
+        Ok(CommandOutput::none())
     }
 }
 
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{console_command::ConsoleCommand, save::SaveApplicationEvent};
+use crate::{
+    console_command::{CommandOutput, CommandResult, ConsoleCommand},
+    save::SaveApplicationEvent,
+};
 
 #[derive(Default)]
 pub struct SaveCommand;
@@ -14,7 +17,8 @@ impl ConsoleCommand for SaveCommand {
         "Save the current application state"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(SaveApplicationEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(SaveApplicationEvent);
+        Ok(CommandOutput::message("Saved application state"))
     }
 }
@@ -2,8 +2,10 @@ use bevy::asset::LoadedFolder;
 use bevy::prelude::*;
 use bevy_falling_sand::prelude::{ParticleSceneRegistry, SpawnSceneSignal};
 
-use super::parse_position;
-use crate::console_command::ConsoleCommand;
+use crate::console_command::{
+    CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand,
+    args::{ArgKind, ArgSchema, ParsedArgs},
+};
 
 #[derive(Default)]
 pub struct SceneConsoleCommand;
@@ -34,58 +36,71 @@ impl ConsoleCommand for SceneSpawnConsoleCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Spawn a scene at a position. Usage: scene spawn <name> <x>,<y> [--overwrite]"
+        "Spawn a scene at a position"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if args.len() < 2 {
-            warn!("Usage: scene spawn <name> <x>,<y> [--overwrite]");
-            return;
-        }
+    fn args(&self) -> Option<ArgSchema> {
+        Some(
+            ArgSchema::new()
+                .arg("name", ArgKind::Text)
+                .arg("position", ArgKind::IPosition)
+                .flag("--overwrite"),
+        )
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let name: String = args.req("name");
+        let center: IVec2 = args.req("position");
+
+        let registry = world.resource::<ParticleSceneRegistry>();
+        let Some((_, handle)) = registry
+            .scenes
+            .iter()
+            .find(|(path, _)| scene_name(path) == name)
+        else {
+            return Err(CommandError::new(format!(
+                "Scene '{name}' not found. Use 'scene list' to see available scenes."
+            )));
+        };
+
+        let handle = handle.clone();
+        world
+            .resource_mut::<Messages<SpawnSceneSignal>>()
+            .write(SpawnSceneSignal::new(handle, center));
+        Ok(CommandOutput::message(format!(
+            "Spawning scene '{name}' at {center}"
+        )))
+    }
 
-        let scene_name = &args[0];
-
-        let position_args: Vec<&String> =
-            args[1..].iter().filter(|a| *a != "--overwrite").collect();
-        let position_slice: Vec<String> = position_args.into_iter().cloned().collect();
-
-        match parse_position::<IVec2>(&position_slice) {
-            Ok(center) => {
-                let name = scene_name.clone();
-                info!("Spawning scene '{name}' at {center}");
-                commands.queue(move |world: &mut World| {
-                    let registry = world.resource::<ParticleSceneRegistry>();
-                    let handle = registry.scenes.iter().find(|(path, _)| {
-                        let filename = path
-                            .rsplit('/')
-                            .next()
-                            .unwrap_or(path)
-                            .strip_suffix(".scn.ron")
-                            .unwrap_or(path);
-                        filename == name
-                    });
-
-                    let Some((_, handle)) = handle else {
-                        warn!(
-                            "Scene '{}' not found. Use 'scene list' to see available scenes.",
-                            name
-                        );
-                        return;
-                    };
-
-                    let handle = handle.clone();
-                    world
-                        .resource_mut::<Messages<SpawnSceneSignal>>()
-                        .write(SpawnSceneSignal::new(handle, center));
-                });
-            }
-            Err(e) => {
-                warn!("{e}");
-            }
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        match args.len() {
+            0 => world
+                .get_resource::<ParticleSceneRegistry>()
+                .map(|registry| {
+                    registry
+                        .scenes
+                        .keys()
+                        .map(|path| scene_name(path).to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => self
+                .args()
+                .map(|schema| schema.complete(args))
+                .unwrap_or_default(),
         }
     }
 }
 
+/// The name a scene is referred to by in the console: its file name without `.scn.ron`.
+fn scene_name(path: &str) -> &str {
+    path.rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .strip_suffix(".scn.ron")
+        .unwrap_or(path)
+}
+
 #[derive(Default)]
 pub struct SceneListConsoleCommand;
 
@@ -98,23 +113,21 @@ impl ConsoleCommand for SceneListConsoleCommand {
         "List available scenes"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.queue(|world: &mut World| {
-            let registry = world.resource::<ParticleSceneRegistry>();
-            if registry.scenes.is_empty() {
-                info!("No scenes loaded");
-            } else {
-                for path in registry.scenes.keys() {
-                    let name = path
-                        .rsplit('/')
-                        .next()
-                        .unwrap_or(path)
-                        .strip_suffix(".scn.ron")
-                        .unwrap_or(path);
-                    info!("  {name}");
-                }
-            }
-        });
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        let registry = world.resource::<ParticleSceneRegistry>();
+        if registry.scenes.is_empty() {
+            return Ok(CommandOutput::message("No scenes loaded"));
+        }
+        let mut names: Vec<String> = registry
+            .scenes
+            .keys()
+            .map(|path| scene_name(path).to_string())
+            .collect();
+        names.sort();
+        Ok(
+            CommandOutput::message(format!("{} scene(s) loaded", names.len()))
+                .with_payload(CommandPayload::List(names)),
+        )
     }
 }
 
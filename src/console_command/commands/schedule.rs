@@ -0,0 +1,625 @@
+use std::{collections::VecDeque, fmt, path::PathBuf, str::FromStr, time::Duration};
+
+use bevy::prelude::*;
+use bevy_falling_sand::core::ParticleSimulationRun;
+use shlex::Shlex;
+use thiserror::Error;
+
+use crate::console_command::{
+    CommandError, CommandOutput, CommandPayload, CommandResult, ConsoleCommand,
+    ConsoleCommandCompleted, ConsoleCommandRegistry, Table,
+    args::{ArgKind, ArgSchema, ParsedArgs},
+    execute,
+};
+
+use super::{find_console_script, join_command_words, read_console_script};
+
+/// Maximum number of scripts a single script may pull in through nested `exec` lines.
+const MAX_NESTED_SCRIPTS: usize = 64;
+
+pub struct ScheduleConsoleCommandPlugin;
+
+impl Plugin for ScheduleConsoleCommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleScheduler>()
+            .add_systems(Update, tick_console_scheduler);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DelayParseError {
+    #[error("invalid delay '{0}' (expected e.g. 500ms, 5s or 60f)")]
+    Invalid(String),
+}
+
+/// How long a scheduled job waits, either in time or in frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Delay {
+    Duration(Duration),
+    Frames(u32),
+}
+
+impl FromStr for Delay {
+    type Err = DelayParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DelayParseError::Invalid(s.to_string());
+        let (value, unit) = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .map_or((s, ""), |index| s.split_at(index));
+        match unit {
+            "f" => value.parse().map(Delay::Frames).map_err(|_| invalid()),
+            "ms" | "s" | "" => {
+                let value: f64 = value.parse().map_err(|_| invalid())?;
+                let seconds = if unit == "ms" { value / 1000.0 } else { value };
+                Duration::try_from_secs_f64(seconds)
+                    .map(Delay::Duration)
+                    .map_err(|_| invalid())
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Delay::Duration(duration) => write!(f, "{:.2}s", duration.as_secs_f64()),
+            Delay::Frames(frames) => write!(f, "{frames}f"),
+        }
+    }
+}
+
+impl Delay {
+    const ZERO: Self = Delay::Frames(0);
+
+    /// Advance by one frame of `delta`, returning `true` once the delay has elapsed.
+    fn tick(&mut self, delta: Duration) -> bool {
+        match self {
+            Delay::Duration(remaining) => *remaining = remaining.saturating_sub(delta),
+            Delay::Frames(remaining) => *remaining = remaining.saturating_sub(1),
+        }
+        self.is_elapsed()
+    }
+
+    fn is_elapsed(&self) -> bool {
+        match self {
+            Delay::Duration(remaining) => remaining.is_zero(),
+            Delay::Frames(remaining) => *remaining == 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ScheduledJobKind {
+    /// Run a command once.
+    After { command: String },
+    /// Run a command repeatedly.
+    Every { command: String, interval: Delay },
+    /// Run the remaining lines of a script, pausing at `wait` lines.
+    Script {
+        path: PathBuf,
+        lines: VecDeque<String>,
+        nested_scripts: usize,
+        /// Cancel the rest of the script once a line fails.
+        stop_on_error: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct ScheduledJob {
+    pub id: u32,
+    pub kind: ScheduledJobKind,
+    pub remaining: Delay,
+    /// Only count down while the particle simulation is running.
+    pub pausable: bool,
+}
+
+/// Console commands waiting to run, either on a delay, on an interval, or as part of a script.
+#[derive(Resource, Default, Debug)]
+pub struct ConsoleScheduler {
+    jobs: Vec<ScheduledJob>,
+    next_id: u32,
+}
+
+impl ConsoleScheduler {
+    pub fn jobs(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    pub fn schedule(&mut self, kind: ScheduledJobKind, delay: Delay, pausable: bool) -> u32 {
+        self.next_id += 1;
+        self.jobs.push(ScheduledJob {
+            id: self.next_id,
+            kind,
+            remaining: delay,
+            pausable,
+        });
+        self.next_id
+    }
+
+    /// Schedule the lines of a script to run from the next frame.
+    pub fn schedule_script(
+        &mut self,
+        path: PathBuf,
+        lines: Vec<String>,
+        stop_on_error: bool,
+    ) -> u32 {
+        self.schedule(
+            ScheduledJobKind::Script {
+                path,
+                lines: lines.into(),
+                nested_scripts: 0,
+                stop_on_error,
+            },
+            Delay::ZERO,
+            false,
+        )
+    }
+
+    pub fn cancel(&mut self, id: u32) -> Option<ScheduledJob> {
+        let index = self.jobs.iter().position(|job| job.id == id)?;
+        Some(self.jobs.remove(index))
+    }
+
+    pub fn cancel_all(&mut self) -> usize {
+        let count = self.jobs.len();
+        self.jobs.clear();
+        count
+    }
+
+    /// Take the next line of a script job, removing the job once it has no lines left.
+    fn next_script_line(&mut self, id: u32) -> Option<(String, bool)> {
+        let index = self.jobs.iter().position(|job| job.id == id)?;
+        let ScheduledJobKind::Script {
+            lines,
+            stop_on_error,
+            ..
+        } = &mut self.jobs[index].kind
+        else {
+            return None;
+        };
+        match lines.pop_front() {
+            Some(line) => Some((line, *stop_on_error)),
+            None => {
+                self.jobs.remove(index);
+                None
+            }
+        }
+    }
+}
+
+fn tick_console_scheduler(world: &mut World) {
+    let delta = world.resource::<Time>().delta();
+    let paused = !world.contains_resource::<ParticleSimulationRun>();
+
+    let mut scheduler = world.resource_mut::<ConsoleScheduler>();
+    if scheduler.jobs.is_empty() {
+        return;
+    }
+
+    let mut queued = Vec::new();
+    let mut due_scripts = Vec::new();
+    scheduler.jobs.retain_mut(|job| {
+        if job.pausable && paused {
+            return true;
+        }
+        if !job.remaining.is_elapsed() && !job.remaining.tick(delta) {
+            return true;
+        }
+
+        match &job.kind {
+            ScheduledJobKind::After { command } => {
+                queued.push(command.clone());
+                false
+            }
+            ScheduledJobKind::Every { command, interval } => {
+                queued.push(command.clone());
+                job.remaining = *interval;
+                true
+            }
+            ScheduledJobKind::Script { .. } => {
+                due_scripts.push(job.id);
+                true
+            }
+        }
+    });
+
+    for input in queued {
+        execute(world, &input);
+    }
+    for id in due_scripts {
+        run_script(world, id);
+    }
+}
+
+/// Run the lines of a script job until it reaches a `wait`, runs out of lines, or fails with
+/// `stop_on_error` set.
+fn run_script(world: &mut World, id: u32) {
+    loop {
+        let Some((line, stop_on_error)) = world
+            .resource_mut::<ConsoleScheduler>()
+            .next_script_line(id)
+        else {
+            return;
+        };
+        let mut tokens = Shlex::new(&line);
+        let directive = match tokens.next().as_deref() {
+            Some("wait") => match tokens.next().map(|delay| delay.parse::<Delay>()) {
+                Some(Ok(delay)) => {
+                    if let Some(job) = world
+                        .resource_mut::<ConsoleScheduler>()
+                        .jobs
+                        .iter_mut()
+                        .find(|job| job.id == id)
+                    {
+                        job.remaining = delay;
+                    }
+                    return;
+                }
+                Some(Err(error)) => Some(Err(CommandError::new(error.to_string()))),
+                None => Some(Err(CommandError::usage(usage_of(world, "wait")))),
+            },
+            // Nested scripts are spliced in place so their lines keep their order relative to
+            // the rest of this script.
+            Some("exec") => Some(
+                splice_script(world, id, tokens.find(|token| !token.starts_with("--")))
+                    .map(|()| CommandOutput::none()),
+            ),
+            _ => None,
+        };
+
+        let failed = match directive {
+            Some(result) => {
+                let failed = result.is_err();
+                world.write_message(ConsoleCommandCompleted {
+                    input: line.clone(),
+                    result,
+                });
+                failed
+            }
+            None => execute(world, &line).iter().any(Result::is_err),
+        };
+
+        if failed && stop_on_error {
+            if let Some(ScheduledJob {
+                kind: ScheduledJobKind::Script { path, .. },
+                ..
+            }) = world.resource_mut::<ConsoleScheduler>().cancel(id)
+            {
+                error!(
+                    "Job {id}: stopped script {} after '{line}' failed",
+                    path.display()
+                );
+            }
+            return;
+        }
+    }
+}
+
+/// Insert the lines of the script `file` at the front of a running script job.
+fn splice_script(world: &mut World, id: u32, file: Option<String>) -> Result<(), CommandError> {
+    let file = file.ok_or_else(|| CommandError::usage(usage_of(world, "exec")))?;
+    let path = find_console_script(world, &file)?;
+    let lines = read_console_script(&path).map_err(|error| {
+        CommandError::new(format!("Failed to read script {}: {error}", path.display()))
+    })?;
+
+    let mut scheduler = world.resource_mut::<ConsoleScheduler>();
+    let Some(ScheduledJob {
+        kind:
+            ScheduledJobKind::Script {
+                lines: remaining,
+                nested_scripts,
+                ..
+            },
+        ..
+    }) = scheduler.jobs.iter_mut().find(|job| job.id == id)
+    else {
+        return Ok(());
+    };
+    *nested_scripts += 1;
+    if *nested_scripts > MAX_NESTED_SCRIPTS {
+        remaining.clear();
+        return Err(CommandError::new(format!(
+            "Too many nested scripts (over {MAX_NESTED_SCRIPTS}), stopping script"
+        )));
+    }
+    for line in lines.into_iter().rev() {
+        remaining.push_front(line);
+    }
+    Ok(())
+}
+
+/// The usage line generated for the built-in command `name`.
+fn usage_of(world: &World, name: &str) -> String {
+    world
+        .resource::<ConsoleCommandRegistry>()
+        .get(name)
+        .and_then(|node| node.usage.clone())
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Arguments shared by `after` and `every`.
+fn schedule_args(delay: &'static str) -> ArgSchema {
+    ArgSchema::new()
+        .flag("--pausable")
+        .arg(delay, ArgKind::Text)
+        .command("command")
+}
+
+/// Read the delay named `name` and the command to schedule from `schedule_args`.
+fn parse_schedule_args(args: &ParsedArgs, name: &str) -> Result<(Delay, String), CommandError> {
+    let delay = args
+        .req::<String>(name)
+        .parse::<Delay>()
+        .map_err(|error| CommandError::new(error.to_string()))?;
+    let command = join_command_words(&args.req::<Vec<String>>("command"));
+    Ok((delay, command))
+}
+
+#[derive(Default)]
+pub struct AfterConsoleCommand;
+
+impl ConsoleCommand for AfterConsoleCommand {
+    fn name(&self) -> &'static str {
+        "after"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run a command after a delay (500ms, 5s, 60f). With --pausable, only counts down while the simulation runs."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(schedule_args("delay"))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let (delay, command) = parse_schedule_args(args, "delay")?;
+        let id = world.resource_mut::<ConsoleScheduler>().schedule(
+            ScheduledJobKind::After {
+                command: command.clone(),
+            },
+            delay,
+            args.flag("--pausable"),
+        );
+        Ok(CommandOutput::message(format!(
+            "Job {id}: '{command}' in {delay}"
+        )))
+    }
+}
+
+#[derive(Default)]
+pub struct EveryConsoleCommand;
+
+impl ConsoleCommand for EveryConsoleCommand {
+    fn name(&self) -> &'static str {
+        "every"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run a command repeatedly on an interval (500ms, 5s, 60f). With --pausable, only counts down while the simulation runs."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(schedule_args("interval"))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let (interval, command) = parse_schedule_args(args, "interval")?;
+        // A zero interval would run the command every frame, and a nested `every` would
+        // schedule another job every frame.
+        if interval.is_elapsed() {
+            return Err(CommandError::new("Interval must be greater than zero"));
+        }
+        let id = world.resource_mut::<ConsoleScheduler>().schedule(
+            ScheduledJobKind::Every {
+                command: command.clone(),
+                interval,
+            },
+            interval,
+            args.flag("--pausable"),
+        );
+        Ok(CommandOutput::message(format!(
+            "Job {id}: '{command}' every {interval}"
+        )))
+    }
+}
+
+#[derive(Default)]
+pub struct WaitConsoleCommand;
+
+impl ConsoleCommand for WaitConsoleCommand {
+    fn name(&self) -> &'static str {
+        "wait"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pause a script for a delay (500ms, 5s, 60f). Only has an effect inside scripts."
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("delay", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, _world: &mut World) -> CommandResult {
+        args.req::<String>("delay")
+            .parse::<Delay>()
+            .map_err(|error| CommandError::new(error.to_string()))?;
+        Ok(CommandOutput::message(
+            "'wait' only has an effect inside scripts run with 'exec'",
+        ))
+    }
+}
+
+#[derive(Default)]
+pub struct JobsConsoleCommand;
+
+impl ConsoleCommand for JobsConsoleCommand {
+    fn name(&self) -> &'static str {
+        "jobs"
+    }
+
+    fn description(&self) -> &'static str {
+        "List scheduled commands and running scripts"
+    }
+
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        let scheduler = world.resource::<ConsoleScheduler>();
+        if scheduler.jobs().is_empty() {
+            return Ok(CommandOutput::message("No scheduled jobs"));
+        }
+        let mut table = Table::new(["Id", "Kind", "Schedule", "Command", "Pausable"]);
+        for job in scheduler.jobs() {
+            let pausable = if job.pausable { "yes" } else { "no" };
+            match &job.kind {
+                ScheduledJobKind::After { command } => table.push_row([
+                    job.id.to_string(),
+                    "after".to_string(),
+                    format!("in {}", job.remaining),
+                    command.clone(),
+                    pausable.to_string(),
+                ]),
+                ScheduledJobKind::Every { command, interval } => table.push_row([
+                    job.id.to_string(),
+                    "every".to_string(),
+                    format!("every {interval}, next in {}", job.remaining),
+                    command.clone(),
+                    pausable.to_string(),
+                ]),
+                ScheduledJobKind::Script { path, lines, .. } => table.push_row([
+                    job.id.to_string(),
+                    "script".to_string(),
+                    format!("{} lines left, resumes in {}", lines.len(), job.remaining),
+                    path.display().to_string(),
+                    pausable.to_string(),
+                ]),
+            }
+        }
+        Ok(
+            CommandOutput::message(format!("{} scheduled job(s)", table.rows.len()))
+                .with_payload(CommandPayload::Table(table)),
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct CancelConsoleCommand;
+
+impl ConsoleCommand for CancelConsoleCommand {
+    fn name(&self) -> &'static str {
+        "cancel"
+    }
+
+    fn description(&self) -> &'static str {
+        "Cancel a scheduled command or running script by id, or all of them with 'all'"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("job", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let target: String = args.req("job");
+        let mut scheduler = world.resource_mut::<ConsoleScheduler>();
+        if target == "all" {
+            let count = scheduler.cancel_all();
+            return Ok(CommandOutput::message(format!("Cancelled {count} job(s)")));
+        }
+        let id = target
+            .parse::<u32>()
+            .map_err(|_| CommandError::new(format!("'{target}' is not a valid job id")))?;
+        match scheduler.cancel(id) {
+            Some(_) => Ok(CommandOutput::message(format!("Cancelled job {id}"))),
+            None => Err(CommandError::new(format!(
+                "No job with id {id}. Use 'jobs' to list scheduled jobs."
+            ))),
+        }
+    }
+
+    fn complete(&self, args: &[String], world: &mut World) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        let scheduler = world.resource::<ConsoleScheduler>();
+        std::iter::once("all".to_string())
+            .chain(scheduler.jobs().iter().map(|job| job.id.to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console_command::split_command_chain;
+
+    #[test]
+    fn delay_parses_times_and_frames() {
+        assert_eq!(
+            "500ms".parse::<Delay>().unwrap(),
+            Delay::Duration(Duration::from_millis(500))
+        );
+        assert_eq!(
+            "1.5s".parse::<Delay>().unwrap(),
+            Delay::Duration(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            "5".parse::<Delay>().unwrap(),
+            Delay::Duration(Duration::from_secs(5))
+        );
+        assert_eq!("60f".parse::<Delay>().unwrap(), Delay::Frames(60));
+    }
+
+    #[test]
+    fn delay_rejects_invalid_values() {
+        for value in [
+            "",
+            "s",
+            "5m",
+            "-1s",
+            "1.5f",
+            "fast",
+            "99999999999999999999999s",
+        ] {
+            assert!(value.parse::<Delay>().is_err(), "{value} should not parse");
+        }
+    }
+
+    #[test]
+    fn delay_ticks_down() {
+        let mut frames = Delay::Frames(2);
+        assert!(!frames.tick(Duration::ZERO));
+        assert!(frames.tick(Duration::ZERO));
+
+        let mut time = Delay::Duration(Duration::from_millis(20));
+        assert!(!time.tick(Duration::from_millis(16)));
+        assert!(time.tick(Duration::from_millis(16)));
+    }
+
+    fn schedule(line: &[&str]) -> (Delay, String) {
+        let words: Vec<String> = line.iter().map(|word| word.to_string()).collect();
+        let args = schedule_args("delay").parse(&words).unwrap();
+        parse_schedule_args(&args, "delay").unwrap()
+    }
+
+    #[test]
+    fn schedule_keeps_quoted_commands() {
+        // `after 1s 'sim pause; sim resume'` arrives as a single word.
+        let (delay, command) = schedule(&["1s", "sim pause; sim resume"]);
+        assert_eq!(delay, Delay::Duration(Duration::from_secs(1)));
+        assert_eq!(command, "sim pause; sim resume");
+        assert_eq!(
+            split_command_chain(&command),
+            ["sim pause", "sim resume"].map(String::from)
+        );
+
+        let (_, command) = schedule(&["5s", "earthquake circle 0,0 40"]);
+        assert_eq!(command, "earthquake circle 0,0 40");
+    }
+
+    #[test]
+    fn schedule_quotes_separate_words() {
+        let (_, command) = schedule(&["--pausable", "60f", "select", "Wet Sand"]);
+        assert_eq!(command, "select 'Wet Sand'");
+    }
+}
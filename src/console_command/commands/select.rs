@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    console_command::ConsoleCommand,
+    console_command::{
+        CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
     tools::select::states::{SelectModeState, SetSelectModeEvent},
 };
 
@@ -51,17 +54,19 @@ impl ConsoleCommand for SelectSetModeCommand {
         "Set the select tool mode (drag, throw)"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        match args[0].to_lowercase().as_str() {
-            "drag" => {
-                info!("Select mode set to Drag");
-                commands.trigger(SetSelectModeEvent(SelectModeState::Drag));
-            }
-            "throw" => {
-                info!("Select mode set to Throw");
-                commands.trigger(SetSelectModeEvent(SelectModeState::Throw));
-            }
-            _ => error!("Invalid select mode. Specify one of 'drag', 'throw'"),
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("mode", ArgKind::Choice(&["drag", "throw"])))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let mode = match args.req::<String>("mode").as_str() {
+            "drag" => SelectModeState::Drag,
+            "throw" => SelectModeState::Throw,
+            _ => unreachable!("validated by the argument schema"),
         };
+        world.trigger(SetSelectModeEvent(mode));
+        Ok(CommandOutput::message(format!(
+            "Select mode set to {mode:?}"
+        )))
     }
 }
@@ -1,14 +1,21 @@
+use super::alias::load_aliases;
 use crate::console_command::ConsoleCommandRegistry;
 use crate::console_command::commands::*;
+use crate::setup::SetupSystems;
 
 pub(super) struct SetupPlugin;
 
 impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(HelpConsoleCommandPlugin)
-            .add_plugins(ParticlesConsoleCommandPlugin)
+        app.add_plugins(ExecConsoleCommandPlugin)
+            .add_plugins(ScheduleConsoleCommandPlugin)
             .add_plugins(RigidBodyConsoleCommandPlugin)
-            .add_systems(Startup, setup_console_command_registry);
+            .add_systems(
+                Startup,
+                (setup_console_command_registry, load_aliases)
+                    .chain()
+                    .after(SetupSystems::Configuration),
+            );
     }
 }
 
@@ -26,5 +33,17 @@ fn setup_console_command_registry(mut commands: Commands) {
     registry.register(SaveCommand);
     registry.register(EarthquakeConsoleCommand);
     registry.register(UiConsoleCommand);
+    registry.register(SimConsoleCommand);
+    registry.register(ExecConsoleCommand);
+    registry.register(AfterConsoleCommand);
+    registry.register(EveryConsoleCommand);
+    registry.register(WaitConsoleCommand);
+    registry.register(JobsConsoleCommand);
+    registry.register(CancelConsoleCommand);
+    registry.register(AliasConsoleCommand);
+    registry.register(UnaliasConsoleCommand);
+    registry.register(GetConsoleCommand);
+    registry.register(SetConsoleCommand);
+    registry.register(LogConsoleCommand);
     commands.insert_resource(registry);
 }
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use super::complete_first_arg;
+use crate::{
+    config::FrameLimitConfig,
+    console_command::{
+        CommandError, CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, ParsedArgs},
+    },
+    frames::SetFrameLimitEvent,
+};
+
+#[derive(Default)]
+pub struct SimConsoleCommand;
+
+impl ConsoleCommand for SimConsoleCommand {
+    fn name(&self) -> &'static str {
+        "sim"
+    }
+
+    fn description(&self) -> &'static str {
+        "Simulation operations"
+    }
+
+    fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
+        vec![Box::new(SimFpsConsoleCommand)]
+    }
+}
+
+#[derive(Default)]
+pub struct SimFpsConsoleCommand;
+
+impl ConsoleCommand for SimFpsConsoleCommand {
+    fn name(&self) -> &'static str {
+        "fps"
+    }
+
+    fn description(&self) -> &'static str {
+        "Set the frame rate limit in frames per second, or turn it off with 'off'"
+    }
+
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("limit", ArgKind::Text))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let value: String = args.req("limit");
+        if value.eq_ignore_ascii_case("off") {
+            world.trigger(SetFrameLimitEvent(FrameLimitConfig::off()));
+            return Ok(CommandOutput::message("Disabled the frame rate limit"));
+        }
+
+        match value.parse::<f64>() {
+            Ok(fps) if fps > 0.0 => {
+                world.trigger(SetFrameLimitEvent(FrameLimitConfig::fps(fps)));
+                Ok(CommandOutput::message(format!(
+                    "Limited the frame rate to {fps} fps"
+                )))
+            }
+            Ok(_) => Err(CommandError::new("Frame rate must be greater than 0")),
+            Err(_) => Err(CommandError::new(format!(
+                "'{value}' is not a valid frame rate"
+            ))),
+        }
+    }
+
+    fn complete(&self, args: &[String], _world: &mut World) -> Vec<String> {
+        complete_first_arg(args, &["off", "30", "60", "120", "144"])
+    }
+}
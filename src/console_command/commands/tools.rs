@@ -1,5 +1,8 @@
 use crate::{
-    console_command::ConsoleCommand,
+    console_command::{
+        CommandOutput, CommandResult, ConsoleCommand,
+        args::{ArgKind, ArgSchema, POSITIVE, ParsedArgs},
+    },
     tools::{SelectedTool, SetSelectedToolEvent, brush::SetSelectedToolBrushSize},
 };
 use bevy::prelude::*;
@@ -33,27 +36,24 @@ impl ConsoleCommand for ToolSelectCommand {
         "Select a tool"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if args.is_empty() {
-            error!("Tool selection required (usage: tool select <painter|earthquake|select>)");
-            return;
-        }
-
-        match args[0].to_lowercase().as_str() {
-            "select" => {
-                info!("Setting selected tool to 'Select'");
-                commands.trigger(SetSelectedToolEvent(SelectedTool::Select));
-            }
-            "painter" => {
-                info!("Setting selected tool to 'Painter'");
-                commands.trigger(SetSelectedToolEvent(SelectedTool::Painter));
-            }
-            "earthquake" => {
-                info!("Setting selected tool to 'Earthquake'");
-                commands.trigger(SetSelectedToolEvent(SelectedTool::Earthquake))
-            }
-            _ => error!("Invalid tool. Specify one of 'select', 'painter', 'earthquake'"),
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg(
+            "tool",
+            ArgKind::Choice(&["select", "painter", "earthquake"]),
+        ))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let tool = match args.req::<String>("tool").as_str() {
+            "select" => SelectedTool::Select,
+            "painter" => SelectedTool::Painter,
+            "earthquake" => SelectedTool::Earthquake,
+            _ => unreachable!("validated by the argument schema"),
         };
+        world.trigger(SetSelectedToolEvent(tool));
+        Ok(CommandOutput::message(format!(
+            "Set selected tool to '{tool:?}'"
+        )))
     }
 }
 
@@ -103,23 +103,13 @@ impl ConsoleCommand for ToolBrushSetSizeCommand {
         "Change the selected tool brush size"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if args.is_empty() {
-            error!("Size value required (usage: tool brush set size <value>)");
-            return;
-        }
-
-        match args[0].parse::<f32>() {
-            Ok(size) if size > 0.0 => {
-                info!("Setting brush size to {}", size);
-                commands.trigger(SetSelectedToolBrushSize(size));
-            }
-            Ok(_) => {
-                error!("Brush size must be greater than 0");
-            }
-            Err(_) => {
-                error!("'{}' is not a valid size value", args[0]);
-            }
-        }
+    fn args(&self) -> Option<ArgSchema> {
+        Some(ArgSchema::new().arg("size", ArgKind::float(POSITIVE)))
+    }
+
+    fn run_with_args(&self, args: &ParsedArgs, world: &mut World) -> CommandResult {
+        let size: f32 = args.req("size");
+        world.trigger(SetSelectedToolBrushSize(size));
+        Ok(CommandOutput::message(format!("Set brush size to {size}")))
     }
 }
@@ -1,7 +1,7 @@
-use crate::console_command::ConsoleCommand;
+use crate::console_command::{CommandError, CommandOutput, CommandResult, ConsoleCommand};
 use crate::ui::{
-    UiToggleCursorOverlayEvent, UiToggleEvent, UiToggleParticleEditorEvent, UiToggleSettingsEvent,
-    UiToggleToolOptionsEvent,
+    UiToggleCursorOverlayEvent, UiToggleEvent, UiToggleParticleEditorEvent,
+    UiToggleReactionGraphEvent, UiToggleSettingsEvent, UiToggleToolOptionsEvent,
 };
 use bevy::prelude::*;
 
@@ -34,12 +34,12 @@ impl ConsoleCommand for UiToggleConsoleCommand {
         "Toggle Ui components"
     }
 
-    fn run(&self, args: &[String], commands: &mut Commands) {
-        if !args.is_empty() {
-            warn!("Invalid argument");
-            return;
+    fn run(&self, args: &[String], world: &mut World) -> CommandResult {
+        if let Some(arg) = args.first() {
+            return Err(CommandError::new(format!("Unknown UI component '{arg}'")));
         }
-        commands.trigger(UiToggleEvent);
+        world.trigger(UiToggleEvent);
+        Ok(CommandOutput::none())
     }
 
     fn subcommands(&self) -> Vec<Box<dyn ConsoleCommand>> {
@@ -48,6 +48,7 @@ impl ConsoleCommand for UiToggleConsoleCommand {
             Box::new(UiToggleSettingsConsoleCommand),
             Box::new(UiToggleCursorOverlayConsoleCommand),
             Box::new(UiToggleToolOptionsConsoleCommand),
+            Box::new(UiToggleReactionGraphConsoleCommand),
         ]
     }
 }
@@ -64,8 +65,9 @@ impl ConsoleCommand for UiToggleParticleEditorConsoleCommand {
         "Toggle Particle Editor"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(UiToggleParticleEditorEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleParticleEditorEvent);
+        Ok(CommandOutput::none())
     }
 }
 
@@ -81,8 +83,9 @@ impl ConsoleCommand for UiToggleSettingsConsoleCommand {
         "Toggle settings"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(UiToggleSettingsEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleSettingsEvent);
+        Ok(CommandOutput::none())
     }
 }
 
@@ -98,8 +101,9 @@ impl ConsoleCommand for UiToggleCursorOverlayConsoleCommand {
         "Toggle Cursor Overlay"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(UiToggleCursorOverlayEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleCursorOverlayEvent);
+        Ok(CommandOutput::none())
     }
 }
 
@@ -115,7 +119,26 @@ impl ConsoleCommand for UiToggleToolOptionsConsoleCommand {
         "Toggle Tool Options"
     }
 
-    fn run(&self, _args: &[String], commands: &mut Commands) {
-        commands.trigger(UiToggleToolOptionsEvent);
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleToolOptionsEvent);
+        Ok(CommandOutput::none())
+    }
+}
+
+#[derive(Default)]
+pub struct UiToggleReactionGraphConsoleCommand;
+
+impl ConsoleCommand for UiToggleReactionGraphConsoleCommand {
+    fn name(&self) -> &'static str {
+        "reaction_graph"
+    }
+
+    fn description(&self) -> &'static str {
+        "Toggle Reaction Graph"
+    }
+
+    fn run(&self, _args: &[String], world: &mut World) -> CommandResult {
+        world.trigger(UiToggleReactionGraphEvent);
+        Ok(CommandOutput::none())
     }
 }
@@ -1,13 +1,23 @@
+use std::sync::Arc;
+
 use bevy::{platform::collections::HashMap, prelude::*};
 use shlex::Shlex;
+use thiserror::Error;
+
+use args::{ArgSchema, ParsedArgs};
 
+pub mod args;
 pub mod commands;
+mod output;
+
+pub use output::{CommandError, CommandOutput, CommandPayload, CommandResult, Table, TreeNode};
 
 pub struct ConsoleCommandPlugin;
 
 impl Plugin for ConsoleCommandPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<ConsoleCommandQueued>()
+            .add_message::<ConsoleCommandCompleted>()
             .add_systems(Update, msgr_console_command_queued);
     }
 }
@@ -23,7 +33,32 @@ pub trait ConsoleCommand: Send + Sync + 'static {
     }
 
     /// Execute this console command with the given arguments.
-    fn run(&self, _args: &[String], _commands: &mut Commands) {}
+    fn run(&self, _args: &[String], _world: &mut World) -> CommandResult {
+        Ok(CommandOutput::none())
+    }
+
+    /// Declare the arguments this command accepts.
+    ///
+    /// Commands with a schema have their arguments validated before they run, and are run with
+    /// [`ConsoleCommand::run_with_args`] instead of [`ConsoleCommand::run`].
+    fn args(&self) -> Option<ArgSchema> {
+        None
+    }
+
+    /// Execute this console command with arguments validated against [`ConsoleCommand::args`].
+    fn run_with_args(&self, _args: &ParsedArgs, _world: &mut World) -> CommandResult {
+        Ok(CommandOutput::none())
+    }
+
+    /// Suggest values for the next argument, given the arguments already typed.
+    ///
+    /// Suggestions are filtered against the partially typed argument by the caller. By default,
+    /// choices and flags from the argument schema are suggested.
+    fn complete(&self, args: &[String], _world: &mut World) -> Vec<String> {
+        self.args()
+            .map(|schema| schema.complete(args))
+            .unwrap_or_default()
+    }
 }
 
 /// Message to queue a console command for execution.
@@ -32,6 +67,25 @@ pub struct ConsoleCommandQueued {
     pub input: String,
 }
 
+/// Message sent with the result of each command run from the console or a script.
+#[derive(Message, Clone, Debug)]
+pub struct ConsoleCommandCompleted {
+    /// The command line that was run, after alias expansion.
+    pub input: String,
+    pub result: CommandResult,
+}
+
+/// Maximum depth of aliases expanding into other aliases.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum AliasError {
+    #[error("'{0}' is a built-in command and cannot be used as an alias")]
+    ShadowsCommand(String),
+    #[error("invalid alias name '{0}'")]
+    InvalidName(String),
+}
+
 /// Stores registered console commands as a tree structure.
 #[derive(Resource, Default)]
 pub struct ConsoleCommandRegistry {
@@ -40,10 +94,49 @@ pub struct ConsoleCommandRegistry {
 
 impl ConsoleCommandRegistry {
     pub fn register(&mut self, command: impl ConsoleCommand) {
-        let node = build_node(Box::new(command));
+        let node = build_node(Box::new(command), &[]);
         self.commands.insert(node.name.clone(), node);
     }
 
+    /// Register or replace a user-defined alias expanding to `expansion`.
+    pub fn register_alias(&mut self, name: &str, expansion: &str) -> Result<(), AliasError> {
+        if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == ';' || c == '$') {
+            return Err(AliasError::InvalidName(name.to_string()));
+        }
+        if self
+            .commands
+            .get(name)
+            .is_some_and(|node| node.alias.is_none())
+        {
+            return Err(AliasError::ShadowsCommand(name.to_string()));
+        }
+        self.commands.insert(
+            name.to_string(),
+            ConsoleCommandNode {
+                name: name.to_string(),
+                description: format!("Alias for '{expansion}'"),
+                alias: Some(expansion.to_string()),
+                ..default()
+            },
+        );
+        Ok(())
+    }
+
+    /// Remove a user-defined alias, returning its expansion if it existed.
+    pub fn remove_alias(&mut self, name: &str) -> Option<String> {
+        if self.commands.get(name)?.alias.is_none() {
+            return None;
+        }
+        self.commands.remove(name)?.alias
+    }
+
+    /// Iterate over user-defined aliases and their expansions.
+    pub fn aliases(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.commands
+            .iter()
+            .filter_map(|(name, node)| Some((name, node.alias.as_ref()?)))
+    }
+
     pub fn get(&self, name: &str) -> Option<&ConsoleCommandNode> {
         self.commands.get(name)
     }
@@ -57,15 +150,21 @@ impl ConsoleCommandRegistry {
     }
 }
 
-type RunFn = Box<dyn Fn(&[String], &mut Commands) + Send + Sync>;
+type RunFn = Arc<dyn Fn(&[String], &mut World) -> CommandResult + Send + Sync>;
+type CompleteFn = Box<dyn Fn(&[String], &mut World) -> Vec<String> + Send + Sync>;
 
 /// Node for command tree
 #[derive(Default)]
 pub struct ConsoleCommandNode {
     pub name: String,
     pub description: String,
+    /// Usage line generated from the command's argument schema, including the command path.
+    pub usage: Option<String>,
     pub children: HashMap<String, ConsoleCommandNode>,
+    /// The command line this node expands to if it is a user-defined alias.
+    pub alias: Option<String>,
     run_fn: Option<RunFn>,
+    complete_fn: Option<CompleteFn>,
 }
 
 impl ConsoleCommandNode {
@@ -82,70 +181,272 @@ impl ConsoleCommandNode {
         self.children.get(&path[0])?.get_node(&path[1..])
     }
 
-    /// Execute this node's run function if it has one.
-    pub fn run(&self, args: &[String], commands: &mut Commands) {
-        if let Some(ref f) = self.run_fn {
-            f(args, commands);
+    /// Find the node that handles `args`, returning its run function and the remaining
+    /// arguments.
+    fn resolve<'a>(&self, args: &'a [String]) -> (Option<RunFn>, &'a [String]) {
+        if let Some(child) = args.first().and_then(|arg| self.children.get(arg)) {
+            return child.resolve(&args[1..]);
         }
+        (self.run_fn.clone(), args)
+    }
+
+    /// Get argument completions for this node given the arguments typed so far.
+    pub fn complete_args(&self, args: &[String], world: &mut World) -> Vec<String> {
+        self.complete_fn
+            .as_ref()
+            .map(|f| f(args, world))
+            .unwrap_or_default()
     }
 }
 
-fn build_node(command: Box<dyn ConsoleCommand>) -> ConsoleCommandNode {
+fn build_node(command: Box<dyn ConsoleCommand>, parent_path: &[String]) -> ConsoleCommandNode {
+    let command: Arc<dyn ConsoleCommand> = Arc::from(command);
     let name = command.name().to_string();
     let description = command.description().to_string();
 
+    let mut path = parent_path.to_vec();
+    path.push(name.clone());
+
     let mut children = HashMap::new();
     for sub in command.subcommands() {
-        let child = build_node(sub);
+        let child = build_node(sub, &path);
         children.insert(child.name.clone(), child);
     }
 
-    let run_fn: RunFn = Box::new(move |args, commands| {
-        command.run(args, commands);
+    let schema = command.args();
+    let usage = schema.as_ref().map(|schema| {
+        format!("{} {}", path.join(" "), schema.usage())
+            .trim_end()
+            .to_string()
     });
 
+    let run_command = command.clone();
+    let run_usage = usage.clone();
+    let run_fn: RunFn = Arc::new(move |args, world| match &schema {
+        Some(schema) => match schema.parse(args) {
+            Ok(parsed) => run_command.run_with_args(&parsed, world),
+            Err(error) => {
+                let error = CommandError::from(error);
+                Err(match &run_usage {
+                    Some(usage) => error.with_usage(usage.clone()),
+                    None => error,
+                })
+            }
+        },
+        None => run_command.run(args, world),
+    });
+    let complete_fn: CompleteFn = Box::new(move |args, world| command.complete(args, world));
+
     ConsoleCommandNode {
         name,
         description,
+        usage,
         children,
+        alias: None,
         run_fn: Some(run_fn),
+        complete_fn: Some(complete_fn),
     }
 }
 
-fn msgr_console_command_queued(
-    mut messages: MessageReader<ConsoleCommandQueued>,
-    registry: Res<ConsoleCommandRegistry>,
-    mut commands: Commands,
+fn msgr_console_command_queued(world: &mut World) {
+    let inputs: Vec<String> = world
+        .resource_mut::<Messages<ConsoleCommandQueued>>()
+        .drain()
+        .map(|msg| msg.input)
+        .collect();
+    for input in inputs {
+        execute(world, &input);
+    }
+}
+
+/// Run a command line now, returning the result of each command in it.
+///
+/// Each result is also sent as a [`ConsoleCommandCompleted`] message.
+pub fn execute(world: &mut World, input: &str) -> Vec<CommandResult> {
+    let mut results = Vec::new();
+    execute_with_depth(world, input, 0, &mut results);
+    results
+}
+
+fn execute_with_depth(
+    world: &mut World,
+    input: &str,
+    depth: usize,
+    results: &mut Vec<CommandResult>,
 ) {
-    for msg in messages.read() {
-        execute(&msg.input, &registry, &mut commands);
+    for segment in split_command_chain(input) {
+        let tokens: Vec<String> = Shlex::new(&segment).collect();
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let registry = world.resource::<ConsoleCommandRegistry>();
+        let Some(node) = registry.get(&tokens[0]) else {
+            let error = CommandError::new(format!("Unknown command: {}", tokens[0]));
+            report(world, segment, Err(error), results);
+            continue;
+        };
+
+        if let Some(expansion) = &node.alias {
+            if depth >= MAX_ALIAS_DEPTH {
+                let error = CommandError::new(format!(
+                    "Alias '{}' expands too deeply; is it recursive?",
+                    tokens[0]
+                ));
+                report(world, segment, Err(error), results);
+                return;
+            }
+            let expanded = expand_alias(expansion, &tokens[1..]);
+            execute_with_depth(world, &expanded, depth + 1, results);
+            continue;
+        }
+
+        // The registry is released before running so commands can use it themselves.
+        let (run_fn, args) = node.resolve(&tokens[1..]);
+        let args = args.to_vec();
+        let result = match run_fn {
+            Some(run_fn) => run_fn(&args, world),
+            None => Ok(CommandOutput::none()),
+        };
+        report(world, segment, result, results);
     }
 }
 
-fn execute(input: &str, registry: &ConsoleCommandRegistry, commands: &mut Commands) {
-    let tokens: Vec<String> = Shlex::new(input).collect();
-    if tokens.is_empty() {
-        return;
+fn report(
+    world: &mut World,
+    input: String,
+    result: CommandResult,
+    results: &mut Vec<CommandResult>,
+) {
+    world.write_message(ConsoleCommandCompleted {
+        input,
+        result: result.clone(),
+    });
+    results.push(result);
+}
+
+/// Split a command line on `;` outside of quotes.
+pub fn split_command_chain(input: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in input.chars() {
+        match (c, quote) {
+            _ if escaped => escaped = false,
+            ('\\', _) => escaped = true,
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (';', None) => {
+                segments.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
     }
+    segments.push(current);
+
+    segments
+        .into_iter()
+        .map(|segment| segment.trim().to_string())
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
 
-    let Some(node) = registry.get(&tokens[0]) else {
-        error!("Unknown command: {}", tokens[0]);
-        return;
+/// Substitute `$1`..`$9` and `$@` in an alias expansion with `args`. Expansions without any
+/// parameters get `args` appended instead.
+fn expand_alias(expansion: &str, args: &[String]) -> String {
+    let quote = |arg: &String| {
+        shlex::try_quote(arg)
+            .map(|quoted| quoted.into_owned())
+            .unwrap_or_else(|_| arg.clone())
     };
 
-    run_node(node, &tokens[1..], commands);
+    let mut expanded = String::with_capacity(expansion.len());
+    let mut uses_params = false;
+    let mut chars = expansion.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.peek().copied() {
+            Some(digit @ '1'..='9') => {
+                chars.next();
+                uses_params = true;
+                let index = digit as usize - '1' as usize;
+                if let Some(arg) = args.get(index) {
+                    expanded.push_str(&quote(arg));
+                }
+            }
+            Some('@') => {
+                chars.next();
+                uses_params = true;
+                expanded.push_str(&args.iter().map(quote).collect::<Vec<_>>().join(" "));
+            }
+            _ => expanded.push(c),
+        }
+    }
+
+    if !uses_params && !args.is_empty() {
+        expanded.push(' ');
+        expanded.push_str(&args.iter().map(quote).collect::<Vec<_>>().join(" "));
+    }
+    expanded
 }
 
-fn run_node(node: &ConsoleCommandNode, args: &[String], commands: &mut Commands) {
-    // If children exist and first arg matches one, recurse
-    if !node.children.is_empty()
-        && !args.is_empty()
-        && let Some(child) = node.children.get(&args[0])
-    {
-        run_node(child, &args[1..], commands);
-        return;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn split_command_chain_on_semicolons() {
+        assert_eq!(
+            split_command_chain("sim pause; earthquake 0,0 10 ;sim resume"),
+            strings(&["sim pause", "earthquake 0,0 10", "sim resume"])
+        );
+        assert_eq!(split_command_chain(" ;; help ; "), strings(&["help"]));
     }
 
-    // Run this node (either leaf or no matching child)
-    node.run(args, commands);
+    #[test]
+    fn split_command_chain_keeps_quoted_and_escaped_semicolons() {
+        assert_eq!(
+            split_command_chain(r#"alias a "x; y"; after 1s 'b; c'; echo d\;e"#),
+            strings(&[r#"alias a "x; y""#, "after 1s 'b; c'", r"echo d\;e"])
+        );
+    }
+
+    #[test]
+    fn expand_alias_substitutes_parameters() {
+        assert_eq!(
+            expand_alias("particles spawn $1 $2", &strings(&["Sand", "4,2"])),
+            "particles spawn Sand 4,2"
+        );
+        assert_eq!(
+            expand_alias("exec $@", &strings(&["a.cfg", "--stop-on-error"])),
+            "exec a.cfg --stop-on-error"
+        );
+        assert_eq!(expand_alias("select $2", &strings(&["a"])), "select ");
+    }
+
+    #[test]
+    fn expand_alias_quotes_arguments() {
+        assert_eq!(
+            expand_alias("select $1", &strings(&["Wet Sand"])),
+            "select 'Wet Sand'"
+        );
+    }
+
+    #[test]
+    fn expand_alias_appends_arguments_without_parameters() {
+        assert_eq!(expand_alias("sim step", &strings(&["10"])), "sim step 10");
+        assert_eq!(expand_alias("sim step", &[]), "sim step");
+        assert_eq!(expand_alias("echo $x", &strings(&["y"])), "echo $x y");
+    }
 }
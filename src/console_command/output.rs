@@ -0,0 +1,226 @@
+//! Results reported by console commands.
+
+use std::fmt;
+
+use bevy::prelude::default;
+use thiserror::Error;
+
+use super::args::ArgError;
+
+/// The outcome of running a console command.
+pub type CommandResult = Result<CommandOutput, CommandError>;
+
+/// What a command reports back when it succeeds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandOutput {
+    /// A short summary of what the command did.
+    pub message: Option<String>,
+    /// Structured data for callers that want more than the message.
+    pub payload: Option<CommandPayload>,
+}
+
+impl CommandOutput {
+    /// Success with nothing to report.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn message(message: impl Into<String>) -> Self {
+        Self {
+            message: Some(message.into()),
+            payload: None,
+        }
+    }
+
+    pub fn with_payload(mut self, payload: CommandPayload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+}
+
+/// Structured data returned by a command.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandPayload {
+    /// A list of values, such as scene or alias names.
+    List(Vec<String>),
+    /// Rows of values under column headers.
+    Table(Table),
+    /// Nested items, such as commands and their subcommands.
+    Tree(Vec<TreeNode>),
+}
+
+impl CommandPayload {
+    /// The payload as lines of text for display in the console.
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            CommandPayload::List(items) => items.clone(),
+            CommandPayload::Table(table) => table.lines(),
+            CommandPayload::Tree(nodes) => {
+                let mut lines = Vec::new();
+                for node in nodes {
+                    node.push_lines(0, &mut lines);
+                }
+                lines
+            }
+        }
+    }
+}
+
+impl fmt::Display for CommandPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+/// A table of text cells. Rows shorter than the header are padded with empty cells.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// The column the rows are sorted by, and whether the order is descending.
+    pub sorted_by: Option<(usize, bool)>,
+}
+
+impl Table {
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            ..default()
+        }
+    }
+
+    pub fn push_row<S: ToString>(&mut self, cells: impl IntoIterator<Item = S>) {
+        let mut row: Vec<String> = cells.into_iter().map(|cell| cell.to_string()).collect();
+        row.resize(row.len().max(self.headers.len()), String::new());
+        self.rows.push(row);
+    }
+
+    /// Sort rows by `column`, comparing numerically when both cells are numbers.
+    pub fn sort_by(&mut self, column: usize, descending: bool) {
+        self.rows.sort_by(|a, b| {
+            let (a, b) = (a.get(column), b.get(column));
+            let ordering = match (
+                a.and_then(|cell| cell.parse::<f64>().ok()),
+                b.and_then(|cell| cell.parse::<f64>().ok()),
+            ) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => a
+                    .map(|cell| cell.to_lowercase())
+                    .cmp(&b.map(|cell| cell.to_lowercase())),
+            };
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.sorted_by = Some((column, descending));
+    }
+
+    /// The width of each column in characters, including the header.
+    pub fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (index, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(index) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+        widths
+    }
+
+    /// The header and rows with columns padded to line up.
+    pub fn lines(&self) -> Vec<String> {
+        let widths = self.column_widths();
+        let format_row = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        std::iter::once(format_row(&self.headers))
+            .chain(self.rows.iter().map(|row| format_row(row)))
+            .collect()
+    }
+
+    /// The table as tab-separated values, for pasting into other programs.
+    pub fn to_tsv(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| row.join("\t"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// An item of a tree payload.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<TreeNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    fn push_lines(&self, depth: usize, lines: &mut Vec<String>) {
+        lines.push(format!("{}{}", "  ".repeat(depth), self.label));
+        for child in &self.children {
+            child.push_lines(depth + 1, lines);
+        }
+    }
+}
+
+/// Why a command failed.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("{message}")]
+pub struct CommandError {
+    pub message: String,
+    /// Usage line to show alongside the error, if the command was called incorrectly.
+    pub usage: Option<String>,
+}
+
+impl CommandError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            usage: None,
+        }
+    }
+
+    /// An error for a command called with the wrong arguments.
+    pub fn usage(usage: impl Into<String>) -> Self {
+        Self {
+            message: "Invalid arguments".to_string(),
+            usage: Some(usage.into()),
+        }
+    }
+
+    pub fn with_usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = Some(usage.into());
+        self
+    }
+}
+
+impl From<ArgError> for CommandError {
+    fn from(error: ArgError) -> Self {
+        Self::new(error.to_string())
+    }
+}
@@ -3,21 +3,29 @@ use bevy::prelude::*;
 use bevy_falling_sand::debug::{ChunkColor, DebugDirtyRects, DebugParticleMap, DirtyRectColor};
 use bevy_persistent::Persistent;
 
-use crate::{config::SettingsConfig, setup::SetupSystems};
+use crate::{
+    config::{AvianDebugConfig, SettingsConfig},
+    setup::SetupSystems,
+};
 
 pub(super) struct SetupPlugin;
 
 impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Startup,
-            (
-                load_bfs_settings
-                    .run_if(condition_bfs_debug_ready)
-                    .in_set(SetupSystems::Debug),
-                load_avian_settings.in_set(SetupSystems::Debug),
-            ),
-        );
+        app.register_type::<AvianDebugConfig>()
+            .add_systems(
+                Startup,
+                (
+                    load_bfs_settings
+                        .run_if(condition_bfs_debug_ready)
+                        .in_set(SetupSystems::Debug),
+                    load_avian_settings.in_set(SetupSystems::Debug),
+                ),
+            )
+            .add_systems(
+                Update,
+                sync_avian_settings.run_if(resource_exists_and_changed::<AvianDebugConfig>),
+            );
     }
 }
 
@@ -54,6 +62,15 @@ fn load_avian_settings(
     commands.insert_resource(avian_config);
 }
 
+/// Apply changes to `AvianDebugConfig`, e.g. from the `set` console command, to the physics gizmos.
+fn sync_avian_settings(
+    mut gizmo_store: ResMut<GizmoConfigStore>,
+    avian_config: Res<AvianDebugConfig>,
+) {
+    let (_, physics_gizmos) = gizmo_store.config_mut::<PhysicsGizmos>();
+    *physics_gizmos = avian_config.clone().into();
+}
+
 fn condition_bfs_debug_ready(
     debug_particle_map: Option<Res<DebugParticleMap>>,
     debug_dirty_rects: Option<Res<DebugDirtyRects>>,
@@ -1,14 +1,34 @@
 use bevy::prelude::*;
 use bevy_framepace::{FramepacePlugin, FramepaceSettings, Limiter};
 
+use crate::config::{FrameLimitConfig, SimulationSettings};
+
 pub(super) struct FramesPlugin;
 
 impl Plugin for FramesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(FramepacePlugin).add_systems(Startup, setup);
+        app.add_plugins(FramepacePlugin)
+            .add_observer(on_set_frame_limit)
+            .add_systems(
+                Update,
+                apply_frame_limit.run_if(resource_changed::<SimulationSettings>),
+            );
     }
 }
 
-fn setup(mut settings: ResMut<FramepaceSettings>) {
-    settings.limiter = Limiter::from_framerate(60.0)
+/// Trigger this event to change the frame limiter at runtime.
+#[derive(Event, Debug)]
+pub struct SetFrameLimitEvent(pub FrameLimitConfig);
+
+fn on_set_frame_limit(trigger: On<SetFrameLimitEvent>, mut simulation: ResMut<SimulationSettings>) {
+    simulation.set_frame_limit(trigger.event().0.clone());
+}
+
+fn apply_frame_limit(simulation: Res<SimulationSettings>, mut settings: ResMut<FramepaceSettings>) {
+    let frame_limit = simulation.effective().frame_limit;
+    settings.limiter = if frame_limit.enabled {
+        Limiter::from_framerate(frame_limit.fps)
+    } else {
+        Limiter::Off
+    };
 }
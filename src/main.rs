@@ -3,6 +3,7 @@
 
 mod camera;
 mod chunk_effects;
+mod cli;
 mod config;
 mod console_command;
 mod cursor;
@@ -23,6 +24,7 @@ use chunk_effects::ChunkEffectsPlugin;
 use game_of_life::GameOfLifePlugin;
 
 use camera::CameraPlugin;
+use cli::*;
 use config::*;
 use console_command::*;
 pub use cursor::*;
@@ -34,7 +36,11 @@ use record::*;
 use save::*;
 use tools::*;
 
-use bevy::{log::LogPlugin, prelude::*, window::WindowMode};
+use bevy::{
+    log::LogPlugin,
+    prelude::*,
+    window::{WindowMode, WindowResolution},
+};
 
 use crate::particles::ParticlesPlugin;
 use crate::setup::SetupPlugin;
@@ -42,17 +48,44 @@ use crate::ui::UiPlugin;
 use crate::ui::console_capture_layer;
 
 fn main() {
+    let cli = match CliArgs::from_env() {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        println!("{USAGE}");
+        return;
+    }
+
+    let mut config_plugin = ConfigPlugin::default();
+    if let Some(config_dir) = cli.config_dir {
+        config_plugin.config_path = config_dir;
+    }
+    config_plugin.active_world = cli.world;
+    let simulation = config_plugin.simulation_config();
+
+    let mut window = Window {
+        title: "Falling Sand Editor".into(),
+        mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
+        fit_canvas_to_parent: true,
+        ..default()
+    };
+    if cli.windowed {
+        window.mode = WindowMode::Windowed;
+    }
+    if let Some(size) = cli.size {
+        window.resolution = WindowResolution::new(size.x, size.y);
+    }
+
     App::new()
         .add_plugins((
             DefaultPlugins
                 .set(ImagePlugin::default_nearest())
                 .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "Falling Sand Editor".into(),
-                        mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
-                        fit_canvas_to_parent: true,
-                        ..default()
-                    }),
+                    primary_window: Some(window),
                     ..default()
                 })
                 .set(LogPlugin {
@@ -61,7 +94,7 @@ fn main() {
                     custom_layer: console_capture_layer,
                     ..default()
                 }),
-            ConfigPlugin::default(),
+            config_plugin,
             SetupPlugin,
             ExitPlugin,
             CursorPlugin,
@@ -69,8 +102,8 @@ fn main() {
             UiPlugin,
             ConsoleCommandPlugin,
             FallingSandPlugin::default()
-                .with_chunk_size(64)
-                .with_map_size(32),
+                .with_chunk_size(simulation.chunk_size)
+                .with_map_size(simulation.map_size),
             ParticlesPlugin,
             ChunkEffectsPlugin,
             // This path is overwritten with the active world path as soon as the app configuration is loaded.
@@ -81,6 +114,9 @@ fn main() {
             SavePlugin,
         ))
         .add_plugins((ToolsPlugin, GameOfLifePlugin, FramesPlugin))
+        .add_plugins(CliPlugin {
+            startup_commands: cli.startup_commands,
+        })
         .insert_gizmo_config(
             PhysicsGizmos {
                 collider_color: None,
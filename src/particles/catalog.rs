@@ -0,0 +1,446 @@
+//! Writes a static catalog of the particle set, with a page per category, for documentation and
+//! design review.
+
+use std::{
+    any::TypeId,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::serde::TypedReflectSerializer,
+};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::{ColorProfile, ColorSource},
+};
+use ron::ser::PrettyConfig;
+
+use super::{ParticleCategory, ParticleGraph, ParticleName};
+
+/// File format of a particle catalog.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CatalogFormat {
+    #[default]
+    Html,
+    Markdown,
+}
+
+impl CatalogFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            CatalogFormat::Html => "html",
+            CatalogFormat::Markdown => "md",
+        }
+    }
+}
+
+/// What [`write_particle_catalog`] wrote.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParticleCatalog {
+    /// The index page followed by a page per category.
+    pub pages: Vec<PathBuf>,
+    pub particle_types: usize,
+}
+
+/// Colors shown as a particle type's swatch.
+enum Swatch {
+    Palette(Vec<String>),
+    Gradient(Vec<String>),
+    Texture(String),
+}
+
+struct CatalogEntry {
+    id: ParticleTypeId,
+    name: String,
+    category: String,
+    swatch: Option<Swatch>,
+    /// Every reflected component except the type's id, name and category, as pretty RON.
+    properties: Vec<(String, String)>,
+    /// Transformations into, or acting on, other types, as the other type's index and a
+    /// description.
+    outgoing: Vec<(usize, String)>,
+    /// Transformations from, or acting on this type by, other types.
+    incoming: Vec<(usize, String)>,
+}
+
+/// Write an index page and a page per [`ParticleCategory`] into `dir`, describing every particle
+/// type with a color swatch, all of its configured properties, and links to the types it turns
+/// into or is produced by.
+pub fn write_particle_catalog(
+    world: &mut World,
+    dir: &Path,
+    format: CatalogFormat,
+) -> Result<ParticleCatalog, String> {
+    let graph = ParticleGraph::build(world);
+    let mut entries: Vec<CatalogEntry> = graph
+        .nodes
+        .iter()
+        .map(|node| CatalogEntry {
+            id: node.id,
+            name: node.name.clone(),
+            category: node.category.clone(),
+            swatch: world
+                .get::<ColorProfile>(node.entity)
+                .map(|profile| swatch(&profile.source)),
+            properties: reflected_properties(world, node.entity),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        })
+        .collect();
+    for edge in &graph.edges {
+        let description = format!("{}: {}", edge.transformation, edge.label);
+        entries[edge.from]
+            .outgoing
+            .push((edge.to, description.clone()));
+        entries[edge.to].incoming.push((edge.from, description));
+    }
+
+    // Nodes are ordered by category, so each category's entries are contiguous.
+    let mut categories: Vec<(String, String)> = Vec::new();
+    let mut taken = HashSet::default();
+    for entry in &entries {
+        if categories
+            .last()
+            .is_none_or(|(name, _)| *name != entry.category)
+        {
+            let stem = unique_stem(&entry.category, &mut taken);
+            categories.push((entry.category.clone(), stem));
+        }
+    }
+    let pages: HashMap<&str, String> = categories
+        .iter()
+        .map(|(name, stem)| (name.as_str(), format!("{stem}.{}", format.extension())))
+        .collect();
+    let href = |index: usize| {
+        let entry: &CatalogEntry = &entries[index];
+        format!("{}#{}", pages[entry.category.as_str()], anchor(entry.id))
+    };
+
+    fs::create_dir_all(dir)
+        .map_err(|error| format!("Failed to create {}: {error}", dir.display()))?;
+    let write = |file: &str, contents: String| -> Result<PathBuf, String> {
+        let path = dir.join(file);
+        fs::write(&path, contents)
+            .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+        Ok(path)
+    };
+
+    let index_file = match format {
+        CatalogFormat::Html => "index.html",
+        CatalogFormat::Markdown => "README.md",
+    };
+    let mut catalog = ParticleCatalog {
+        pages: vec![write(
+            index_file,
+            render_index(&categories, &entries, &pages, format),
+        )?],
+        particle_types: entries.len(),
+    };
+    for (category, _) in &categories {
+        let members: Vec<&CatalogEntry> = entries
+            .iter()
+            .filter(|entry| entry.category == *category)
+            .collect();
+        let page = &pages[category.as_str()];
+        let contents = match format {
+            CatalogFormat::Html => {
+                render_html_page(category, index_file, &members, &entries, &href)
+            }
+            CatalogFormat::Markdown => {
+                render_markdown_page(category, index_file, &members, &entries, &href)
+            }
+        };
+        catalog.pages.push(write(page, contents)?);
+    }
+    Ok(catalog)
+}
+
+fn render_index(
+    categories: &[(String, String)],
+    entries: &[CatalogEntry],
+    pages: &HashMap<&str, String>,
+    format: CatalogFormat,
+) -> String {
+    let count = |category: &str| {
+        entries
+            .iter()
+            .filter(|entry| entry.category == category)
+            .count()
+    };
+    let mut out = String::new();
+    match format {
+        CatalogFormat::Html => {
+            out.push_str(&html_header("Particle catalog"));
+            out.push_str("<h1>Particle catalog</h1>\n<ul>\n");
+            for (category, _) in categories {
+                let _ = writeln!(
+                    out,
+                    "<li><a href=\"{}\">{}</a> ({})</li>",
+                    escape_html(&pages[category.as_str()]),
+                    escape_html(category),
+                    count(category)
+                );
+            }
+            out.push_str("</ul>\n</body>\n</html>\n");
+        }
+        CatalogFormat::Markdown => {
+            out.push_str("# Particle catalog\n\n");
+            for (category, _) in categories {
+                let _ = writeln!(
+                    out,
+                    "- [{}]({}) ({})",
+                    category,
+                    pages[category.as_str()],
+                    count(category)
+                );
+            }
+        }
+    }
+    out
+}
+
+fn render_html_page(
+    category: &str,
+    index_file: &str,
+    members: &[&CatalogEntry],
+    entries: &[CatalogEntry],
+    href: &impl Fn(usize) -> String,
+) -> String {
+    let mut out = html_header(category);
+    let _ = writeln!(
+        out,
+        "<p><a href=\"{index_file}\">Particle catalog</a></p>\n<h1>{}</h1>",
+        escape_html(category)
+    );
+    for entry in members {
+        let _ = writeln!(
+            out,
+            "<section id=\"{}\">\n<h2>{} <small>id {}</small></h2>",
+            anchor(entry.id),
+            escape_html(&entry.name),
+            entry.id.get()
+        );
+        match &entry.swatch {
+            Some(Swatch::Palette(colors)) => {
+                out.push_str("<div class=\"swatch\">");
+                for color in colors {
+                    let _ = write!(
+                        out,
+                        "<span style=\"background: {color}\" title=\"{color}\"></span>"
+                    );
+                }
+                out.push_str("</div>\n");
+            }
+            Some(Swatch::Gradient(colors)) => {
+                let stops = match colors.as_slice() {
+                    [color] => format!("{color}, {color}"),
+                    colors => colors.join(", "),
+                };
+                let _ = writeln!(
+                    out,
+                    "<div class=\"swatch gradient\" style=\"background: linear-gradient(to right, {stops})\" title=\"{}\"></div>",
+                    colors.join(" → ")
+                );
+            }
+            Some(Swatch::Texture(path)) => {
+                let _ = writeln!(out, "<p>Texture: <code>{}</code></p>", escape_html(path));
+            }
+            None => {}
+        }
+
+        for (title, links) in [
+            ("Turns into or acts on", &entry.outgoing),
+            ("Produced or acted on by", &entry.incoming),
+        ] {
+            if links.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "<h3>{title}</h3>\n<ul>");
+            for (index, description) in links {
+                let _ = writeln!(
+                    out,
+                    "<li><a href=\"{}\">{}</a> ({})</li>",
+                    escape_html(&href(*index)),
+                    escape_html(&entries[*index].name),
+                    escape_html(description)
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+
+        out.push_str("<h3>Properties</h3>\n<table>\n");
+        for (component, value) in &entry.properties {
+            let _ = writeln!(
+                out,
+                "<tr><th>{}</th><td><pre>{}</pre></td></tr>",
+                escape_html(component),
+                escape_html(value)
+            );
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn render_markdown_page(
+    category: &str,
+    index_file: &str,
+    members: &[&CatalogEntry],
+    entries: &[CatalogEntry],
+    href: &impl Fn(usize) -> String,
+) -> String {
+    let mut out = format!("[Particle catalog]({index_file})\n\n# {category}\n");
+    for entry in members {
+        let _ = writeln!(
+            out,
+            "\n<a id=\"{}\"></a>\n\n## {} (id {})\n",
+            anchor(entry.id),
+            entry.name,
+            entry.id.get()
+        );
+        match &entry.swatch {
+            Some(Swatch::Palette(colors)) => {
+                let _ = writeln!(out, "Palette: `{}`\n", colors.join("` `"));
+            }
+            Some(Swatch::Gradient(colors)) => {
+                let _ = writeln!(out, "Gradient: `{}`\n", colors.join("` → `"));
+            }
+            Some(Swatch::Texture(path)) => {
+                let _ = writeln!(out, "Texture: `{path}`\n");
+            }
+            None => {}
+        }
+
+        for (title, links) in [
+            ("Turns into or acts on", &entry.outgoing),
+            ("Produced or acted on by", &entry.incoming),
+        ] {
+            if links.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "### {title}\n");
+            for (index, description) in links {
+                let _ = writeln!(
+                    out,
+                    "- [{}]({}) ({description})",
+                    entries[*index].name,
+                    href(*index)
+                );
+            }
+            out.push('\n');
+        }
+
+        out.push_str("### Properties\n");
+        for (component, value) in &entry.properties {
+            let _ = writeln!(out, "\n**{component}**\n\n```ron\n{value}\n```");
+        }
+    }
+    out
+}
+
+fn html_header(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 960px; margin: 2em auto; }}\n\
+         section {{ border-top: 1px solid #ccc; padding-top: 0.5em; }}\n\
+         .swatch {{ display: flex; height: 24px; border: 1px solid #888; }}\n\
+         .swatch span {{ flex: 1; }}\n\
+         th {{ text-align: left; vertical-align: top; padding-right: 1em; }}\n\
+         pre {{ margin: 0; }}\n\
+         </style>\n</head>\n<body>\n",
+        escape_html(title)
+    )
+}
+
+/// Every reflected component of a particle type except its id, name and category, sorted by
+/// short type name.
+fn reflected_properties(world: &World, entity: Entity) -> Vec<(String, String)> {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let skipped = [
+        TypeId::of::<ParticleType>(),
+        TypeId::of::<ParticleName>(),
+        TypeId::of::<ParticleCategory>(),
+    ];
+    let entity_ref = world.entity(entity);
+    let mut properties: Vec<(String, String)> = type_registry
+        .iter()
+        .filter(|registration| !skipped.contains(&registration.type_id()))
+        .filter_map(|registration| {
+            let component = registration
+                .data::<ReflectComponent>()?
+                .reflect(entity_ref)?
+                .as_partial_reflect();
+            let value = ron::ser::to_string_pretty(
+                &TypedReflectSerializer::new(component, &type_registry),
+                PrettyConfig::default(),
+            )
+            .unwrap_or_else(|_| format!("{component:?}"));
+            Some((
+                registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_string(),
+                value,
+            ))
+        })
+        .collect();
+    properties.sort();
+    properties
+}
+
+fn swatch(source: &ColorSource) -> Swatch {
+    let hex = |color: &Color| Srgba::from(*color).to_hex();
+    match source {
+        ColorSource::Palette(palette) => Swatch::Palette(palette.colors.iter().map(hex).collect()),
+        ColorSource::Gradient(gradient) => {
+            Swatch::Gradient(gradient.colors.iter().map(hex).collect())
+        }
+        ColorSource::Texture(texture) => Swatch::Texture(texture.path.clone()),
+    }
+}
+
+fn anchor(id: ParticleTypeId) -> String {
+    format!("particle-{}", id.get())
+}
+
+/// A file name stem for `category`, unique among `taken`.
+fn unique_stem(category: &str, taken: &mut HashSet<String>) -> String {
+    let mut stem: String = category
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    stem = stem.trim_matches('-').to_string();
+    // The index is written beside the category pages.
+    if stem.is_empty() || stem == "index" || stem == "readme" {
+        stem = format!("category-{stem}").trim_end_matches('-').to_string();
+    }
+    let stem = (1..)
+        .map(|n| match n {
+            1 => stem.clone(),
+            n => format!("{stem}-{n}"),
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_default();
+    taken.insert(stem.clone());
+    stem
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
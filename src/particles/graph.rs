@@ -0,0 +1,269 @@
+//! A graph of how particle types turn into one another, for display and Graphviz export.
+
+use std::{fmt, fmt::Write, time::Duration};
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::{
+        ChanceMutation, ContactOutcome, ContactReaction, Corrodible, Corrosive, Flammable,
+        TimedMutation,
+    },
+};
+
+use super::{ParticleCategory, ParticleName};
+
+/// How one particle type turns into, or acts on, another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleTransformation {
+    ChanceMutation,
+    TimedMutation,
+    Contact,
+    Burns,
+    Corrodes,
+}
+
+impl fmt::Display for ParticleTransformation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParticleTransformation::ChanceMutation => write!(f, "mutates"),
+            ParticleTransformation::TimedMutation => write!(f, "mutates"),
+            ParticleTransformation::Contact => write!(f, "contact"),
+            ParticleTransformation::Burns => write!(f, "burns"),
+            ParticleTransformation::Corrodes => write!(f, "corrodes"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParticleGraphNode {
+    pub entity: Entity,
+    pub id: ParticleTypeId,
+    pub name: String,
+    pub category: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ParticleGraphEdge {
+    /// Index of the source node.
+    pub from: usize,
+    /// Index of the target node.
+    pub to: usize,
+    pub transformation: ParticleTransformation,
+    /// Chance, timing and radius of the transformation.
+    pub label: String,
+}
+
+/// Every particle type and the transformations between them.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct ParticleGraph {
+    pub nodes: Vec<ParticleGraphNode>,
+    pub edges: Vec<ParticleGraphEdge>,
+}
+
+impl ParticleGraph {
+    /// Build the graph from the particle types in the world, ordered by category and name.
+    pub fn build(world: &mut World) -> Self {
+        let mut query = world.query::<(
+            Entity,
+            &ParticleType,
+            Option<&ParticleName>,
+            Option<&ParticleCategory>,
+            Option<&ChanceMutation>,
+            Option<&TimedMutation>,
+            Option<&ContactReaction>,
+            Option<&Flammable>,
+            Option<&Corrosive>,
+            Option<&Corrodible>,
+        )>();
+
+        let mut nodes: Vec<ParticleGraphNode> = query
+            .iter(world)
+            .map(
+                |(entity, particle_type, name, category, ..)| ParticleGraphNode {
+                    entity,
+                    id: particle_type.id(),
+                    name: name
+                        .map(|name| name.0.clone())
+                        .unwrap_or_else(|| format!("Particle {}", particle_type.id().get())),
+                    category: category
+                        .map(|category| category.0.clone())
+                        .unwrap_or_else(|| String::from("Other")),
+                },
+            )
+            .collect();
+        nodes.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let index: HashMap<ParticleTypeId, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id, i))
+            .collect();
+        let corrodible: Vec<usize> = query
+            .iter(world)
+            .filter(|(.., corrodible)| corrodible.is_some())
+            .filter_map(|(_, particle_type, ..)| index.get(&particle_type.id()).copied())
+            .collect();
+
+        let mut edges = Vec::new();
+        let mut edge = |from: usize, to: ParticleTypeId, transformation, label: String| {
+            // Dangling targets are reported by validation, not drawn.
+            if let Some(&to) = index.get(&to) {
+                edges.push(ParticleGraphEdge {
+                    from,
+                    to,
+                    transformation,
+                    label,
+                });
+            }
+        };
+        for (
+            _,
+            particle_type,
+            _,
+            _,
+            chance_mutation,
+            timed_mutation,
+            contact_reaction,
+            burns,
+            corrosive,
+            _,
+        ) in query.iter(world)
+        {
+            let from = index[&particle_type.id()];
+            if let Some(mutation) = chance_mutation {
+                edge(
+                    from,
+                    mutation.target,
+                    ParticleTransformation::ChanceMutation,
+                    format!(
+                        "{} every {}",
+                        percent(mutation.chance),
+                        milliseconds(mutation.tick_timer.duration())
+                    ),
+                );
+            }
+            if let Some(mutation) = timed_mutation {
+                edge(
+                    from,
+                    mutation.target,
+                    ParticleTransformation::TimedMutation,
+                    format!("after {}", milliseconds(mutation.duration())),
+                );
+            }
+            if let Some(contact_reaction) = contact_reaction {
+                for rule in &contact_reaction.rules {
+                    let with = index.get(&rule.target).map_or_else(
+                        || format!("particle type {}", rule.target.get()),
+                        |&target| nodes[target].name.clone(),
+                    );
+                    let details = format!("{}, r={}", percent(rule.chance), rule.radius);
+                    if let ContactOutcome::Becomes(becomes) = &rule.source_outcome {
+                        edge(
+                            from,
+                            *becomes,
+                            ParticleTransformation::Contact,
+                            format!("touching {with}, {details}"),
+                        );
+                    }
+                    if let (ContactOutcome::Becomes(becomes), Some(&target)) =
+                        (&rule.target_outcome, index.get(&rule.target))
+                    {
+                        edge(
+                            target,
+                            *becomes,
+                            ParticleTransformation::Contact,
+                            format!("touched by {}, {details}", nodes[from].name),
+                        );
+                    }
+                }
+            }
+            if let Some(reaction) = burns.and_then(|burns| burns.reaction.as_ref()) {
+                edge(
+                    from,
+                    reaction.produces,
+                    ParticleTransformation::Burns,
+                    percent(reaction.chance_to_produce),
+                );
+            }
+            if let Some(corrosive) = corrosive {
+                for &target in &corrodible {
+                    edge(
+                        from,
+                        nodes[target].id,
+                        ParticleTransformation::Corrodes,
+                        format!(
+                            "{} every {}",
+                            percent(corrosive.chance),
+                            milliseconds(corrosive.tick_timer.duration())
+                        ),
+                    );
+                }
+            }
+        }
+
+        Self { nodes, edges }
+    }
+
+    /// Render the graph in Graphviz DOT, clustering nodes by category.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph particles {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=rounded];\n");
+
+        let mut categories: Vec<&str> = self
+            .nodes
+            .iter()
+            .map(|node| node.category.as_str())
+            .collect();
+        categories.dedup();
+        for (i, category) in categories.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{i} {{");
+            let _ = writeln!(dot, "        label={};", quote(category));
+            for node in self.nodes.iter().filter(|node| node.category == *category) {
+                let _ = writeln!(
+                    dot,
+                    "        p{} [label={}];",
+                    node.id.get(),
+                    quote(&node.name)
+                );
+            }
+            dot.push_str("    }\n");
+        }
+
+        for edge in &self.edges {
+            let style = match edge.transformation {
+                ParticleTransformation::ChanceMutation | ParticleTransformation::TimedMutation => {
+                    "solid"
+                }
+                ParticleTransformation::Contact => "bold",
+                ParticleTransformation::Burns => "dashed",
+                ParticleTransformation::Corrodes => "dotted",
+            };
+            let _ = writeln!(
+                dot,
+                "    p{} -> p{} [label={}, style={style}];",
+                self.nodes[edge.from].id.get(),
+                self.nodes[edge.to].id.get(),
+                quote(&format!("{}: {}", edge.transformation, edge.label))
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn percent(chance: f64) -> String {
+    format!("{}%", (chance * 1000.0).round() / 10.0)
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,126 @@
+//! Reloads the particle set when its file is changed on disk, e.g. by a text editor or a script.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use bevy::prelude::*;
+use bevy_falling_sand::prelude::ParticleTypesPersistedSignal;
+
+use super::{read_particle_set, reload_particle_types};
+use crate::config::ParticleTypesFile;
+
+/// How often the particle types file is checked for changes, in seconds.
+const POLL_INTERVAL_SECS: f32 = 0.5;
+
+pub(super) struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleTypesHotReload>().add_systems(
+            Update,
+            (track_persisted_particle_types, poll_particle_types_file)
+                .chain()
+                .run_if(resource_exists::<ParticleTypesFile>),
+        );
+    }
+}
+
+/// Watches the [`ParticleTypesFile`] for changes made outside the editor.
+#[derive(Resource)]
+pub struct ParticleTypesHotReload {
+    /// Why the file couldn't be reloaded after its last change, if it couldn't.
+    pub error: Option<String>,
+    /// Particle types that are no longer in the file after the last reload. They are kept until
+    /// the user confirms their deletion, so a bad write can't wipe materials from the map.
+    pub missing: Vec<String>,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    timer: Timer,
+}
+
+impl Default for ParticleTypesHotReload {
+    fn default() -> Self {
+        Self {
+            error: None,
+            missing: Vec::new(),
+            path: PathBuf::new(),
+            modified: None,
+            timer: Timer::from_seconds(POLL_INTERVAL_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Writing the particle set from the editor changes the file too, which shouldn't reload it.
+fn track_persisted_particle_types(
+    mut msgr_persisted: MessageReader<ParticleTypesPersistedSignal>,
+    mut hot_reload: ResMut<ParticleTypesHotReload>,
+) {
+    for persisted in msgr_persisted.read() {
+        if persisted.0 == hot_reload.path {
+            hot_reload.modified = modified_time(&hot_reload.path);
+        }
+    }
+}
+
+fn poll_particle_types_file(
+    mut commands: Commands,
+    time: Res<Time>,
+    particle_types_file: Res<ParticleTypesFile>,
+    mut hot_reload: ResMut<ParticleTypesHotReload>,
+) {
+    if !hot_reload.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    // A different particle set was selected, and is loaded by whoever selected it.
+    if hot_reload.path != particle_types_file.0 {
+        hot_reload.path = particle_types_file.0.clone();
+        hot_reload.modified = modified_time(&hot_reload.path);
+        hot_reload.error = None;
+        hot_reload.missing.clear();
+        return;
+    }
+    let modified = modified_time(&hot_reload.path);
+    if modified.is_none() || modified == hot_reload.modified {
+        return;
+    }
+    hot_reload.modified = modified;
+
+    let path = hot_reload.path.clone();
+    commands.queue(move |world: &mut World| {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let types = read_particle_set(&path, &type_registry.read());
+        let reload = types.and_then(|types| reload_particle_types(world, &types));
+        let mut hot_reload = world.resource_mut::<ParticleTypesHotReload>();
+        match reload {
+            Ok(reload) => {
+                hot_reload.error = None;
+                if !reload.added.is_empty() || !reload.updated.is_empty() {
+                    info!(
+                        "Reloaded {:?}: added {:?}, updated {:?}",
+                        path, reload.added, reload.updated
+                    );
+                }
+                if !reload.missing.is_empty() {
+                    warn!(
+                        "Particle types missing from {:?} were kept: {:?}",
+                        path, reload.missing
+                    );
+                }
+                hot_reload.missing = reload.missing;
+            }
+            Err(error) => {
+                error!("Failed to reload particle types, keeping the current set: {error}");
+                hot_reload.error = Some(error);
+            }
+        }
+    });
+}
@@ -0,0 +1,716 @@
+//! Imports particle types from another particle set file into the current set, without
+//! replacing it.
+
+use std::{
+    any::TypeId,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::{PartialReflect, TypeRegistry},
+    scene::serde::SceneDeserializer,
+};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::SyncParticleTypeChildrenSignal,
+};
+use serde::de::DeserializeSeed;
+
+use super::{
+    ParticleCategory, ParticleName, remap_particle_references, remap_reflected_references,
+};
+
+/// A particle type read from a particle set file.
+pub struct ImportedParticleType {
+    /// The id the type has in its file, which is not kept when importing.
+    pub id: ParticleTypeId,
+    pub name: String,
+    pub category: String,
+    components: Vec<Box<dyn PartialReflect>>,
+}
+
+/// What to do with an imported particle type whose name is already taken.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportResolution {
+    /// Keep the existing type. References to the imported type are pointed at it.
+    Skip,
+    /// Import under a different name.
+    Rename(String),
+    /// Replace the existing type's components, keeping its id and live particles.
+    Overwrite,
+}
+
+/// What [`import_particle_types`] changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParticleImport {
+    pub imported: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub skipped: Vec<String>,
+    /// References to types that are neither imported nor present, which were removed.
+    pub dropped_references: usize,
+}
+
+/// Read the particle types in a particle set file.
+pub fn read_particle_set(
+    path: &Path,
+    type_registry: &TypeRegistry,
+) -> Result<Vec<ImportedParticleType>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    let invalid = |error: &dyn std::fmt::Display| {
+        format!("{} is not a particle set: {error}", path.display())
+    };
+    let mut deserializer = ron::Deserializer::from_str(&text).map_err(|error| invalid(&error))?;
+    let scene = SceneDeserializer { type_registry }
+        .deserialize(&mut deserializer)
+        .map_err(|error| invalid(&error))?;
+
+    let mut types: Vec<ImportedParticleType> = scene
+        .entities
+        .into_iter()
+        .filter_map(|entity| {
+            let id = find_component(&entity.components, TypeId::of::<ParticleType>())
+                .and_then(ParticleType::from_reflect)?
+                .id();
+            let name = find_component(&entity.components, TypeId::of::<ParticleName>())
+                .and_then(ParticleName::from_reflect)
+                .map_or_else(|| format!("Particle {}", id.get()), |name| name.0);
+            let category = find_component(&entity.components, TypeId::of::<ParticleCategory>())
+                .and_then(ParticleCategory::from_reflect)
+                .map_or_else(|| String::from("Other"), |category| category.0);
+            Some(ImportedParticleType {
+                id,
+                name,
+                category,
+                components: entity.components,
+            })
+        })
+        .collect();
+    types.sort_by(|a, b| {
+        a.category
+            .cmp(&b.category)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(types)
+}
+
+/// The existing particle type with the same name as `name`, ignoring case.
+pub fn existing_particle_type(world: &mut World, name: &str) -> Option<(Entity, ParticleTypeId)> {
+    world
+        .query::<(Entity, &ParticleType, &ParticleName)>()
+        .iter(world)
+        .find(|(_, _, existing)| existing.0.eq_ignore_ascii_case(name))
+        .map(|(entity, particle_type, _)| (entity, particle_type.id()))
+}
+
+/// Import the `selection` of `types`, each paired with how to resolve a collision with an
+/// existing type of the same name. Types that don't collide are imported under their own name.
+///
+/// Imported types get new ids, and references between them are rewritten to match. References
+/// to types that weren't selected are pointed at the existing type of the same name, or removed
+/// if there is none.
+pub fn import_particle_types(
+    world: &mut World,
+    types: &[ImportedParticleType],
+    selection: &[(usize, ImportResolution)],
+) -> Result<ParticleImport, String> {
+    enum Plan {
+        Spawn(ParticleType, String),
+        Overwrite(Entity),
+    }
+
+    let mut import = ParticleImport::default();
+    let mut ids: HashMap<ParticleTypeId, ParticleTypeId> = HashMap::default();
+    let mut taken = taken_particle_names(world);
+
+    let mut plans = Vec::new();
+    for (index, resolution) in selection {
+        let imported = types
+            .get(*index)
+            .ok_or_else(|| format!("No particle type at index {index}"))?;
+        let plan = match (existing_particle_type(world, &imported.name), resolution) {
+            (Some((_, id)), ImportResolution::Skip) => {
+                ids.insert(imported.id, id);
+                import.skipped.push(imported.name.clone());
+                continue;
+            }
+            (Some((entity, id)), ImportResolution::Overwrite) => {
+                ids.insert(imported.id, id);
+                import.overwritten.push(imported.name.clone());
+                Plan::Overwrite(entity)
+            }
+            (existing, resolution) => {
+                let name = match (existing, resolution) {
+                    (Some(_), ImportResolution::Rename(name)) => name.clone(),
+                    _ => imported.name.clone(),
+                };
+                if !taken.insert(name.to_lowercase()) {
+                    return Err(format!("A particle type named '{name}' already exists"));
+                }
+                let particle_type = ParticleType::new();
+                ids.insert(imported.id, particle_type.id());
+                import.imported.push(name.clone());
+                Plan::Spawn(particle_type, name)
+            }
+        };
+        plans.push((imported, plan));
+    }
+
+    // Types that weren't selected stand in for the existing type of the same name, if any.
+    for imported in types {
+        if !ids.contains_key(&imported.id)
+            && let Some((_, id)) = existing_particle_type(world, &imported.name)
+        {
+            ids.insert(imported.id, id);
+        }
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    // Every component type the file stores, so overwriting removes components the imported
+    // definition doesn't have.
+    let stored: Vec<&ReflectComponent> = {
+        let mut seen = HashSet::default();
+        types
+            .iter()
+            .flat_map(|imported| definition_components(imported, &type_registry))
+            .filter(|(component, _)| {
+                component
+                    .get_represented_type_info()
+                    .is_some_and(|info| seen.insert(info.type_id()))
+            })
+            .map(|(_, reflect_component)| reflect_component)
+            .collect()
+    };
+
+    for (imported, plan) in plans {
+        let (mut entity, name) = match plan {
+            Plan::Spawn(particle_type, name) => (world.spawn(particle_type), Some(name)),
+            Plan::Overwrite(entity) => {
+                let mut entity = world.entity_mut(entity);
+                for reflect_component in &stored {
+                    reflect_component.remove(&mut entity);
+                }
+                (entity, None)
+            }
+        };
+        for (component, reflect_component) in definition_components(imported, &type_registry) {
+            reflect_component.insert(&mut entity, component, &type_registry);
+        }
+        if let Some(name) = name {
+            entity.insert(ParticleName(name));
+        }
+        import.dropped_references +=
+            remap_particle_references(&mut entity, |id| ids.get(&id).copied());
+
+        let entity = entity.id();
+        world.write_message(SyncParticleTypeChildrenSignal::from_parent_handle(entity));
+    }
+
+    Ok(import)
+}
+
+fn find_component(
+    components: &[Box<dyn PartialReflect>],
+    type_id: TypeId,
+) -> Option<&dyn PartialReflect> {
+    components
+        .iter()
+        .find(|component| {
+            component
+                .get_represented_type_info()
+                .is_some_and(|info| info.type_id() == type_id)
+        })
+        .map(|component| component.as_ref())
+}
+
+/// The components of an imported type that make up its definition, which is every registered
+/// component except its [`ParticleType`].
+fn definition_components<'a>(
+    imported: &'a ImportedParticleType,
+    type_registry: &'a TypeRegistry,
+) -> Vec<(&'a dyn PartialReflect, &'a ReflectComponent)> {
+    imported
+        .components
+        .iter()
+        .filter_map(|component| {
+            let registration = component
+                .get_represented_type_info()
+                .and_then(|info| type_registry.get(info.type_id()))?;
+            let reflect_component = registration.data::<ReflectComponent>()?;
+            (registration.type_id() != TypeId::of::<ParticleType>())
+                .then_some((component.as_ref(), reflect_component))
+        })
+        .collect()
+}
+
+/// Particle set files in the active world and every other world, labelled by their path
+/// relative to the worlds directory.
+pub fn particle_set_files(active_world_path: &Path) -> Vec<(String, PathBuf)> {
+    let worlds_path = active_world_path.parent().unwrap_or(active_world_path);
+    let mut world_paths: Vec<PathBuf> = fs::read_dir(worlds_path)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    world_paths.sort();
+
+    world_paths
+        .iter()
+        .filter_map(|world_path| fs::read_dir(world_path).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".scn.ron"))
+        })
+        .map(|path| {
+            let label = path
+                .strip_prefix(worlds_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .into_owned();
+            (label, path)
+        })
+        .collect()
+}
+
+/// The lowercase names of every particle type, for checking names against while ignoring case.
+pub fn taken_particle_names(world: &mut World) -> HashSet<String> {
+    world
+        .query::<&ParticleName>()
+        .iter(world)
+        .map(|name| name.0.to_lowercase())
+        .collect()
+}
+
+/// `name`, or `name` followed by the first number that makes it unique among the lowercase names
+/// in `taken`.
+pub fn unique_particle_name(taken: &HashSet<String>, name: &str) -> String {
+    (1..)
+        .map(|n| match n {
+            1 => name.to_string(),
+            n => format!("{name} {n}"),
+        })
+        .find(|candidate| !taken.contains(&candidate.to_lowercase()))
+        .unwrap_or_default()
+}
+
+/// New names for the `selected` types whose names collide with the lowercase `existing` names.
+/// Each is unique among the existing names, the names of the other selected types, which are
+/// imported as they are, and the other new names.
+pub fn rename_conflicting_types(
+    existing: &HashSet<String>,
+    types: &[ImportedParticleType],
+    selected: &[usize],
+) -> HashMap<usize, String> {
+    let mut taken = existing.clone();
+    taken.extend(
+        selected
+            .iter()
+            .filter_map(|&index| types.get(index))
+            .map(|imported| imported.name.to_lowercase()),
+    );
+    selected
+        .iter()
+        .filter_map(|&index| Some((index, types.get(index)?)))
+        .filter(|(_, imported)| existing.contains(&imported.name.to_lowercase()))
+        .map(|(index, imported)| {
+            let name = unique_particle_name(&taken, &imported.name);
+            taken.insert(name.to_lowercase());
+            (index, name)
+        })
+        .collect()
+}
+
+/// What [`reload_particle_types`] changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParticleTypesReload {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    /// Types that are no longer in the file, which are kept until the user deletes them.
+    pub missing: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// Update the particle types to match `types`, which were read from a new version of the current
+/// particle set, matching types by name.
+///
+/// Unchanged types are left alone. Changed types are updated in place, keeping their entity, id
+/// and live particles. Types missing from `types` are only reported, since deleting them would
+/// also despawn their particles.
+pub fn reload_particle_types(
+    world: &mut World,
+    types: &[ImportedParticleType],
+) -> Result<ParticleTypesReload, String> {
+    let mut reload = ParticleTypesReload::default();
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    // The ids in the file differ from the current ids whenever types were added by an earlier
+    // reload or renumbered on load, so references are compared through the type names.
+    let ids: HashMap<ParticleTypeId, ParticleTypeId> = types
+        .iter()
+        .filter_map(|imported| {
+            existing_particle_type(world, &imported.name).map(|(_, id)| (imported.id, id))
+        })
+        .collect();
+    let selection: Vec<(usize, ImportResolution)> = {
+        let type_registry = type_registry.read();
+        let stored: HashSet<TypeId> = types
+            .iter()
+            .flat_map(|imported| definition_components(imported, &type_registry))
+            .filter_map(|(component, _)| component.get_represented_type_info())
+            .map(|info| info.type_id())
+            .collect();
+        types
+            .iter()
+            .enumerate()
+            .filter(
+                |(_, imported)| match existing_particle_type(world, &imported.name) {
+                    Some((entity, _)) => {
+                        let changed = !is_defined_by(
+                            world.entity(entity),
+                            imported,
+                            &stored,
+                            &ids,
+                            &type_registry,
+                        );
+                        if !changed {
+                            reload.unchanged += 1;
+                        }
+                        changed
+                    }
+                    None => true,
+                },
+            )
+            .map(|(index, _)| (index, ImportResolution::Overwrite))
+            .collect()
+    };
+
+    let import = import_particle_types(world, types, &selection)?;
+    reload.added = import.imported;
+    reload.updated = import.overwritten;
+
+    let names: HashSet<String> = types
+        .iter()
+        .map(|imported| imported.name.to_lowercase())
+        .collect();
+    reload.missing = world
+        .query_filtered::<&ParticleName, With<ParticleType>>()
+        .iter(world)
+        .filter(|name| !names.contains(&name.0.to_lowercase()))
+        .map(|name| name.0.clone())
+        .collect();
+    reload.missing.sort();
+    Ok(reload)
+}
+
+/// Whether the components of `entity` among the `stored` component types are exactly the
+/// definition of `imported`, once its references are mapped from file ids to current ids
+/// through `ids`.
+fn is_defined_by(
+    entity: EntityRef,
+    imported: &ImportedParticleType,
+    stored: &HashSet<TypeId>,
+    ids: &HashMap<ParticleTypeId, ParticleTypeId>,
+    type_registry: &TypeRegistry,
+) -> bool {
+    let components = definition_components(imported, type_registry);
+    let present = stored
+        .iter()
+        .filter_map(|type_id| type_registry.get(*type_id))
+        .filter_map(|registration| registration.data::<ReflectComponent>())
+        .filter(|reflect_component| reflect_component.contains(entity))
+        .count();
+    present == components.len()
+        && components.iter().all(|(component, reflect_component)| {
+            let remapped =
+                remap_reflected_references(*component, |id| ids.get(&id).copied().unwrap_or(id));
+            let component = remapped.as_deref().unwrap_or(*component);
+            reflect_component
+                .reflect(entity)
+                .and_then(|existing| existing.as_partial_reflect().reflect_partial_eq(component))
+                .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::message::Messages;
+    use bevy_falling_sand::{
+        core::ParticleTypeRegistry,
+        prelude::{ChanceMutation, Density},
+    };
+
+    use super::*;
+
+    fn id(raw: u32) -> ParticleTypeId {
+        ParticleTypeId::from_raw(raw as _)
+    }
+
+    fn new_world() -> World {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<ParticleType>();
+            type_registry.register::<ParticleName>();
+            type_registry.register::<Density>();
+            type_registry.register::<ChanceMutation>();
+        }
+        world.insert_resource(type_registry);
+        world.init_resource::<ParticleTypeRegistry>();
+        world.init_resource::<Messages<SyncParticleTypeChildrenSignal>>();
+        world
+    }
+
+    fn spawn_particle_type(world: &mut World, raw: u32, name: &str, bundle: impl Bundle) -> Entity {
+        world
+            .spawn((
+                ParticleType::from_id(id(raw)),
+                ParticleName(name.to_string()),
+                bundle,
+            ))
+            .id()
+    }
+
+    /// A particle type as it is read from a file, where it has the id `raw`.
+    fn imported(
+        raw: u32,
+        name: &str,
+        components: Vec<Box<dyn PartialReflect>>,
+    ) -> ImportedParticleType {
+        let mut all: Vec<Box<dyn PartialReflect>> = vec![
+            Box::new(ParticleType::from_id(id(raw))),
+            Box::new(ParticleName(name.to_string())),
+        ];
+        all.extend(components);
+        ImportedParticleType {
+            id: id(raw),
+            name: name.to_string(),
+            category: String::from("Other"),
+            components: all,
+        }
+    }
+
+    fn particle_type_id(world: &mut World, name: &str) -> ParticleTypeId {
+        existing_particle_type(world, name).unwrap().1
+    }
+
+    #[test]
+    fn reload_updates_changed_types_in_place() {
+        let mut world = new_world();
+        let sand = spawn_particle_type(&mut world, 0, "Sand", Density::new(10));
+        spawn_particle_type(&mut world, 1, "Water", Density::new(5));
+        let types = [
+            imported(7, "Sand", vec![Box::new(Density::new(20))]),
+            imported(8, "Water", vec![Box::new(Density::new(5))]),
+        ];
+
+        let reload = reload_particle_types(&mut world, &types).unwrap();
+
+        assert_eq!(
+            reload,
+            ParticleTypesReload {
+                updated: vec![String::from("Sand")],
+                unchanged: 1,
+                ..default()
+            }
+        );
+        assert_eq!(
+            existing_particle_type(&mut world, "Sand"),
+            Some((sand, id(0)))
+        );
+        assert_eq!(
+            world
+                .get::<Density>(sand)
+                .unwrap()
+                .reflect_partial_eq(&Density::new(20)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn reload_compares_references_by_name() {
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 0, "Water", ChanceMutation::new(id(1), 0.5));
+        spawn_particle_type(&mut world, 1, "Steam", ());
+        // The file numbers the same types differently.
+        let types = [
+            imported(5, "Water", vec![Box::new(ChanceMutation::new(id(6), 0.5))]),
+            imported(6, "Steam", Vec::new()),
+        ];
+
+        let reload = reload_particle_types(&mut world, &types).unwrap();
+
+        assert_eq!(
+            reload,
+            ParticleTypesReload {
+                unchanged: 2,
+                ..default()
+            }
+        );
+    }
+
+    #[test]
+    fn reload_adds_new_types_and_keeps_missing_ones() {
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 0, "Sand", ());
+        let types = [imported(0, "Water", Vec::new())];
+
+        let reload = reload_particle_types(&mut world, &types).unwrap();
+
+        assert_eq!(
+            reload,
+            ParticleTypesReload {
+                added: vec![String::from("Water")],
+                missing: vec![String::from("Sand")],
+                ..default()
+            }
+        );
+        assert_eq!(particle_type_id(&mut world, "Sand"), id(0));
+        assert!(existing_particle_type(&mut world, "Water").is_some());
+    }
+
+    #[test]
+    fn import_gives_new_ids_and_rewrites_references_between_imported_types() {
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 0, "Sand", ());
+        let types = [
+            imported(0, "Water", vec![Box::new(ChanceMutation::new(id(1), 0.5))]),
+            imported(1, "Steam", Vec::new()),
+        ];
+
+        let import = import_particle_types(
+            &mut world,
+            &types,
+            &[(0, ImportResolution::Skip), (1, ImportResolution::Skip)],
+        )
+        .unwrap();
+
+        assert_eq!(import.imported, ["Water", "Steam"]);
+        let (water, water_id) = existing_particle_type(&mut world, "Water").unwrap();
+        let steam_id = particle_type_id(&mut world, "Steam");
+        assert_ne!(water_id, steam_id);
+        assert_eq!(particle_type_id(&mut world, "Sand"), id(0));
+        assert_eq!(world.get::<ChanceMutation>(water).unwrap().target, steam_id);
+    }
+
+    #[test]
+    fn import_points_references_at_existing_types_or_drops_them() {
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 5, "Steam", ());
+        let types = [
+            imported(0, "Water", vec![Box::new(ChanceMutation::new(id(1), 0.5))]),
+            imported(1, "Steam", Vec::new()),
+            imported(2, "Ice", vec![Box::new(ChanceMutation::new(id(3), 0.5))]),
+        ];
+
+        let import = import_particle_types(
+            &mut world,
+            &types,
+            &[(0, ImportResolution::Skip), (2, ImportResolution::Skip)],
+        )
+        .unwrap();
+
+        // Water's target wasn't imported, but an existing type has its name. Ice's target is
+        // neither imported nor present.
+        assert_eq!(import.dropped_references, 1);
+        let (water, _) = existing_particle_type(&mut world, "Water").unwrap();
+        let (ice, _) = existing_particle_type(&mut world, "Ice").unwrap();
+        assert_eq!(world.get::<ChanceMutation>(water).unwrap().target, id(5));
+        assert!(world.get::<ChanceMutation>(ice).is_none());
+    }
+
+    #[test]
+    fn import_resolves_name_collisions() {
+        let mut world = new_world();
+        let water = spawn_particle_type(&mut world, 0, "Water", Density::new(5));
+        spawn_particle_type(&mut world, 1, "Sand", ());
+        spawn_particle_type(&mut world, 2, "Steam", ());
+        let types = [
+            imported(0, "water", vec![Box::new(Density::new(9))]),
+            imported(1, "Sand", Vec::new()),
+            imported(2, "Steam", Vec::new()),
+        ];
+
+        let import = import_particle_types(
+            &mut world,
+            &types,
+            &[
+                (0, ImportResolution::Overwrite),
+                (1, ImportResolution::Skip),
+                (2, ImportResolution::Rename(String::from("Steam 2"))),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(import.overwritten, ["water"]);
+        assert_eq!(import.skipped, ["Sand"]);
+        assert_eq!(import.imported, ["Steam 2"]);
+        assert_eq!(
+            existing_particle_type(&mut world, "Water"),
+            Some((water, id(0)))
+        );
+        assert_eq!(
+            world
+                .get::<Density>(water)
+                .unwrap()
+                .reflect_partial_eq(&Density::new(9)),
+            Some(true)
+        );
+        assert_eq!(particle_type_id(&mut world, "Steam"), id(2));
+    }
+
+    #[test]
+    fn import_refuses_names_that_are_taken() {
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 0, "Water", ());
+        spawn_particle_type(&mut world, 1, "Water 2", ());
+        let types = [imported(0, "Water", Vec::new())];
+
+        let result = import_particle_types(
+            &mut world,
+            &types,
+            &[(0, ImportResolution::Rename(String::from("water 2")))],
+        );
+
+        assert!(result.is_err());
+        assert_eq!(taken_particle_names(&mut world).len(), 2);
+    }
+
+    #[test]
+    fn renamed_types_avoid_the_names_of_other_imported_types() {
+        let existing: HashSet<String> = [String::from("water")].into_iter().collect();
+        let types = [
+            imported(0, "Water", Vec::new()),
+            imported(1, "Water 2", Vec::new()),
+            imported(2, "Sand", Vec::new()),
+        ];
+
+        let renames = rename_conflicting_types(&existing, &types, &[0, 1, 2]);
+
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames[&0], "Water 3");
+
+        let mut world = new_world();
+        spawn_particle_type(&mut world, 0, "Water", ());
+        let selection: Vec<(usize, ImportResolution)> = (0..types.len())
+            .map(|index| {
+                let name = renames
+                    .get(&index)
+                    .cloned()
+                    .unwrap_or_else(|| types[index].name.clone());
+                (index, ImportResolution::Rename(name))
+            })
+            .collect();
+        let import = import_particle_types(&mut world, &types, &selection).unwrap();
+        assert_eq!(import.imported, ["Water 3", "Water 2", "Sand"]);
+    }
+}
@@ -1,17 +1,36 @@
 mod algs;
+mod catalog;
 mod default;
+mod graph;
+mod hot_reload;
+mod import;
+mod references;
 mod save;
 mod setup;
+mod template;
+mod validation;
 
 use bevy::prelude::*;
-use bevy_falling_sand::core::particle::{
-    AttachedToParticleType, Particle, ParticleMap, ParticleSyncExt, ParticleType, ParticleTypeId,
+use bevy_falling_sand::{
+    core::particle::{
+        AttachedToParticleType, Particle, ParticleMap, ParticleSyncExt, ParticleType,
+        ParticleTypeId,
+    },
+    prelude::{ColorProfile, Movement},
 };
 use serde::{Deserialize, Serialize};
 
 pub use algs::*;
+pub use catalog::*;
 pub use default::DefaultParticleIds;
+pub use graph::*;
+pub use hot_reload::ParticleTypesHotReload;
+pub use import::*;
+pub use references::*;
+pub use save::{PARTICLE_MANIFEST_SCHEMA_VERSION, PARTICLE_MANIFEST_V2_SCHEMA_VERSION};
 pub use setup::*;
+pub use template::*;
+pub use validation::*;
 
 use crate::Cursor;
 
@@ -26,12 +45,19 @@ pub struct ParticlesPlugin;
 
 impl Plugin for ParticlesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((SetupPlugin, PatternsPlugin, save::SavePlugin))
-            .register_type::<ParticleCategory>()
-            .register_type::<ParticleName>()
-            .register_particle_sync_component::<ParticleCategory>()
-            .init_resource::<HoveredParticle>()
-            .add_systems(Update, update_hovered_particle);
+        app.add_plugins((
+            SetupPlugin,
+            PatternsPlugin,
+            save::SavePlugin,
+            hot_reload::HotReloadPlugin,
+            template::TemplatePlugin,
+        ))
+        .register_type::<ParticleCategory>()
+        .register_type::<ParticleName>()
+        .register_particle_sync_component::<ParticleCategory>()
+        .init_resource::<HoveredParticle>()
+        .init_resource::<ParticleDiagnostics>()
+        .add_systems(Update, (update_hovered_particle, validate_before_persist));
     }
 }
 
@@ -43,6 +69,79 @@ impl Plugin for ParticlesPlugin {
 #[type_path = "bfs_editor::particle"]
 pub struct ParticleName(pub String);
 
+/// Triggered by [`rename_particle_type`], so anything that stores particle types by name can
+/// follow the rename.
+#[derive(Event, Clone, Debug)]
+pub struct ParticleTypeRenamedEvent {
+    pub entity: Entity,
+    pub old: String,
+    pub new: String,
+}
+
+/// Rename the particle type `entity` to `name`.
+pub fn rename_particle_type(world: &mut World, entity: Entity, name: String) {
+    let old = world.get::<ParticleName>(entity).map(|name| name.0.clone());
+    world.entity_mut(entity).insert(ParticleName(name.clone()));
+    if let Some(old) = old
+        && old != name
+    {
+        world.trigger(ParticleTypeRenamedEvent {
+            entity,
+            old,
+            new: name,
+        });
+    }
+}
+
+/// Spawn a particle type named `name` with default components.
+pub fn spawn_particle_type(commands: &mut Commands, name: String) -> Entity {
+    commands
+        .spawn((
+            ParticleType::new(),
+            ParticleName(name),
+            ParticleCategory(String::from("Other")),
+            ColorProfile::default(),
+        ))
+        .id()
+}
+
+/// Spawn a particle type named `name` with a copy of every component of `source` except its
+/// [`ParticleType`], which is given a new id.
+pub fn spawn_particle_type_from(
+    commands: &mut Commands,
+    source: Entity,
+    name: String,
+) -> (Entity, ParticleTypeId) {
+    let particle_type = ParticleType::new();
+    let id = particle_type.id();
+    let entity = commands
+        .entity(source)
+        .clone_and_spawn_with_opt_out(|builder| {
+            builder.deny::<ParticleType>();
+        })
+        .insert((particle_type, ParticleName(name)))
+        .id();
+    (entity, id)
+}
+
+/// Broad description of how a particle type moves, derived from its movement neighbors.
+pub fn movement_class(movement: Option<&Movement>) -> &'static str {
+    let Some(movement) = movement else {
+        return "Static";
+    };
+    let mut offsets = movement
+        .neighbor_groups
+        .iter()
+        .flat_map(|group| group.neighbor_group.iter());
+    if offsets.clone().any(|offset| offset.y > 0) {
+        "Gas"
+    } else if offsets.any(|offset| offset.y == 0) {
+        "Liquid"
+    } else {
+        "Solid"
+    }
+}
+
 #[derive(Default, Resource, Clone, Debug)]
 pub struct HoveredParticle {
     pub particle: Option<ParticleTypeId>,
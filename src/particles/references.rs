@@ -0,0 +1,464 @@
+//! Finds and rewrites references between particle types, so types can be deleted without
+//! leaving dangling ids behind.
+
+use std::{any::TypeId, fmt};
+
+use bevy::{prelude::*, reflect::PartialReflect};
+use bevy_falling_sand::{
+    core::{
+        SpawnParticleSignal,
+        particle::{AttachedToParticleType, Particle, ParticleType, ParticleTypeId},
+    },
+    prelude::{
+        ChanceMutation, ContactOutcome, ContactReaction, DespawnParticleTypeChildrenSignal,
+        Flammable, GridPosition, TimedMutation,
+    },
+};
+
+use super::{ParticleName, ParticleTemplate};
+
+/// Where a particle type refers to another particle type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleReferenceKind {
+    ChanceMutation,
+    TimedMutation,
+    /// The target of a contact rule, by rule index.
+    ContactTarget(usize),
+    /// What the source becomes in a contact rule, by rule index.
+    ContactSourceOutcome(usize),
+    /// What the target becomes in a contact rule, by rule index.
+    ContactTargetOutcome(usize),
+    /// What a burning particle produces.
+    BurnProduct,
+    /// The template a particle type inherits from.
+    Template,
+}
+
+impl fmt::Display for ParticleReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChanceMutation => write!(f, "chance mutation target"),
+            Self::TimedMutation => write!(f, "timed mutation target"),
+            Self::ContactTarget(rule) => write!(f, "contact rule {} target", rule + 1),
+            Self::ContactSourceOutcome(rule) => {
+                write!(f, "contact rule {} source outcome", rule + 1)
+            }
+            Self::ContactTargetOutcome(rule) => {
+                write!(f, "contact rule {} target outcome", rule + 1)
+            }
+            Self::BurnProduct => write!(f, "burn product"),
+            Self::Template => write!(f, "template"),
+        }
+    }
+}
+
+/// A reference to a particle type from another particle type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParticleTypeReference {
+    /// The particle type holding the reference.
+    pub entity: Entity,
+    pub name: String,
+    pub kind: ParticleReferenceKind,
+}
+
+/// The components of a particle type that can refer to other particle types.
+pub struct ParticleReferences<'a> {
+    pub chance_mutation: Option<&'a ChanceMutation>,
+    pub timed_mutation: Option<&'a TimedMutation>,
+    pub contact_reaction: Option<&'a ContactReaction>,
+    pub burns: Option<&'a Flammable>,
+    pub template: Option<&'a ParticleTemplate>,
+}
+
+impl ParticleReferences<'_> {
+    /// Every place these components refer to `target`.
+    pub fn to(&self, target: ParticleTypeId) -> Vec<ParticleReferenceKind> {
+        let mut kinds = Vec::new();
+        if self.chance_mutation.is_some_and(|m| m.target == target) {
+            kinds.push(ParticleReferenceKind::ChanceMutation);
+        }
+        if self.timed_mutation.is_some_and(|m| m.target == target) {
+            kinds.push(ParticleReferenceKind::TimedMutation);
+        }
+        if let Some(contact_reaction) = self.contact_reaction {
+            for (index, rule) in contact_reaction.rules.iter().enumerate() {
+                if rule.target == target {
+                    kinds.push(ParticleReferenceKind::ContactTarget(index));
+                }
+                if rule.source_outcome == ContactOutcome::Becomes(target) {
+                    kinds.push(ParticleReferenceKind::ContactSourceOutcome(index));
+                }
+                if rule.target_outcome == ContactOutcome::Becomes(target) {
+                    kinds.push(ParticleReferenceKind::ContactTargetOutcome(index));
+                }
+            }
+        }
+        if self
+            .burns
+            .and_then(|burns| burns.reaction.as_ref())
+            .is_some_and(|reaction| reaction.produces == target)
+        {
+            kinds.push(ParticleReferenceKind::BurnProduct);
+        }
+        if self
+            .template
+            .is_some_and(|template| template.parent == target)
+        {
+            kinds.push(ParticleReferenceKind::Template);
+        }
+        kinds
+    }
+}
+
+/// Find every reference to `target` from other particle types.
+pub fn find_particle_type_references(
+    world: &mut World,
+    target: ParticleTypeId,
+) -> Vec<ParticleTypeReference> {
+    let mut references = Vec::new();
+    let mut query = world.query::<(
+        Entity,
+        &ParticleType,
+        Option<&ParticleName>,
+        Option<&ChanceMutation>,
+        Option<&TimedMutation>,
+        Option<&ContactReaction>,
+        Option<&Flammable>,
+        Option<&ParticleTemplate>,
+    )>();
+    for (
+        entity,
+        particle_type,
+        name,
+        chance_mutation,
+        timed_mutation,
+        contact_reaction,
+        burns,
+        template,
+    ) in query.iter(world)
+    {
+        if particle_type.id() == target {
+            continue;
+        }
+        let components = ParticleReferences {
+            chance_mutation,
+            timed_mutation,
+            contact_reaction,
+            burns,
+            template,
+        };
+        let name = name
+            .map(|name| name.0.clone())
+            .unwrap_or_else(|| format!("Particle {}", particle_type.id().get()));
+        references.extend(
+            components
+                .to(target)
+                .into_iter()
+                .map(|kind| ParticleTypeReference {
+                    entity,
+                    name: name.clone(),
+                    kind,
+                }),
+        );
+    }
+    references.sort_by(|a, b| a.name.cmp(&b.name));
+    references
+}
+
+/// What [`delete_particle_type`] changed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParticleTypeDeletion {
+    /// References rewritten to the replacement, or removed if there was none.
+    pub references: usize,
+    /// Live particles converted to the replacement, or despawned if there was none.
+    pub particles: usize,
+}
+
+/// Delete the particle type `entity`.
+///
+/// With a `replacement`, references to the type are rewritten to the replacement and its live
+/// particles are converted to it. Without one, the mutations, contact rules and burn reactions
+/// that refer to it are removed and its live particles are despawned.
+pub fn delete_particle_type(
+    world: &mut World,
+    entity: Entity,
+    replacement: Option<ParticleTypeId>,
+) -> ParticleTypeDeletion {
+    let Some(id) = world.get::<ParticleType>(entity).map(ParticleType::id) else {
+        return ParticleTypeDeletion::default();
+    };
+    let references = find_particle_type_references(world, id);
+
+    for reference in &references {
+        rewrite_reference(world, reference, id, replacement);
+    }
+    // Contact rules are removed together once every other reference has been visited, so rule
+    // indices stay valid while rewriting.
+    if replacement.is_none() {
+        for reference in &references {
+            if let ParticleReferenceKind::ContactTarget(_)
+            | ParticleReferenceKind::ContactSourceOutcome(_)
+            | ParticleReferenceKind::ContactTargetOutcome(_) = reference.kind
+                && let Some(mut contact_reaction) =
+                    world.get_mut::<ContactReaction>(reference.entity)
+            {
+                contact_reaction.rules.retain(|rule| {
+                    rule.target != id
+                        && rule.source_outcome != ContactOutcome::Becomes(id)
+                        && rule.target_outcome != ContactOutcome::Becomes(id)
+                });
+            }
+        }
+    }
+
+    let positions: Vec<IVec2> = world
+        .query_filtered::<(&AttachedToParticleType, &GridPosition), With<Particle>>()
+        .iter(world)
+        .filter(|(attached, _)| attached.0 == entity)
+        .map(|(_, position)| position.0)
+        .collect();
+    world.trigger(DespawnParticleTypeChildrenSignal::from_particle_type(id));
+    world.entity_mut(entity).despawn();
+    if let Some(replacement) = replacement {
+        for position in &positions {
+            world.write_message(SpawnParticleSignal::new(replacement, *position));
+        }
+    }
+
+    ParticleTypeDeletion {
+        references: references.len(),
+        particles: positions.len(),
+    }
+}
+
+fn rewrite_reference(
+    world: &mut World,
+    reference: &ParticleTypeReference,
+    id: ParticleTypeId,
+    replacement: Option<ParticleTypeId>,
+) {
+    let mut entity = world.entity_mut(reference.entity);
+    match (reference.kind, replacement) {
+        (ParticleReferenceKind::ChanceMutation, Some(replacement)) => {
+            if let Some(mut mutation) = entity.get_mut::<ChanceMutation>() {
+                mutation.target = replacement;
+            }
+        }
+        (ParticleReferenceKind::ChanceMutation, None) => {
+            entity.remove::<ChanceMutation>();
+        }
+        (ParticleReferenceKind::TimedMutation, Some(replacement)) => {
+            if let Some(mut mutation) = entity.get_mut::<TimedMutation>() {
+                mutation.target = replacement;
+            }
+        }
+        (ParticleReferenceKind::TimedMutation, None) => {
+            entity.remove::<TimedMutation>();
+        }
+        (
+            ParticleReferenceKind::ContactTarget(rule)
+            | ParticleReferenceKind::ContactSourceOutcome(rule)
+            | ParticleReferenceKind::ContactTargetOutcome(rule),
+            Some(replacement),
+        ) => {
+            if let Some(mut contact_reaction) = entity.get_mut::<ContactReaction>()
+                && let Some(rule) = contact_reaction.rules.get_mut(rule)
+            {
+                match reference.kind {
+                    ParticleReferenceKind::ContactTarget(_) => rule.target = replacement,
+                    ParticleReferenceKind::ContactSourceOutcome(_) => {
+                        rule.source_outcome = ContactOutcome::Becomes(replacement);
+                    }
+                    _ => rule.target_outcome = ContactOutcome::Becomes(replacement),
+                }
+            }
+        }
+        // Rules are removed together by `delete_particle_type`.
+        (
+            ParticleReferenceKind::ContactTarget(_)
+            | ParticleReferenceKind::ContactSourceOutcome(_)
+            | ParticleReferenceKind::ContactTargetOutcome(_),
+            None,
+        ) => {}
+        (ParticleReferenceKind::BurnProduct, replacement) => {
+            if let Some(mut burns) = entity.get_mut::<Flammable>()
+                && burns
+                    .reaction
+                    .as_ref()
+                    .is_some_and(|reaction| reaction.produces == id)
+            {
+                match replacement {
+                    Some(replacement) => {
+                        if let Some(reaction) = &mut burns.reaction {
+                            reaction.produces = replacement;
+                        }
+                    }
+                    None => burns.reaction = None,
+                }
+            }
+        }
+        // Without a replacement the type stops inheriting, keeping the components it inherited.
+        (ParticleReferenceKind::Template, Some(replacement)) => {
+            if let Some(mut template) = entity.get_mut::<ParticleTemplate>() {
+                template.parent = replacement;
+            }
+        }
+        (ParticleReferenceKind::Template, None) => {
+            entity.remove::<ParticleTemplate>();
+        }
+    }
+}
+
+/// Rewrite every reference on `entity` through `map`, removing the mutations, contact rules, burn
+/// reactions and template it has no replacement for. Returns how many references were removed.
+pub fn remap_particle_references(
+    entity: &mut EntityWorldMut,
+    map: impl Fn(ParticleTypeId) -> Option<ParticleTypeId>,
+) -> usize {
+    let mut removed = 0;
+
+    match entity
+        .get::<ChanceMutation>()
+        .map(|mutation| map(mutation.target))
+    {
+        Some(Some(target)) => {
+            if let Some(mut mutation) = entity.get_mut::<ChanceMutation>() {
+                mutation.target = target;
+            }
+        }
+        Some(None) => {
+            entity.remove::<ChanceMutation>();
+            removed += 1;
+        }
+        None => {}
+    }
+    match entity
+        .get::<TimedMutation>()
+        .map(|mutation| map(mutation.target))
+    {
+        Some(Some(target)) => {
+            if let Some(mut mutation) = entity.get_mut::<TimedMutation>() {
+                mutation.target = target;
+            }
+        }
+        Some(None) => {
+            entity.remove::<TimedMutation>();
+            removed += 1;
+        }
+        None => {}
+    }
+
+    if let Some(mut contact_reaction) = entity.get_mut::<ContactReaction>() {
+        let before = contact_reaction.rules.len();
+        contact_reaction.rules.retain_mut(|rule| {
+            let Some(target) = map(rule.target) else {
+                return false;
+            };
+            rule.target = target;
+            for outcome in [&mut rule.source_outcome, &mut rule.target_outcome] {
+                if let ContactOutcome::Becomes(id) = outcome {
+                    let Some(becomes) = map(*id) else {
+                        return false;
+                    };
+                    *id = becomes;
+                }
+            }
+            true
+        });
+        removed += before - contact_reaction.rules.len();
+    }
+
+    let produces = entity
+        .get::<Flammable>()
+        .and_then(|burns| burns.reaction.as_ref())
+        .map(|reaction| map(reaction.produces));
+    if let Some(produces) = produces
+        && let Some(mut burns) = entity.get_mut::<Flammable>()
+    {
+        match produces {
+            Some(produces) => {
+                if let Some(reaction) = &mut burns.reaction {
+                    reaction.produces = produces;
+                }
+            }
+            None => {
+                burns.reaction = None;
+                removed += 1;
+            }
+        }
+    }
+
+    match entity
+        .get::<ParticleTemplate>()
+        .map(|template| map(template.parent))
+    {
+        Some(Some(parent)) => {
+            if let Some(mut template) = entity.get_mut::<ParticleTemplate>() {
+                template.parent = parent;
+            }
+        }
+        Some(None) => {
+            entity.remove::<ParticleTemplate>();
+            removed += 1;
+        }
+        None => {}
+    }
+
+    removed
+}
+
+/// A copy of the reflected component `component` with every reference rewritten through `map`,
+/// or `None` if it isn't a component that refers to other particle types.
+pub fn remap_reflected_references(
+    component: &dyn PartialReflect,
+    map: impl Fn(ParticleTypeId) -> ParticleTypeId,
+) -> Option<Box<dyn PartialReflect>> {
+    fn remapped<C: FromReflect>(
+        component: &dyn PartialReflect,
+        remap: impl FnOnce(&mut C),
+    ) -> Option<Box<dyn PartialReflect>> {
+        // Dynamic values of other types can have the same fields, so only convert exact matches.
+        if !component
+            .get_represented_type_info()
+            .is_some_and(|info| info.type_id() == TypeId::of::<C>())
+        {
+            return None;
+        }
+        let mut value = C::from_reflect(component)?;
+        remap(&mut value);
+        Some(Box::new(value))
+    }
+
+    remapped(component, |mutation: &mut ChanceMutation| {
+        mutation.target = map(mutation.target);
+    })
+    .or_else(|| {
+        remapped(component, |mutation: &mut TimedMutation| {
+            mutation.target = map(mutation.target);
+        })
+    })
+    .or_else(|| {
+        remapped(component, |contact_reaction: &mut ContactReaction| {
+            for rule in &mut contact_reaction.rules {
+                rule.target = map(rule.target);
+                for outcome in [&mut rule.source_outcome, &mut rule.target_outcome] {
+                    if let ContactOutcome::Becomes(id) = outcome {
+                        *id = map(*id);
+                    }
+                }
+            }
+        })
+    })
+    .or_else(|| {
+        remapped(component, |burns: &mut Flammable| {
+            if let Some(reaction) = &mut burns.reaction {
+                reaction.produces = map(reaction.produces);
+            }
+        })
+    })
+    .or_else(|| {
+        remapped(component, |template: &mut ParticleTemplate| {
+            template.parent = map(template.parent);
+        })
+    })
+}
@@ -1,11 +1,26 @@
 use bevy::prelude::*;
 
 mod manifest;
+mod remap;
+
+pub use manifest::{PARTICLE_MANIFEST_SCHEMA_VERSION, PARTICLE_MANIFEST_V2_SCHEMA_VERSION};
 
 pub(super) struct SavePlugin;
 
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, manifest::write_particle_manifest);
+        app.init_resource::<remap::PendingParticleIdRemap>()
+            .add_systems(
+                Update,
+                (
+                    manifest::write_particle_manifest,
+                    manifest::write_chunk_particle_manifest,
+                    (
+                        remap::track_particle_type_loads,
+                        remap::remap_loaded_particle_ids,
+                    )
+                        .chain(),
+                ),
+            );
     }
 }
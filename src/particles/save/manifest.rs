@@ -1,55 +1,221 @@
-//! Writes the user-facing particle manifest after particle definitions are persisted.
+//! Writes the user-facing particle manifest after particle definitions are persisted, and a copy
+//! of it beside saved chunks recording the particle ids they were written with.
+//!
+//! Schema v1 has each particle's id, name and category. Schema v2 adds the properties tools
+//! outside the editor need: movement, density, speed, colors, effects, flammability and
+//! mutation targets.
 
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
-use bevy::prelude::*;
-use bevy_falling_sand::prelude::{ParticleType, ParticleTypesPersistedSignal};
-use serde::Serialize;
+use bevy::{ecs::query::QueryData, platform::collections::HashMap, prelude::*};
+use bevy_falling_sand::prelude::{
+    ChanceMutation, ColorProfile, ColorSource, Density, Flammable, Movement,
+    ParticlePersistenceConfig, ParticleType, ParticleTypeId, ParticleTypesPersistedSignal,
+    PersistChunksSignal, Speed, TimedMutation,
+};
+use bevy_persistent::Persistent;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use super::super::{ParticleCategory, ParticleName};
+use super::super::{ParticleCategory, ParticleName, movement_class};
+use crate::{
+    chunk_effects::{BurnEffect, GasEffect, GlowEffect, LiquidEffect},
+    config::WorldConfig,
+};
 
-const PARTICLE_MANIFEST_SCHEMA_VERSION: u32 = 1;
+pub const PARTICLE_MANIFEST_SCHEMA_VERSION: u32 = 1;
 const PARTICLE_MANIFEST_HASH_DOMAIN: &[u8] = b"bevy-falling-sand-particle-manifest-v1\0";
+pub const PARTICLE_MANIFEST_V2_SCHEMA_VERSION: u32 = 2;
+const PARTICLE_MANIFEST_V2_HASH_DOMAIN: &[u8] = b"bevy-falling-sand-particle-manifest-v2\0";
+/// Manifest in the chunk data directory, describing the particle set chunks were saved with.
+const CHUNK_PARTICLE_MANIFEST_FILE: &str = "particle-manifest.json";
+
+#[derive(QueryData)]
+pub(super) struct ManifestQueryData {
+    particle_type: &'static ParticleType,
+    name: Option<&'static ParticleName>,
+    category: Option<&'static ParticleCategory>,
+    movement: Option<&'static Movement>,
+    density: Option<&'static Density>,
+    speed: Option<&'static Speed>,
+    color_profile: Option<&'static ColorProfile>,
+    burns: Option<&'static Flammable>,
+    chance_mutation: Option<&'static ChanceMutation>,
+    timed_mutation: Option<&'static TimedMutation>,
+    liquid_effect: Has<LiquidEffect>,
+    gas_effect: Has<GasEffect>,
+    glow_effect: Has<GlowEffect>,
+    burn_effect: Has<BurnEffect>,
+}
+
+type ManifestQuery<'w, 's> = Query<'w, 's, ManifestQueryData>;
+
+/// A schema v1 manifest. Later schemas are supersets of it, so any manifest can be read as one.
+#[derive(Serialize, Deserialize)]
+pub(super) struct ParticleManifest {
+    schema_version: u32,
+    version: String,
+    pub particles: Vec<ParticleManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(super) struct ParticleManifestEntry {
+    pub id: usize,
+    pub name: String,
+    category: String,
+}
 
 #[derive(Serialize)]
-struct ParticleManifest {
+struct ParticleManifestV2 {
     schema_version: u32,
     version: String,
-    particles: Vec<ParticleManifestEntry>,
+    particles: Vec<ParticleManifestEntryV2>,
 }
 
 #[derive(Serialize, Debug)]
-struct ParticleManifestEntry {
+struct ParticleManifestEntryV2 {
     id: usize,
     name: String,
     category: String,
+    movement: &'static str,
+    density: Option<u32>,
+    speed: Option<SpeedSummary>,
+    color: Option<ColorSummary>,
+    effects: Vec<&'static str>,
+    flammability: Option<FlammabilitySummary>,
+    mutations: Vec<MutationSummary>,
+}
+
+#[derive(Serialize, Debug)]
+struct SpeedSummary {
+    max: u32,
+    threshold: u32,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "source", rename_all = "snake_case")]
+enum ColorSummary {
+    /// Colors as `#rrggbbaa` hex.
+    Palette {
+        colors: Vec<String>,
+    },
+    /// Gradient stops as `#rrggbbaa` hex, and the steps between consecutive stops.
+    Gradient {
+        stops: Vec<String>,
+        steps: Vec<u32>,
+    },
+    Texture {
+        path: String,
+    },
+}
+
+#[derive(Serialize, Debug)]
+struct FlammabilitySummary {
+    duration_ms: u64,
+    tick_rate_ms: u64,
+    chance_to_ignite: f64,
+    chance_despawn_per_tick: f64,
+    ignites_on_spawn: bool,
+    /// Name of the particle left behind when burning.
+    produces: Option<String>,
+    chance_to_produce: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum MutationSummary {
+    Chance {
+        target: String,
+        chance: f64,
+        tick_rate_ms: u64,
+    },
+    Timed {
+        target: String,
+        duration_ms: u64,
+    },
 }
 
 pub(super) fn write_particle_manifest(
     mut persisted: MessageReader<ParticleTypesPersistedSignal>,
-    particle_types: Query<(
-        &ParticleType,
-        Option<&ParticleName>,
-        Option<&ParticleCategory>,
-    )>,
+    world_config: Res<Persistent<WorldConfig>>,
+    particle_types: ManifestQuery,
 ) {
     let paths: Vec<PathBuf> = persisted.read().map(|message| message.0.clone()).collect();
     if paths.is_empty() {
         return;
     }
 
+    let manifest_paths = paths.iter().map(|path| manifest_path_for(path));
+    match world_config.get().particle_manifest_schema {
+        PARTICLE_MANIFEST_SCHEMA_VERSION => {
+            let manifest = build_particle_manifest(&particle_types);
+            manifest_paths.for_each(|path| save_particle_manifest(&path, &manifest));
+        }
+        PARTICLE_MANIFEST_V2_SCHEMA_VERSION => {
+            let manifest = build_particle_manifest_v2(&particle_types);
+            manifest_paths.for_each(|path| save_particle_manifest(&path, &manifest));
+        }
+        schema => error!(
+            "Not writing the particle manifest: particle_manifest_schema {schema} in world.toml \
+             is unknown, expected {PARTICLE_MANIFEST_SCHEMA_VERSION} or \
+             {PARTICLE_MANIFEST_V2_SCHEMA_VERSION}"
+        ),
+    }
+}
+
+pub(super) fn write_chunk_particle_manifest(
+    mut persisted: MessageReader<PersistChunksSignal>,
+    persistence_config: Res<ParticlePersistenceConfig>,
+    particle_types: ManifestQuery,
+) {
+    if persisted.read().count() == 0 {
+        return;
+    }
+    if let Err(error) = fs::create_dir_all(&persistence_config.save_path) {
+        error!(
+            "Failed to create chunk directory {:?}: {}",
+            persistence_config.save_path, error
+        );
+        return;
+    }
+    save_particle_manifest(
+        &chunk_manifest_path(&persistence_config.save_path),
+        &build_particle_manifest(&particle_types),
+    );
+}
+
+/// The manifest describing the particle ids saved chunks refer to: the one saved with the chunks,
+/// or the particle set's own manifest for worlds saved before chunks had one.
+pub(super) fn read_chunk_particle_manifest(
+    chunk_path: &Path,
+    particle_types_path: &Path,
+) -> Option<ParticleManifest> {
+    [
+        chunk_manifest_path(chunk_path),
+        manifest_path_for(particle_types_path),
+    ]
+    .iter()
+    .find_map(|path| {
+        let file = File::open(path).ok()?;
+        serde_json::from_reader(file)
+            .inspect_err(|error| {
+                warn!(
+                    "Ignoring unreadable particle manifest {:?}: {}",
+                    path, error
+                )
+            })
+            .ok()
+    })
+}
+
+fn build_particle_manifest(particle_types: &ManifestQuery) -> ParticleManifest {
     let mut particles: Vec<ParticleManifestEntry> = particle_types
         .iter()
-        .map(|(particle_type, name, category)| ParticleManifestEntry {
-            id: particle_type.id().get(),
-            name: name
-                .map(|name| name.0.clone())
-                .unwrap_or_else(|| format!("Particle {}", particle_type.id().get())),
-            category: category
-                .map(|category| category.0.clone())
-                .unwrap_or_else(|| "Other".to_string()),
+        .map(|item| ParticleManifestEntry {
+            id: item.particle_type.id().get(),
+            name: particle_name(item.particle_type, item.name),
+            category: particle_category(item.category),
         })
         .collect();
     particles.sort_by(|left, right| {
@@ -60,24 +226,143 @@ pub(super) fn write_particle_manifest(
     });
 
     let version = manifest_version(&particles);
-    let manifest = ParticleManifest {
+    ParticleManifest {
         schema_version: PARTICLE_MANIFEST_SCHEMA_VERSION,
         version,
         particles,
+    }
+}
+
+fn build_particle_manifest_v2(particle_types: &ManifestQuery) -> ParticleManifestV2 {
+    let names: HashMap<ParticleTypeId, String> = particle_types
+        .iter()
+        .map(|item| {
+            (
+                item.particle_type.id(),
+                particle_name(item.particle_type, item.name),
+            )
+        })
+        .collect();
+    let name_of = |id: ParticleTypeId| {
+        names
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| format!("Particle {}", id.get()))
     };
 
-    for particle_types_path in paths {
-        let manifest_path = manifest_path_for(&particle_types_path);
-        match File::create(&manifest_path)
-            .map_err(serde_json::Error::io)
-            .and_then(|file| serde_json::to_writer_pretty(file, &manifest))
-        {
-            Ok(()) => info!("Particle manifest saved to: {:?}", manifest_path),
-            Err(error) => error!(
-                "Failed to save particle manifest to {:?}: {}",
-                manifest_path, error
-            ),
-        }
+    let mut particles: Vec<ParticleManifestEntryV2> = particle_types
+        .iter()
+        .map(|item| {
+            let effects = [
+                (item.liquid_effect, "liquid"),
+                (item.gas_effect, "gas"),
+                (item.glow_effect, "glow"),
+                (item.burn_effect, "burn"),
+            ]
+            .into_iter()
+            .filter_map(|(enabled, effect)| enabled.then_some(effect))
+            .collect();
+
+            let mut mutations = Vec::new();
+            if let Some(mutation) = item.chance_mutation {
+                mutations.push(MutationSummary::Chance {
+                    target: name_of(mutation.target),
+                    chance: mutation.chance,
+                    tick_rate_ms: mutation.tick_timer.duration().as_millis() as u64,
+                });
+            }
+            if let Some(mutation) = item.timed_mutation {
+                mutations.push(MutationSummary::Timed {
+                    target: name_of(mutation.target),
+                    duration_ms: mutation.duration().as_millis() as u64,
+                });
+            }
+
+            ParticleManifestEntryV2 {
+                id: item.particle_type.id().get(),
+                name: particle_name(item.particle_type, item.name),
+                category: particle_category(item.category),
+                movement: movement_class(item.movement),
+                density: item.density.map(|density| density.get()),
+                speed: item.speed.map(|speed| SpeedSummary {
+                    max: speed.max_speed() as u32,
+                    threshold: speed.threshold() as u32,
+                }),
+                color: item
+                    .color_profile
+                    .map(|profile| color_summary(&profile.source)),
+                effects,
+                flammability: item.burns.map(|burns| FlammabilitySummary {
+                    duration_ms: burns.duration.as_millis() as u64,
+                    tick_rate_ms: burns.tick_rate.as_millis() as u64,
+                    chance_to_ignite: f64::from(burns.chance_to_ignite),
+                    chance_despawn_per_tick: f64::from(burns.chance_despawn_per_tick),
+                    ignites_on_spawn: burns.ignites_on_spawn,
+                    produces: burns
+                        .reaction
+                        .as_ref()
+                        .map(|reaction| name_of(reaction.produces)),
+                    chance_to_produce: burns
+                        .reaction
+                        .as_ref()
+                        .map(|reaction| reaction.chance_to_produce),
+                }),
+                mutations,
+            }
+        })
+        .collect();
+    particles.sort_by(|left, right| {
+        left.category
+            .cmp(&right.category)
+            .then_with(|| left.name.cmp(&right.name))
+            .then_with(|| left.id.cmp(&right.id))
+    });
+
+    let version = manifest_version_v2(&particles);
+    ParticleManifestV2 {
+        schema_version: PARTICLE_MANIFEST_V2_SCHEMA_VERSION,
+        version,
+        particles,
+    }
+}
+
+fn particle_name(particle_type: &ParticleType, name: Option<&ParticleName>) -> String {
+    name.map(|name| name.0.clone())
+        .unwrap_or_else(|| format!("Particle {}", particle_type.id().get()))
+}
+
+fn particle_category(category: Option<&ParticleCategory>) -> String {
+    category
+        .map(|category| category.0.clone())
+        .unwrap_or_else(|| "Other".to_string())
+}
+
+fn color_summary(source: &ColorSource) -> ColorSummary {
+    let hex = |color: &Color| Srgba::from(*color).to_hex();
+    match source {
+        ColorSource::Palette(palette) => ColorSummary::Palette {
+            colors: palette.colors.iter().map(hex).collect(),
+        },
+        ColorSource::Gradient(gradient) => ColorSummary::Gradient {
+            stops: gradient.colors.iter().map(hex).collect(),
+            steps: gradient.steps.clone(),
+        },
+        ColorSource::Texture(texture) => ColorSummary::Texture {
+            path: texture.path.clone(),
+        },
+    }
+}
+
+fn save_particle_manifest(manifest_path: &Path, manifest: &impl Serialize) {
+    match File::create(manifest_path)
+        .map_err(serde_json::Error::io)
+        .and_then(|file| serde_json::to_writer_pretty(file, manifest))
+    {
+        Ok(()) => info!("Particle manifest saved to: {:?}", manifest_path),
+        Err(error) => error!(
+            "Failed to save particle manifest to {:?}: {}",
+            manifest_path, error
+        ),
     }
 }
 
@@ -92,6 +377,17 @@ fn manifest_version(particles: &[ParticleManifestEntry]) -> String {
     format!("sha256:{:x}", hasher.finalize())
 }
 
+/// Hash every field of each particle, through its JSON form so nested properties are covered.
+fn manifest_version_v2(particles: &[ParticleManifestEntryV2]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(PARTICLE_MANIFEST_V2_HASH_DOMAIN);
+    for particle in particles {
+        let json = serde_json::to_string(particle).unwrap_or_default();
+        hash_string(&mut hasher, &json);
+    }
+    format!("sha256:{:x}", hasher.finalize())
+}
+
 fn hash_string(hasher: &mut Sha256, value: &str) {
     hasher.update((value.len() as u64).to_le_bytes());
     hasher.update(value.as_bytes());
@@ -108,3 +404,7 @@ fn manifest_path_for(particle_types_path: &Path) -> PathBuf {
         .unwrap_or(file_name);
     particle_types_path.with_file_name(format!("{stem}.particle-manifest.json"))
 }
+
+fn chunk_manifest_path(chunk_path: &Path) -> PathBuf {
+    chunk_path.join(CHUNK_PARTICLE_MANIFEST_FILE)
+}
@@ -0,0 +1,288 @@
+//! Keeps particle ids stable when a particle set is loaded into a world with saved chunks.
+//!
+//! Saved chunks refer to particles by [`ParticleTypeId`]. When a particle set is loaded, the loaded
+//! set is renumbered to match the chunk particle manifest: each type is given the id its name has
+//! there, matching names case-insensitively, so existing chunk data keeps mapping to the same
+//! materials even if the set was reordered or replaced. Chunk data itself is never translated;
+//! particles whose type is no longer in the set are left with an id no type has.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::{LoadParticleTypesSignal, ParticlePersistenceConfig},
+};
+
+use super::{
+    super::{ParticleName, remap_particle_references},
+    manifest::read_chunk_particle_manifest,
+};
+use crate::config::ParticleTypesFile;
+
+/// Whether the next particle types to be spawned come from loading a particle set. The first
+/// particle set of a session always is.
+#[derive(Resource)]
+pub(super) struct PendingParticleIdRemap(bool);
+
+impl Default for PendingParticleIdRemap {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// How [`remap_particle_type_ids`] changed particle ids.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParticleIdRemap {
+    /// Particle types given a different id, with their old and new ids.
+    pub remapped: Vec<(String, ParticleTypeId, ParticleTypeId)>,
+    /// Particle types in the manifest that are no longer in the set.
+    pub vanished: Vec<(String, ParticleTypeId)>,
+}
+
+pub(super) fn track_particle_type_loads(
+    mut msgr_load_particle_types: MessageReader<LoadParticleTypesSignal>,
+    mut pending: ResMut<PendingParticleIdRemap>,
+) {
+    if msgr_load_particle_types.read().count() > 0 {
+        pending.0 = true;
+    }
+}
+
+pub(super) fn remap_loaded_particle_ids(
+    mut commands: Commands,
+    mut pending: ResMut<PendingParticleIdRemap>,
+    persistence_config: Res<ParticlePersistenceConfig>,
+    particle_types_file: Option<Res<ParticleTypesFile>>,
+    added: Query<(), Added<ParticleType>>,
+) {
+    if !pending.0 || added.is_empty() {
+        return;
+    }
+    pending.0 = false;
+
+    let Some(particle_types_file) = particle_types_file else {
+        return;
+    };
+    let Some(manifest) =
+        read_chunk_particle_manifest(&persistence_config.save_path, &particle_types_file.0)
+    else {
+        return;
+    };
+    let manifest: HashMap<String, ParticleTypeId> = manifest
+        .particles
+        .into_iter()
+        .map(|entry| (entry.name, ParticleTypeId::from_raw(entry.id as _)))
+        .collect();
+
+    commands.queue(move |world: &mut World| {
+        let remap = remap_particle_type_ids(world, &manifest);
+        for (name, from, to) in &remap.remapped {
+            info!(
+                "Particle type '{name}' moved from id {} to id {} to match saved chunks",
+                from.get(),
+                to.get()
+            );
+        }
+        for (name, id) in &remap.vanished {
+            warn!(
+                "Particle type '{name}' (id {}) is no longer in the particle set; its particles in saved chunks have no type",
+                id.get()
+            );
+        }
+    });
+}
+
+/// Give each particle type the id its name has in `manifest`, ignoring case. Types the manifest doesn't know keep
+/// their id unless the manifest has given it to another type, in which case they get a new one.
+/// References between types are rewritten to the new ids.
+pub fn remap_particle_type_ids(
+    world: &mut World,
+    manifest: &HashMap<String, ParticleTypeId>,
+) -> ParticleIdRemap {
+    let mut types: Vec<(Entity, ParticleTypeId, String)> = world
+        .query::<(Entity, &ParticleType, Option<&ParticleName>)>()
+        .iter(world)
+        .map(|(entity, particle_type, name)| {
+            let name = name
+                .map(|name| name.0.clone())
+                .unwrap_or_else(|| format!("Particle {}", particle_type.id().get()));
+            (entity, particle_type.id(), name)
+        })
+        .collect();
+    types.sort_by_key(|(_, id, _)| id.get());
+
+    let manifest_ids: HashMap<String, ParticleTypeId> = manifest
+        .iter()
+        .map(|(name, id)| (name.to_lowercase(), *id))
+        .collect();
+    let mut used: HashSet<ParticleTypeId> = manifest.values().copied().collect();
+    let mut targets: HashMap<Entity, ParticleTypeId> = HashMap::default();
+    // Types named in the manifest claim their ids first, so unnamed types can't take them.
+    for (entity, _, name) in &types {
+        if let Some(&id) = manifest_ids.get(&name.to_lowercase())
+            && !targets.values().any(|target| *target == id)
+        {
+            targets.insert(*entity, id);
+        }
+    }
+    for (entity, id, _) in &types {
+        if targets.contains_key(entity) {
+            continue;
+        }
+        let id = if used.insert(*id) {
+            *id
+        } else {
+            std::iter::repeat_with(|| ParticleType::new().id())
+                .find(|id| used.insert(*id))
+                .unwrap_or(*id)
+        };
+        targets.insert(*entity, id);
+    }
+
+    let mut remap = ParticleIdRemap::default();
+    let mut ids: HashMap<ParticleTypeId, ParticleTypeId> = HashMap::default();
+    for (entity, id, name) in &types {
+        let target = targets[entity];
+        if target != *id {
+            ids.insert(*id, target);
+            remap.remapped.push((name.clone(), *id, target));
+        }
+    }
+    let names: HashSet<String> = types
+        .iter()
+        .map(|(_, _, name)| name.to_lowercase())
+        .collect();
+    remap.vanished = manifest
+        .iter()
+        .filter(|(name, _)| !names.contains(&name.to_lowercase()))
+        .map(|(name, id)| (name.clone(), *id))
+        .collect();
+    remap.vanished.sort_by_key(|(_, id)| id.get());
+
+    if ids.is_empty() {
+        return remap;
+    }
+    // Ids are released before any are reassigned, so types can swap ids.
+    let changed: Vec<Entity> = types
+        .iter()
+        .filter(|(_, id, _)| ids.contains_key(id))
+        .map(|(entity, ..)| *entity)
+        .collect();
+    for entity in &changed {
+        world.entity_mut(*entity).remove::<ParticleType>();
+    }
+    for entity in &changed {
+        world
+            .entity_mut(*entity)
+            .insert(ParticleType::from_id(targets[entity]));
+    }
+    for (entity, ..) in &types {
+        remap_particle_references(&mut world.entity_mut(*entity), |id| {
+            Some(ids.get(&id).copied().unwrap_or(id))
+        });
+    }
+
+    remap
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_falling_sand::{core::ParticleTypeRegistry, prelude::ChanceMutation};
+
+    use super::*;
+
+    fn id(raw: u32) -> ParticleTypeId {
+        ParticleTypeId::from_raw(raw as _)
+    }
+
+    fn spawn_particle_type(world: &mut World, raw: u32, name: &str) -> Entity {
+        world
+            .spawn((
+                ParticleType::from_id(id(raw)),
+                ParticleName(name.to_string()),
+            ))
+            .id()
+    }
+
+    fn manifest(entries: &[(&str, u32)]) -> HashMap<String, ParticleTypeId> {
+        entries
+            .iter()
+            .map(|(name, raw)| (name.to_string(), id(*raw)))
+            .collect()
+    }
+
+    fn new_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<ParticleTypeRegistry>();
+        world
+    }
+
+    #[test]
+    fn swaps_ids_and_rewrites_references() {
+        let mut world = new_world();
+        let sand = spawn_particle_type(&mut world, 0, "Sand");
+        let water = spawn_particle_type(&mut world, 1, "Water");
+        world
+            .entity_mut(sand)
+            .insert(ChanceMutation::new(id(1), 0.5));
+
+        let remap = remap_particle_type_ids(&mut world, &manifest(&[("Sand", 1), ("Water", 0)]));
+
+        assert_eq!(world.get::<ParticleType>(sand).unwrap().id(), id(1));
+        assert_eq!(world.get::<ParticleType>(water).unwrap().id(), id(0));
+        assert_eq!(world.get::<ChanceMutation>(sand).unwrap().target, id(0));
+        assert_eq!(
+            remap,
+            ParticleIdRemap {
+                remapped: vec![
+                    ("Sand".to_string(), id(0), id(1)),
+                    ("Water".to_string(), id(1), id(0)),
+                ],
+                vanished: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_ids_that_already_match() {
+        let mut world = new_world();
+        let sand = spawn_particle_type(&mut world, 0, "Sand");
+
+        let remap = remap_particle_type_ids(&mut world, &manifest(&[("Sand", 0)]));
+
+        assert_eq!(world.get::<ParticleType>(sand).unwrap().id(), id(0));
+        assert_eq!(remap, ParticleIdRemap::default());
+    }
+
+    #[test]
+    fn moves_unknown_types_off_manifest_ids_and_reports_vanished_types() {
+        let mut world = new_world();
+        let lava = spawn_particle_type(&mut world, 0, "Lava");
+
+        let remap = remap_particle_type_ids(&mut world, &manifest(&[("Sand", 0)]));
+
+        let lava_id = world.get::<ParticleType>(lava).unwrap().id();
+        assert_ne!(lava_id, id(0));
+        assert_eq!(remap.remapped, vec![("Lava".to_string(), id(0), lava_id)]);
+        assert_eq!(remap.vanished, vec![("Sand".to_string(), id(0))]);
+    }
+
+    #[test]
+    fn matches_manifest_names_ignoring_case() {
+        let mut world = new_world();
+        let sand = spawn_particle_type(&mut world, 0, "Wet Sand");
+
+        let remap = remap_particle_type_ids(&mut world, &manifest(&[("wet sand", 3)]));
+
+        assert_eq!(world.get::<ParticleType>(sand).unwrap().id(), id(3));
+        assert_eq!(
+            remap,
+            ParticleIdRemap {
+                remapped: vec![("Wet Sand".to_string(), id(0), id(3))],
+                vanished: Vec::new(),
+            }
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Particle type templates. A particle type with a [`ParticleTemplate`] inherits every
+//! inheritable component from its parent type, except those it overrides.
+//!
+//! Inherited components are copied onto the child, so it behaves and saves like any other
+//! particle type. When a parent's component changes, the change is copied to every child that
+//! doesn't override it, and from there to grandchildren on the next frame. Changing an
+//! inherited component on the child itself overrides it.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::TypePath,
+};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::*,
+};
+
+use crate::chunk_effects::{BurnEffect, GasEffect, GlowEffect, LiquidEffect};
+
+pub(super) struct TemplatePlugin;
+
+impl Plugin for TemplatePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ParticleTemplate>()
+            .init_resource::<InheritableComponents>()
+            .add_systems(
+                Update,
+                record_applied_template_parents.after(InheritTemplateSystems),
+            );
+        add_inheritable::<Movement>(app);
+        add_inheritable::<Density>(app);
+        add_inheritable::<Speed>(app);
+        add_inheritable::<Momentum>(app);
+        add_inheritable::<ParticleResistor>(app);
+        add_inheritable::<AirResistance>(app);
+        add_inheritable::<StaticRigidBodyParticle>(app);
+        add_inheritable::<TimedLifetime>(app);
+        add_inheritable::<ChanceLifetime>(app);
+        add_inheritable::<TimedMutation>(app);
+        add_inheritable::<ChanceMutation>(app);
+        add_inheritable::<ColorProfile>(app);
+        add_inheritable::<Flammable>(app);
+        add_inheritable::<ContactReaction>(app);
+        add_inheritable::<Corrosive>(app);
+        add_inheritable::<Corrodible>(app);
+        add_inheritable::<LiquidEffect>(app);
+        add_inheritable::<GasEffect>(app);
+        add_inheritable::<GlowEffect>(app);
+        add_inheritable::<BurnEffect>(app);
+    }
+}
+
+/// Declares that a particle type inherits from another.
+#[derive(Component, Clone, PartialEq, Debug, Reflect)]
+#[reflect(Component)]
+#[type_path = "bfs_editor::particle"]
+pub struct ParticleTemplate {
+    pub parent: ParticleTypeId,
+    /// Short type names of the inheritable components this type defines itself.
+    pub overrides: Vec<String>,
+}
+
+impl ParticleTemplate {
+    pub fn overrides(&self, component: &str) -> bool {
+        self.overrides.iter().any(|name| name == component)
+    }
+}
+
+/// The parent a child's inherited components were last copied from. A child whose template names
+/// another parent copies every inherited component from its new parent.
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+struct AppliedTemplateParent(ParticleTypeId);
+
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+struct InheritTemplateSystems;
+
+/// Short type names of the components a particle type can inherit from its template, in the
+/// order they are shown in the editor.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct InheritableComponents(pub Vec<&'static str>);
+
+fn add_inheritable<C: Component + Clone + Reflect + TypePath>(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<InheritableComponents>()
+        .0
+        .push(C::short_type_path());
+    app.add_systems(
+        Update,
+        inherit_component::<C>.in_set(InheritTemplateSystems),
+    );
+}
+
+/// Copy `C` from parents to the children that inherit it, and record an override on children
+/// whose `C` was changed directly.
+fn inherit_component<C: Component + Clone + Reflect + TypePath>(
+    mut commands: Commands,
+    particle_types: Query<(Entity, &ParticleType, Option<Ref<C>>)>,
+    mut templates: Query<
+        (
+            Entity,
+            &mut ParticleTemplate,
+            Option<&AppliedTemplateParent>,
+        ),
+        With<ParticleType>,
+    >,
+    mut removed: RemovedComponents<C>,
+    mut msgw_sync: MessageWriter<SyncParticleTypeChildrenSignal>,
+) {
+    let component = C::short_type_path();
+    let removed: HashSet<Entity> = removed.read().collect();
+    let entities: HashMap<ParticleTypeId, Entity> = particle_types
+        .iter()
+        .map(|(entity, particle_type, _)| (particle_type.id(), entity))
+        .collect();
+
+    for (child, mut template, applied_parent) in &mut templates {
+        if template.overrides(component) {
+            continue;
+        }
+        let Some(&parent) = entities.get(&template.parent) else {
+            continue;
+        };
+        let (Ok((.., parent_value)), Ok((.., child_value))) =
+            (particle_types.get(parent), particle_types.get(child))
+        else {
+            continue;
+        };
+        if parent == child {
+            continue;
+        }
+
+        let parent_changed = parent_value
+            .as_ref()
+            .is_some_and(|value| value.is_changed())
+            || removed.contains(&parent);
+        let child_changed = child_value.as_ref().is_some_and(|value| value.is_changed())
+            || removed.contains(&child);
+        let same = match (&parent_value, &child_value) {
+            (Some(parent_value), Some(child_value)) => child_value
+                .as_partial_reflect()
+                .reflect_partial_eq(parent_value.as_partial_reflect())
+                .unwrap_or(false),
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            continue;
+        }
+
+        // Compared explicitly rather than through the template's change tick, which the other
+        // `inherit_component` systems also set when they record an override.
+        let parent_reassigned = applied_parent != Some(&AppliedTemplateParent(template.parent));
+        if child_changed && !parent_changed && !parent_reassigned {
+            template.overrides.push(component.to_string());
+            continue;
+        }
+        match parent_value {
+            Some(parent_value) => {
+                commands.entity(child).insert((*parent_value).clone());
+            }
+            None => {
+                commands.entity(child).remove::<C>();
+            }
+        }
+        msgw_sync.write(SyncParticleTypeChildrenSignal::from_parent_handle(child));
+    }
+}
+
+/// Remember which parent each child's inherited components now come from.
+fn record_applied_template_parents(
+    mut commands: Commands,
+    templates: Query<(Entity, &ParticleTemplate, Option<&AppliedTemplateParent>)>,
+    untemplated: Query<Entity, (With<AppliedTemplateParent>, Without<ParticleTemplate>)>,
+) {
+    for (entity, template, applied_parent) in &templates {
+        let parent = AppliedTemplateParent(template.parent);
+        if applied_parent != Some(&parent) {
+            commands.entity(entity).insert(parent);
+        }
+    }
+    for entity in &untemplated {
+        commands.entity(entity).remove::<AppliedTemplateParent>();
+    }
+}
+
+/// Make `child` inherit from `parent`, or stop inheriting with `None`. Components on which the
+/// child already differs from the parent are overridden, so it keeps its current definition.
+/// Parents that would make a cycle are ignored.
+pub fn set_particle_template(world: &mut World, child: Entity, parent: Option<ParticleTypeId>) {
+    let Some(parent) = parent else {
+        world.entity_mut(child).remove::<ParticleTemplate>();
+        return;
+    };
+    let Some(child_id) = world.get::<ParticleType>(child).map(ParticleType::id) else {
+        return;
+    };
+    let parents = template_parents(world);
+    if parent == child_id || template_ancestors(&parents, parent).contains(&child_id) {
+        return;
+    }
+    let Some(parent_entity) = world
+        .query::<(Entity, &ParticleType)>()
+        .iter(world)
+        .find(|(_, particle_type)| particle_type.id() == parent)
+        .map(|(entity, _)| entity)
+    else {
+        return;
+    };
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    let inheritable = world.resource::<InheritableComponents>().0.clone();
+    let (parent_ref, child_ref) = (world.entity(parent_entity), world.entity(child));
+    let overrides = inheritable
+        .into_iter()
+        .filter(|component| {
+            let Some(reflect_component) = type_registry
+                .get_with_short_type_path(component)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            else {
+                return false;
+            };
+            match (
+                reflect_component.reflect(parent_ref),
+                reflect_component.reflect(child_ref),
+            ) {
+                (Some(parent_value), Some(child_value)) => !child_value
+                    .as_partial_reflect()
+                    .reflect_partial_eq(parent_value.as_partial_reflect())
+                    .unwrap_or(false),
+                (None, None) => false,
+                _ => true,
+            }
+        })
+        .map(str::to_string)
+        .collect();
+    world
+        .entity_mut(child)
+        .insert(ParticleTemplate { parent, overrides });
+}
+
+/// Stop or start overriding `component` on `child`. A component that is no longer overridden
+/// is copied from the parent.
+pub fn set_template_override(world: &mut World, child: Entity, component: &str, overridden: bool) {
+    let Some(mut template) = world.get_mut::<ParticleTemplate>(child) else {
+        return;
+    };
+    template.overrides.retain(|name| name != component);
+    if overridden {
+        template.overrides.push(component.to_string());
+    }
+}
+
+/// The parent of every particle type that has a template.
+pub fn template_parents(world: &mut World) -> HashMap<ParticleTypeId, ParticleTypeId> {
+    world
+        .query::<(&ParticleType, &ParticleTemplate)>()
+        .iter(world)
+        .map(|(particle_type, template)| (particle_type.id(), template.parent))
+        .collect()
+}
+
+/// The parents of the particle type `id`, nearest first, stopping before a cycle.
+pub fn template_ancestors(
+    parents: &HashMap<ParticleTypeId, ParticleTypeId>,
+    id: ParticleTypeId,
+) -> Vec<ParticleTypeId> {
+    let mut ancestors = Vec::new();
+    let mut current = id;
+    while let Some(&parent) = parents.get(&current) {
+        if parent == id || ancestors.contains(&parent) {
+            break;
+        }
+        ancestors.push(parent);
+        current = parent;
+    }
+    ancestors
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_falling_sand::core::ParticleTypeRegistry;
+
+    use super::*;
+
+    fn id(raw: u32) -> ParticleTypeId {
+        ParticleTypeId::from_raw(raw as _)
+    }
+
+    fn new_app() -> App {
+        let mut app = App::new();
+        app.add_message::<SyncParticleTypeChildrenSignal>()
+            .init_resource::<ParticleTypeRegistry>()
+            .add_plugins(TemplatePlugin);
+        app
+    }
+
+    /// Spawn a parent and a child inheriting from it, with the same density and speed.
+    fn spawn_family(app: &mut App) -> (Entity, Entity) {
+        let world = app.world_mut();
+        let parent = world
+            .spawn((
+                ParticleType::from_id(id(0)),
+                Density::new(10),
+                Speed::new(1, 2),
+            ))
+            .id();
+        let child = world
+            .spawn((
+                ParticleType::from_id(id(1)),
+                Density::new(10),
+                Speed::new(1, 2),
+                ParticleTemplate {
+                    parent: id(0),
+                    overrides: Vec::new(),
+                },
+            ))
+            .id();
+        app.update();
+        (parent, child)
+    }
+
+    fn has<C: Component + Reflect>(app: &App, entity: Entity, value: C) -> bool {
+        app.world()
+            .get::<C>(entity)
+            .is_some_and(|component| component.reflect_partial_eq(&value).unwrap_or(false))
+    }
+
+    fn overrides(app: &App, child: Entity) -> Vec<String> {
+        let mut overrides = app
+            .world()
+            .get::<ParticleTemplate>(child)
+            .unwrap()
+            .overrides
+            .clone();
+        overrides.sort();
+        overrides
+    }
+
+    #[test]
+    fn parent_changes_are_copied_to_children() {
+        let mut app = new_app();
+        let (parent, child) = spawn_family(&mut app);
+
+        app.world_mut().entity_mut(parent).insert(Density::new(20));
+        app.update();
+
+        assert!(has(&app, child, Density::new(20)));
+        assert!(overrides(&app, child).is_empty());
+    }
+
+    #[test]
+    fn child_edits_become_overrides() {
+        let mut app = new_app();
+        let (_, child) = spawn_family(&mut app);
+
+        app.world_mut().entity_mut(child).insert(Density::new(30));
+        app.update();
+
+        assert!(has(&app, child, Density::new(30)));
+        assert_eq!(overrides(&app, child), ["Density"]);
+    }
+
+    #[test]
+    fn child_edits_to_several_components_at_once_become_overrides() {
+        let mut app = new_app();
+        let (_, child) = spawn_family(&mut app);
+
+        app.world_mut()
+            .entity_mut(child)
+            .insert((Density::new(30), Speed::new(3, 4)));
+        app.update();
+
+        assert!(has(&app, child, Density::new(30)));
+        assert!(has(&app, child, Speed::new(3, 4)));
+        assert_eq!(overrides(&app, child), ["Density", "Speed"]);
+    }
+
+    #[test]
+    fn reassigned_parent_is_copied_over_child_changes() {
+        let mut app = new_app();
+        let (_, child) = spawn_family(&mut app);
+        app.world_mut().spawn((
+            ParticleType::from_id(id(2)),
+            Density::new(40),
+            Speed::new(5, 6),
+        ));
+        app.update();
+
+        let mut child_ref = app.world_mut().entity_mut(child);
+        child_ref.insert(Density::new(30));
+        child_ref.get_mut::<ParticleTemplate>().unwrap().parent = id(2);
+        app.update();
+
+        assert!(has(&app, child, Density::new(40)));
+        assert!(has(&app, child, Speed::new(5, 6)));
+        assert!(overrides(&app, child).is_empty());
+    }
+}
@@ -0,0 +1,449 @@
+//! Checks particle type definitions for mistakes, reporting them whenever they are persisted.
+
+use std::{fmt, path::Path};
+
+use bevy::{
+    ecs::message::MessageCursor,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_falling_sand::{
+    core::particle::{ParticleType, ParticleTypeId},
+    prelude::{
+        ChanceLifetime, ChanceMutation, ColorProfile, ColorSource, ContactOutcome, ContactReaction,
+        Flammable, Movement, PersistParticleTypesSignal, TimedLifetime, TimedMutation,
+    },
+};
+
+use super::{
+    InheritableComponents, ParticleName, ParticleTemplate, template_ancestors, template_parents,
+};
+
+/// Directory texture paths of [`ColorSource::Texture`] are relative to.
+const ASSETS_DIR: &str = "assets";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in a particle type definition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleDiagnostic {
+    pub severity: Severity,
+    /// The particle type the problem was found on.
+    pub entity: Entity,
+    pub particle: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParticleDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Particle type '{}': {}", self.particle, self.message)
+    }
+}
+
+/// The result of the last validation of particle type definitions.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct ParticleDiagnostics {
+    pub diagnostics: Vec<ParticleDiagnostic>,
+}
+
+impl ParticleDiagnostics {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+}
+
+/// Validate every particle type and store the results in [`ParticleDiagnostics`].
+pub fn refresh_particle_diagnostics(world: &mut World) -> &ParticleDiagnostics {
+    let diagnostics = validate_particle_types(world);
+    world.insert_resource(ParticleDiagnostics { diagnostics });
+    world.resource::<ParticleDiagnostics>()
+}
+
+/// Check every particle type for problems, most severe first.
+pub fn validate_particle_types(world: &mut World) -> Vec<ParticleDiagnostic> {
+    let mut query = world.query::<(
+        Entity,
+        &ParticleType,
+        Option<&ParticleName>,
+        Option<&TimedLifetime>,
+        Option<&ChanceLifetime>,
+        Option<&TimedMutation>,
+        Option<&ChanceMutation>,
+        Option<&ContactReaction>,
+        Option<&Flammable>,
+        Option<&ColorProfile>,
+        Option<&Movement>,
+    )>();
+
+    let mut names: HashMap<ParticleTypeId, (Entity, String)> = HashMap::default();
+    for (entity, particle_type, name, ..) in query.iter(world) {
+        let name = name
+            .map(|name| name.0.clone())
+            .unwrap_or_else(|| format!("Particle {}", particle_type.id().get()));
+        names.insert(particle_type.id(), (entity, name));
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut mutations: HashMap<ParticleTypeId, Vec<ParticleTypeId>> = HashMap::default();
+    for (
+        entity,
+        particle_type,
+        _,
+        timed_lifetime,
+        chance_lifetime,
+        timed_mutation,
+        chance_mutation,
+        contact_reaction,
+        burns,
+        color_profile,
+        movement,
+    ) in query.iter(world)
+    {
+        let particle = names[&particle_type.id()].1.clone();
+        let mut report = |severity, message: String| {
+            diagnostics.push(ParticleDiagnostic {
+                severity,
+                entity,
+                particle: particle.clone(),
+                message,
+            });
+        };
+        let mut check_target = |what: &str, target: ParticleTypeId| {
+            if !names.contains_key(&target) {
+                report(
+                    Severity::Error,
+                    format!(
+                        "{what} refers to particle type {}, which does not exist",
+                        target.get()
+                    ),
+                );
+            }
+        };
+
+        if let Some(mutation) = chance_mutation {
+            check_target("Chance mutation target", mutation.target);
+        }
+        if let Some(mutation) = timed_mutation {
+            check_target("Timed mutation target", mutation.target);
+        }
+        if let Some(contact_reaction) = contact_reaction {
+            for (index, rule) in contact_reaction.rules.iter().enumerate() {
+                check_target(&format!("Contact rule {} target", index + 1), rule.target);
+                for (side, outcome) in [
+                    ("source", &rule.source_outcome),
+                    ("target", &rule.target_outcome),
+                ] {
+                    if let ContactOutcome::Becomes(target) = outcome {
+                        check_target(
+                            &format!("Contact rule {} {side} outcome", index + 1),
+                            *target,
+                        );
+                    }
+                }
+            }
+        }
+        if let Some(reaction) = burns.and_then(|burns| burns.reaction.as_ref()) {
+            check_target("Burn product", reaction.produces);
+        }
+
+        if timed_lifetime.is_some_and(|lifetime| lifetime.duration().is_zero()) {
+            report(
+                Severity::Warning,
+                "Timed lifetime has zero duration".to_string(),
+            );
+        }
+        if let Some(lifetime) = chance_lifetime {
+            if lifetime.chance <= 0.0 {
+                report(
+                    Severity::Warning,
+                    "Chance lifetime has zero chance".to_string(),
+                );
+            }
+            if lifetime.tick_timer.duration().is_zero() {
+                report(
+                    Severity::Warning,
+                    "Chance lifetime has zero tick rate".to_string(),
+                );
+            }
+        }
+        if timed_mutation.is_some_and(|mutation| mutation.duration().is_zero()) {
+            report(
+                Severity::Warning,
+                "Timed mutation has zero duration".to_string(),
+            );
+        }
+        if let Some(mutation) = chance_mutation {
+            if mutation.chance <= 0.0 {
+                report(
+                    Severity::Warning,
+                    "Chance mutation has zero chance".to_string(),
+                );
+            }
+            if mutation.tick_timer.duration().is_zero() {
+                report(
+                    Severity::Warning,
+                    "Chance mutation has zero tick rate".to_string(),
+                );
+            }
+        }
+
+        match color_profile.map(|profile| &profile.source) {
+            Some(ColorSource::Palette(palette)) if palette.colors.is_empty() => {
+                report(Severity::Error, "Palette has no colors".to_string());
+            }
+            Some(ColorSource::Gradient(gradient)) if gradient.colors.is_empty() => {
+                report(Severity::Error, "Gradient has no colors".to_string());
+            }
+            Some(ColorSource::Texture(texture)) if texture.path.is_empty() => {
+                report(Severity::Error, "Texture has no file".to_string());
+            }
+            Some(ColorSource::Texture(texture))
+                if !Path::new(ASSETS_DIR).join(&texture.path).is_file() =>
+            {
+                report(
+                    Severity::Error,
+                    format!("Texture file '{}' does not exist", texture.path),
+                );
+            }
+            _ => {}
+        }
+
+        if let Some(movement) = movement
+            && movement
+                .neighbor_groups
+                .iter()
+                .flat_map(|group| group.neighbor_group.iter())
+                .all(|offset| *offset == IVec2::ZERO)
+        {
+            report(
+                Severity::Warning,
+                "Movement has no neighbors to move to, so particles never move".to_string(),
+            );
+        }
+
+        let targets = mutations.entry(particle_type.id()).or_default();
+        targets.extend(chance_mutation.map(|mutation| mutation.target));
+        targets.extend(timed_mutation.map(|mutation| mutation.target));
+    }
+
+    let inheritable = world
+        .get_resource::<InheritableComponents>()
+        .map(|inheritable| inheritable.0.clone())
+        .unwrap_or_default();
+    let parents = template_parents(world);
+    for (entity, particle_type, template) in world
+        .query::<(Entity, &ParticleType, &ParticleTemplate)>()
+        .iter(world)
+    {
+        let id = particle_type.id();
+        let particle = names[&id].1.clone();
+        let mut report = |severity, message: String| {
+            diagnostics.push(ParticleDiagnostic {
+                severity,
+                entity,
+                particle: particle.clone(),
+                message,
+            });
+        };
+        if !names.contains_key(&template.parent) {
+            report(
+                Severity::Error,
+                format!(
+                    "Template refers to particle type {}, which does not exist",
+                    template.parent.get()
+                ),
+            );
+        } else if template.parent == id
+            || template_ancestors(&parents, template.parent).contains(&id)
+        {
+            let path = std::iter::once(id)
+                .chain(template_ancestors(&parents, id))
+                .chain(std::iter::once(id))
+                .map(|id| names[&id].1.as_str())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            report(
+                Severity::Error,
+                format!("Template inherits from itself: {path}"),
+            );
+        }
+        for component in &template.overrides {
+            if !inheritable.contains(&component.as_str()) {
+                report(
+                    Severity::Warning,
+                    format!("Template overrides '{component}', which is not inheritable"),
+                );
+            }
+        }
+    }
+
+    let mut seen_names: HashMap<String, Vec<(Entity, String)>> = HashMap::default();
+    for (entity, name) in names.values() {
+        seen_names
+            .entry(name.to_lowercase())
+            .or_default()
+            .push((*entity, name.clone()));
+    }
+    for duplicates in seen_names.values().filter(|entities| entities.len() > 1) {
+        for (entity, name) in duplicates {
+            diagnostics.push(ParticleDiagnostic {
+                severity: Severity::Error,
+                entity: *entity,
+                particle: name.clone(),
+                message: format!("Name is used by {} particle types", duplicates.len()),
+            });
+        }
+    }
+
+    for cycle in mutation_cycles(&mutations) {
+        let path = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|id| names[id].1.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        let (entity, particle) = names[&cycle[0]].clone();
+        diagnostics.push(ParticleDiagnostic {
+            severity: Severity::Warning,
+            entity,
+            particle,
+            message: format!("Particles mutate in an endless cycle: {path}"),
+        });
+    }
+
+    diagnostics.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.particle.cmp(&b.particle))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+    diagnostics
+}
+
+/// Every cycle in the mutation graph, each starting at its smallest id.
+fn mutation_cycles(
+    mutations: &HashMap<ParticleTypeId, Vec<ParticleTypeId>>,
+) -> Vec<Vec<ParticleTypeId>> {
+    fn visit(
+        id: ParticleTypeId,
+        mutations: &HashMap<ParticleTypeId, Vec<ParticleTypeId>>,
+        path: &mut Vec<ParticleTypeId>,
+        done: &mut HashSet<ParticleTypeId>,
+        cycles: &mut HashSet<Vec<ParticleTypeId>>,
+    ) {
+        if let Some(start) = path.iter().position(|visited| *visited == id) {
+            let mut cycle = path[start..].to_vec();
+            let min = (0..cycle.len())
+                .min_by_key(|&i| cycle[i].get())
+                .unwrap_or(0);
+            cycle.rotate_left(min);
+            cycles.insert(cycle);
+            return;
+        }
+        if done.contains(&id) {
+            return;
+        }
+        path.push(id);
+        for target in mutations.get(&id).into_iter().flatten() {
+            visit(*target, mutations, path, done, cycles);
+        }
+        path.pop();
+        done.insert(id);
+    }
+
+    let mut cycles = HashSet::default();
+    let mut done = HashSet::default();
+    for id in mutations.keys() {
+        visit(*id, mutations, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    let mut cycles: Vec<_> = cycles.into_iter().collect();
+    cycles.sort_by_key(|cycle| cycle.iter().map(|id| id.get()).collect::<Vec<_>>());
+    cycles
+}
+
+/// Validate particle types whenever persisting them is requested, logging what was found.
+///
+/// This reports alongside the write rather than gating it: the persisted set is written in the
+/// same frame whether or not problems are found.
+pub(super) fn validate_before_persist(
+    world: &mut World,
+    mut persist: Local<MessageCursor<PersistParticleTypesSignal>>,
+) {
+    let requested = persist
+        .read(world.resource::<Messages<PersistParticleTypesSignal>>())
+        .count()
+        > 0;
+    if !requested {
+        return;
+    }
+    for diagnostic in &refresh_particle_diagnostics(world).diagnostics {
+        match diagnostic.severity {
+            Severity::Warning => warn!("{diagnostic}"),
+            Severity::Error => error!("{diagnostic}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(u32, u32)]) -> HashMap<ParticleTypeId, Vec<ParticleTypeId>> {
+        let mut mutations: HashMap<ParticleTypeId, Vec<ParticleTypeId>> = HashMap::default();
+        for (from, to) in edges {
+            mutations
+                .entry(ParticleTypeId::from_raw(*from as _))
+                .or_default()
+                .push(ParticleTypeId::from_raw(*to as _));
+        }
+        mutations
+    }
+
+    fn ids(raw: &[u32]) -> Vec<ParticleTypeId> {
+        raw.iter()
+            .map(|id| ParticleTypeId::from_raw(*id as _))
+            .collect()
+    }
+
+    #[test]
+    fn acyclic_mutations_have_no_cycles() {
+        assert!(mutation_cycles(&graph(&[(0, 1), (1, 2), (0, 2)])).is_empty());
+    }
+
+    #[test]
+    fn self_mutation_is_a_cycle() {
+        assert_eq!(mutation_cycles(&graph(&[(3, 3)])), vec![ids(&[3])]);
+    }
+
+    #[test]
+    fn cycles_start_at_their_smallest_id() {
+        assert_eq!(
+            mutation_cycles(&graph(&[(5, 2), (2, 7), (7, 5)])),
+            vec![ids(&[2, 7, 5])]
+        );
+    }
+
+    #[test]
+    fn separate_cycles_are_reported_once_each_in_order() {
+        assert_eq!(
+            mutation_cycles(&graph(&[(4, 3), (3, 4), (1, 0), (0, 1), (2, 0)])),
+            vec![ids(&[0, 1]), ids(&[3, 4])]
+        );
+    }
+}
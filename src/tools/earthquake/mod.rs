@@ -24,7 +24,7 @@ pub(super) struct EarthquakePlugin;
 
 impl Plugin for EarthquakePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
+        app.register_type::<EarthquakeConfiguration>().add_plugins((
             debug::DebugPlugin,
             gizmos::GizmosPlugin,
             setup::SetupPlugin,
@@ -10,6 +10,7 @@ pub(super) const EARTHQUAKE_BRUSH_DEFAULT_SIZE: f32 = 24.0;
 pub(super) const EARTHQUAKE_BRUSH_MIN_SIZE: f32 = 1.0;
 
 #[derive(Resource, Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
 #[serde(default)]
 pub struct EarthquakeConfiguration {
     pub brush: ToolBrushConfiguration,
@@ -17,7 +17,12 @@ pub struct PainterPlugin;
 
 impl Plugin for PainterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((SetupPlugin, StatesPlugin, SystemsPlugin, GizmosPlugin));
+        app.register_type::<PainterConfiguration>().add_plugins((
+            SetupPlugin,
+            StatesPlugin,
+            SystemsPlugin,
+            GizmosPlugin,
+        ));
     }
 }
 
@@ -7,6 +7,7 @@ pub(super) const PAINTER_BRUSH_DEFAULT_SIZE: f32 = 2.0;
 pub(super) const PAINTER_BRUSH_MIN_SIZE: f32 = 1.0;
 
 #[derive(Resource, Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
 #[serde(default)]
 pub struct PainterConfiguration {
     pub brush: ToolBrushConfiguration,
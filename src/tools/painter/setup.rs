@@ -52,7 +52,6 @@ impl Plugin for SetupPlugin {
 #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
 pub struct PainterKeyBindings {
     pub draw: InputButton,
-    #[serde(alias = "toggle_brush_mode")]
     pub toggle_mode: InputButton,
 }
 
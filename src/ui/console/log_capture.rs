@@ -1,7 +1,8 @@
 //! Sets up a custom log layer for capturing logs, exposing them as a resource within the ECS.
-use bevy::log::BoxedLayer;
 use bevy::log::tracing_subscriber::Layer;
+use bevy::log::{BoxedLayer, Level};
 use bevy::prelude::*;
+use std::fmt;
 use std::sync::{Arc, Mutex};
 
 /// Creates a custom log layer and inserts the `LogCapture` resource into the app.
@@ -14,15 +15,30 @@ pub fn console_capture_layer(app: &mut App) -> Option<BoxedLayer> {
     Some(Box::new(layer) as BoxedLayer)
 }
 
+/// A log event captured for the console.
+#[derive(Clone, Debug)]
+pub struct CapturedLog {
+    pub level: Level,
+    /// The module path or explicit target the event was logged from.
+    pub target: String,
+    pub message: String,
+}
+
+impl fmt::Display for CapturedLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.level, self.target, self.message)
+    }
+}
+
 #[derive(Resource, Clone, Default)]
 pub struct LogCapture {
-    logs: Arc<Mutex<Vec<String>>>,
+    logs: Arc<Mutex<Vec<CapturedLog>>>,
 }
 
 impl LogCapture {
     /// Drain all captured logs, returning them and clearing the internal buffer.
     #[allow(unused)]
-    pub fn drain(&self) -> Vec<String> {
+    pub fn drain(&self) -> Vec<CapturedLog> {
         self.logs
             .lock()
             .map(|mut logs| std::mem::take(&mut *logs))
@@ -44,7 +60,7 @@ impl LogCapture {
 
 /// `Layer` that captures logs to a shared buffer intended for use by the console.
 struct ConsoleCaptureLayer {
-    logs: Arc<Mutex<Vec<String>>>,
+    logs: Arc<Mutex<Vec<CapturedLog>>>,
 }
 
 impl<S: tracing::Subscriber> Layer<S> for ConsoleCaptureLayer {
@@ -57,7 +73,12 @@ impl<S: tracing::Subscriber> Layer<S> for ConsoleCaptureLayer {
         event.record(&mut visitor);
 
         if let Ok(mut logs) = self.logs.lock() {
-            logs.push(format!("[{}] {}", event.metadata().level(), visitor.0));
+            let metadata = event.metadata();
+            logs.push(CapturedLog {
+                level: *metadata.level(),
+                target: metadata.target().to_string(),
+                message: visitor.0,
+            });
         }
     }
 }
@@ -9,7 +9,7 @@ use crate::{
     ui::{CommandHistory, ConsoleInformationAreaState, ConsolePromptState},
 };
 
-use super::ConsoleCache;
+use super::{ConsoleCache, ConsoleCompletions};
 
 pub(super) struct SetupPlugin;
 
@@ -17,6 +17,7 @@ impl Plugin for SetupPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(InputManagerPlugin::<ConsoleAction>::default())
             .init_resource::<ConsoleCache>()
+            .init_resource::<ConsoleCompletions>()
             .init_resource::<ConsoleInformationAreaState>()
             .init_resource::<ConsolePromptState>()
             .add_systems(
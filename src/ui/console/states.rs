@@ -1,13 +1,17 @@
 use std::{
-    fs,
+    fmt, fs,
     io::{BufRead, BufReader, Write},
     path::{Path, PathBuf},
 };
 
-use bevy::{platform::collections::HashMap, prelude::*};
+use bevy::{log::Level, platform::collections::HashMap, prelude::*};
+use shlex::Shlex;
 use trie_rs::{Trie, TrieBuilder};
 
-use crate::{console_command::ConsoleCommandRegistry, ui::LogCapture};
+use crate::{
+    console_command::{CommandPayload, ConsoleCommandCompleted, ConsoleCommandRegistry, Table},
+    ui::{CapturedLog, LogCapture},
+};
 
 pub struct StatePlugin;
 
@@ -17,6 +21,7 @@ impl Plugin for StatePlugin {
             Update,
             (
                 rebuild_console_cache.run_if(resource_changed::<ConsoleCommandRegistry>),
+                update_console_completions.run_if(resource_exists::<ConsoleCommandRegistry>),
                 update_information_area,
             ),
         );
@@ -28,6 +33,59 @@ fn rebuild_console_cache(mut cache: ResMut<ConsoleCache>, registry: Res<ConsoleC
     cache.rebuild_tries(&registry);
 }
 
+/// Recompute completions for the prompt whenever its text changes.
+fn update_console_completions(world: &mut World) {
+    let input = world.resource::<ConsolePromptState>().input_text.clone();
+    if world.resource::<ConsoleCompletions>().input == input {
+        return;
+    }
+
+    let completions = world.resource_scope(|world, registry: Mut<ConsoleCommandRegistry>| {
+        world.resource_scope(|world, cache: Mut<ConsoleCache>| {
+            cache.complete(&input, &registry, world)
+        })
+    });
+    *world.resource_mut::<ConsoleCompletions>() = completions;
+}
+
+/// Completion candidates for the current prompt text.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct ConsoleCompletions {
+    /// The prompt text these completions were computed for.
+    pub input: String,
+    /// Byte offset in `input` where the argument being completed starts.
+    pub replace_from: usize,
+    /// The partially typed argument, unquoted.
+    pub partial: String,
+    pub candidates: Vec<String>,
+}
+
+impl ConsoleCompletions {
+    /// The prompt text with the argument being completed replaced by `candidate`.
+    pub fn apply(&self, candidate: &str) -> String {
+        let quoted = shlex::try_quote(candidate)
+            .map(|quoted| quoted.into_owned())
+            .unwrap_or_else(|_| candidate.to_string());
+        format!("{}{quoted}", &self.input[..self.replace_from])
+    }
+
+    /// The longest prefix shared by every candidate.
+    pub fn common_prefix(&self) -> Option<String> {
+        let (first, rest) = self.candidates.split_first()?;
+        let mut prefix = first.as_str();
+        for candidate in rest {
+            let len = prefix
+                .char_indices()
+                .zip(candidate.chars())
+                .take_while(|((_, a), b)| a.eq_ignore_ascii_case(b))
+                .last()
+                .map_or(0, |((index, a), _)| index + a.len_utf8());
+            prefix = &prefix[..len];
+        }
+        Some(prefix.to_string())
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ConsoleCache {
     pub context_tries: HashMap<Vec<String>, Trie<u8>>,
@@ -61,6 +119,64 @@ impl ConsoleCache {
         }
     }
 
+    /// Complete the last argument of `input`, using the subcommand tries for command names and
+    /// [`ConsoleCommand::complete`](crate::console_command::ConsoleCommand::complete) for
+    /// arguments.
+    pub fn complete(
+        &self,
+        input: &str,
+        registry: &ConsoleCommandRegistry,
+        world: &mut World,
+    ) -> ConsoleCompletions {
+        let (segment_start, replace_from) = completion_offsets(input);
+        let mut tokens: Vec<String> = Shlex::new(&input[segment_start..replace_from]).collect();
+        let partial: String = Shlex::new(&input[replace_from..])
+            .next()
+            .unwrap_or_else(|| input[replace_from..].trim_matches(['"', '\'']).to_string());
+
+        // Walk the command tree as far as the typed tokens match subcommands.
+        let mut path = Vec::new();
+        let mut node = None;
+        for token in &tokens {
+            let next = match node {
+                None => registry.get(token),
+                Some(node) => node.children.get(token),
+            };
+            match next {
+                Some(next) => {
+                    path.push(token.clone());
+                    node = Some(next);
+                }
+                None => break,
+            }
+        }
+        let args = tokens.split_off(path.len());
+
+        let mut candidates: Vec<String> = Vec::new();
+        if args.is_empty()
+            && let Some(trie) = self.context_tries.get(&path)
+        {
+            candidates.extend(trie.predictive_search(&partial).collect::<Vec<String>>());
+        }
+        if let Some(node) = node {
+            let partial_lower = partial.to_lowercase();
+            candidates.extend(
+                node.complete_args(&args, world)
+                    .into_iter()
+                    .filter(|candidate| candidate.to_lowercase().starts_with(&partial_lower)),
+            );
+        }
+        candidates.sort();
+        candidates.dedup();
+
+        ConsoleCompletions {
+            input: input.to_string(),
+            replace_from,
+            partial,
+            candidates,
+        }
+    }
+
     fn get_context_completions(
         &self,
         context_path: &[String],
@@ -79,10 +195,197 @@ impl ConsoleCache {
     }
 }
 
-#[derive(Resource, Default)]
+/// Byte offsets of the start of the last `;`-separated command in `input` and of its last,
+/// possibly partial, token. Quotes are respected.
+fn completion_offsets(input: &str) -> (usize, usize) {
+    let mut segment_start = 0;
+    let mut token_start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (index, c) in input.char_indices() {
+        match (c, quote) {
+            _ if escaped => escaped = false,
+            ('\\', _) => escaped = true,
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (';', None) => {
+                segment_start = index + 1;
+                token_start = index + 1;
+            }
+            (c, None) if c.is_whitespace() => token_start = index + c.len_utf8(),
+            _ => {}
+        }
+    }
+    (segment_start, token_start)
+}
+
+/// Default number of lines kept in the information area before the oldest are dropped.
+pub const DEFAULT_LOG_HISTORY_LIMIT: usize = 2000;
+
+#[derive(Resource)]
 pub struct ConsoleInformationAreaState {
     pub is_open: bool,
-    pub log_history: Vec<String>,
+    pub log_history: Vec<ConsoleLine>,
+    /// Maximum number of lines kept in `log_history`.
+    pub history_limit: usize,
+    pub filter: ConsoleLogFilter,
+}
+
+impl Default for ConsoleInformationAreaState {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            log_history: Vec::new(),
+            history_limit: DEFAULT_LOG_HISTORY_LIMIT,
+            filter: ConsoleLogFilter::default(),
+        }
+    }
+}
+
+impl ConsoleInformationAreaState {
+    /// Append lines, dropping the oldest once the history limit is exceeded.
+    pub fn push_lines(&mut self, lines: impl IntoIterator<Item = ConsoleLine>) {
+        self.log_history.extend(lines);
+        self.enforce_history_limit();
+    }
+
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
+        self.enforce_history_limit();
+    }
+
+    fn enforce_history_limit(&mut self) {
+        let excess = self.log_history.len().saturating_sub(self.history_limit);
+        self.log_history.drain(..excess);
+    }
+
+    /// Lines that pass the current filter.
+    pub fn visible_lines(&self) -> impl DoubleEndedIterator<Item = &ConsoleLine> {
+        self.log_history
+            .iter()
+            .filter(|line| self.filter.matches(line))
+    }
+}
+
+/// Which lines of the information area are shown.
+#[derive(Clone, Debug)]
+pub struct ConsoleLogFilter {
+    /// The most verbose log level shown. Lines that are not logs are always shown.
+    pub level: Level,
+    /// Only show logs whose target contains this text.
+    pub target: String,
+    /// Only show lines containing this text, ignoring ASCII case. Matches are highlighted.
+    pub search: String,
+}
+
+impl Default for ConsoleLogFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::TRACE,
+            target: String::new(),
+            search: String::new(),
+        }
+    }
+}
+
+impl ConsoleLogFilter {
+    pub fn matches(&self, line: &ConsoleLine) -> bool {
+        if let Some(level) = line.level
+            && level > self.level
+        {
+            return false;
+        }
+        if !self.target.is_empty()
+            && !line
+                .target
+                .as_ref()
+                .is_some_and(|target| target.contains(self.target.as_str()))
+        {
+            return false;
+        }
+        self.search.is_empty()
+            || line
+                .label()
+                .to_ascii_lowercase()
+                .contains(&self.search.to_ascii_lowercase())
+    }
+}
+
+/// A line shown in the console's information area.
+#[derive(Clone, Debug)]
+pub struct ConsoleLine {
+    pub text: String,
+    pub kind: ConsoleLineKind,
+    /// The level of a captured log.
+    pub level: Option<Level>,
+    /// The target of a captured log.
+    pub target: Option<String>,
+    /// Table output from a command, rendered with aligned columns. `text` holds it as text.
+    pub table: Option<Table>,
+}
+
+impl ConsoleLine {
+    pub fn new(kind: ConsoleLineKind, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kind,
+            level: None,
+            target: None,
+            table: None,
+        }
+    }
+
+    pub fn table(table: Table) -> Self {
+        Self {
+            text: table.lines().join("\n"),
+            kind: ConsoleLineKind::Output,
+            level: None,
+            target: None,
+            table: Some(table),
+        }
+    }
+
+    pub fn log(log: CapturedLog) -> Self {
+        Self {
+            text: log.message,
+            kind: ConsoleLineKind::Log,
+            level: Some(log.level),
+            target: Some(log.target),
+            table: None,
+        }
+    }
+
+    /// The text shown in the information area, prefixed with the level for logs.
+    pub fn label(&self) -> String {
+        match &self.level {
+            Some(level) => format!("[{level}] {}", self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Formats the line as it is written to a saved log, including the target of logs.
+impl fmt::Display for ConsoleLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.level, &self.target) {
+            (Some(level), Some(target)) => write!(f, "[{level}] {target}: {}", self.text),
+            _ => write!(f, "{}", self.text),
+        }
+    }
+}
+
+/// Where a console line came from, used to color it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsoleLineKind {
+    /// A captured log message.
+    Log,
+    /// The message of a command that succeeded.
+    Success,
+    /// The message of a command that failed.
+    Failure,
+    /// Structured output from a command, such as the items of a list.
+    Output,
 }
 
 #[derive(Resource)]
@@ -90,6 +393,8 @@ pub struct ConsolePromptState {
     pub input_text: String,
     pub request_focus: bool,
     pub surrender_focus: bool,
+    /// Move the text cursor to the end of the input on the next frame, e.g. after a completion.
+    pub move_cursor_to_end: bool,
 }
 
 impl Default for ConsolePromptState {
@@ -98,6 +403,7 @@ impl Default for ConsolePromptState {
             input_text: String::new(),
             request_focus: false,
             surrender_focus: true,
+            move_cursor_to_end: false,
         }
     }
 }
@@ -195,9 +501,50 @@ impl CommandHistory {
 
 fn update_information_area(
     mut information_area: ResMut<ConsoleInformationAreaState>,
+    mut msgr_command_completed: MessageReader<ConsoleCommandCompleted>,
     log_capture: Res<LogCapture>,
 ) {
-    for log in log_capture.drain() {
-        information_area.log_history.push(log);
+    let mut lines: Vec<ConsoleLine> = log_capture
+        .drain()
+        .into_iter()
+        .map(ConsoleLine::log)
+        .collect();
+
+    for completed in msgr_command_completed.read() {
+        match &completed.result {
+            Ok(output) => {
+                if let Some(message) = &output.message {
+                    lines.push(ConsoleLine::new(ConsoleLineKind::Success, message));
+                }
+                match &output.payload {
+                    Some(CommandPayload::Table(table)) => {
+                        lines.push(ConsoleLine::table(table.clone()))
+                    }
+                    Some(payload) => lines.extend(
+                        payload
+                            .lines()
+                            .into_iter()
+                            .map(|line| ConsoleLine::new(ConsoleLineKind::Output, line)),
+                    ),
+                    None => {}
+                }
+            }
+            Err(error) => {
+                lines.push(ConsoleLine::new(
+                    ConsoleLineKind::Failure,
+                    format!("{}: {error}", completed.input),
+                ));
+                if let Some(usage) = &error.usage {
+                    lines.push(ConsoleLine::new(
+                        ConsoleLineKind::Failure,
+                        format!("Usage: {usage}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    if !lines.is_empty() {
+        information_area.push_lines(lines);
     }
 }
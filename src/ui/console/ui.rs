@@ -1,20 +1,31 @@
-use bevy::prelude::*;
+use bevy::{log::Level, prelude::*};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
 use leafwing_input_manager::prelude::ActionState;
 
 use crate::{
     console_command::ConsoleCommandQueued,
     ui::{
-        CommandHistory, ConsoleAction, ConsoleInformationAreaState, ConsolePromptState, ShowUi,
-        UiSystems,
+        CommandHistory, ConsoleAction, ConsoleCompletions, ConsoleInformationAreaState,
+        ConsoleLine, ConsoleLineKind, ConsolePromptState, ShowUi, UiSystems,
     },
 };
 
+const PROMPT_ID: &str = "console_prompt";
+const MAX_VISIBLE_COMPLETIONS: usize = 24;
+const INFORMATION_AREA_HEIGHT: f32 = 400.0;
+const LOG_LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
 pub(super) struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<CompletionCycle>().add_systems(
             EguiPrimaryContextPass,
             show.run_if(resource_exists::<ShowUi>)
                 .in_set(UiSystems::Console),
@@ -22,12 +33,23 @@ impl Plugin for UiPlugin {
     }
 }
 
+/// Candidates being cycled through by repeated tab presses.
+#[derive(Resource, Default)]
+struct CompletionCycle {
+    completions: Option<ConsoleCompletions>,
+    index: usize,
+    /// The prompt text after the last completion was applied. Cycling stops once it changes.
+    applied: String,
+}
+
 fn show(
     mut contexts: EguiContexts,
     mut msgw_console_command_queued: MessageWriter<ConsoleCommandQueued>,
     mut information_area: ResMut<ConsoleInformationAreaState>,
     mut prompt: ResMut<ConsolePromptState>,
     mut command_history: ResMut<CommandHistory>,
+    completions: Res<ConsoleCompletions>,
+    mut cycle: ResMut<CompletionCycle>,
     action_state: Single<&ActionState<ConsoleAction>>,
 ) -> Result {
     let ctx = contexts.ctx_mut()?;
@@ -43,7 +65,11 @@ fn show(
     }
 
     let console_width = ctx.content_rect().width();
-    let console_y = if information_area.is_open { 400.0 } else { 0.0 };
+    let console_y = if information_area.is_open {
+        INFORMATION_AREA_HEIGHT
+    } else {
+        0.0
+    };
 
     if information_area.is_open {
         egui::Area::new("information_area".into())
@@ -52,7 +78,7 @@ fn show(
                 ui.set_width(console_width);
                 egui::Frame::NONE
                     .fill(console_background())
-                    .show(ui, |ui| information_area_ui(ui, &information_area));
+                    .show(ui, |ui| information_area_ui(ui, &mut information_area));
             });
     }
 
@@ -61,6 +87,11 @@ fn show(
         .show(ctx, |ui| {
             ui.set_width(console_width);
             egui::Frame::NONE.fill(console_background()).show(ui, |ui| {
+                let tab_pressed = prompt_has_focus(ui)
+                    && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab));
+                if tab_pressed {
+                    complete_prompt(&mut prompt, &completions, &mut cycle);
+                }
                 prompt_ui(
                     ui,
                     &mut msgw_console_command_queued,
@@ -69,6 +100,7 @@ fn show(
                     toggle_info_area,
                     &action_state,
                 );
+                completions_ui(ui, &mut prompt, &completions, &cycle);
             });
         });
 
@@ -79,8 +111,16 @@ fn console_background() -> egui::Color32 {
     egui::Color32::from_rgba_unmultiplied(12, 12, 12, 230)
 }
 
-fn information_area_ui(ui: &mut egui::Ui, state: &ConsoleInformationAreaState) {
-    let height = 400.0;
+fn information_area_ui(ui: &mut egui::Ui, state: &mut ConsoleInformationAreaState) {
+    let toolbar = ui.horizontal(|ui| log_filter_ui(ui, state));
+    let height = INFORMATION_AREA_HEIGHT - toolbar.response.rect.height();
+
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let text_color = ui.visuals().text_color();
+    let search = state.filter.search.clone();
+    let visible: Vec<usize> = (0..state.log_history.len())
+        .filter(|&index| state.filter.matches(&state.log_history[index]))
+        .collect();
     egui::ScrollArea::vertical()
         .stick_to_bottom(true)
         .max_height(height)
@@ -88,11 +128,125 @@ fn information_area_ui(ui: &mut egui::Ui, state: &ConsoleInformationAreaState) {
         .auto_shrink(false)
         .show(ui, |ui| {
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-                for msg in state.log_history.iter().rev() {
-                    ui.label(msg);
+                for index in visible.into_iter().rev() {
+                    let line = &mut state.log_history[index];
+                    let color = match line.kind {
+                        ConsoleLineKind::Success => egui::Color32::from_rgb(120, 200, 120),
+                        ConsoleLineKind::Failure => egui::Color32::from_rgb(230, 100, 100),
+                        ConsoleLineKind::Log | ConsoleLineKind::Output => text_color,
+                    };
+                    let format = egui::TextFormat::simple(font_id.clone(), color);
+                    if line.table.is_some() {
+                        table_ui(ui, index, line, &search, format);
+                    } else {
+                        ui.label(highlight_matches(&line.label(), &search, format));
+                    }
+                }
+            });
+        });
+}
+
+/// Show a table output with sortable columns and a button to copy it.
+fn table_ui(
+    ui: &mut egui::Ui,
+    index: usize,
+    line: &mut ConsoleLine,
+    search: &str,
+    format: egui::TextFormat,
+) {
+    let Some(table) = &mut line.table else {
+        return;
+    };
+
+    let mut sort_column = None;
+    ui.vertical(|ui| {
+        egui::Grid::new(("console_table", index))
+            .striped(true)
+            .show(ui, |ui| {
+                for (column, header) in table.headers.iter().enumerate() {
+                    let arrow = match table.sorted_by {
+                        Some((sorted, false)) if sorted == column => " ^",
+                        Some((sorted, true)) if sorted == column => " v",
+                        _ => "",
+                    };
+                    let label = egui::RichText::new(format!("{header}{arrow}")).strong();
+                    if ui
+                        .add(egui::Label::new(label).sense(egui::Sense::click()))
+                        .on_hover_text("Sort by this column")
+                        .clicked()
+                    {
+                        sort_column = Some(column);
+                    }
+                }
+                ui.end_row();
+
+                for row in &table.rows {
+                    for cell in row {
+                        ui.label(highlight_matches(cell, search, format.clone()));
+                    }
+                    ui.end_row();
                 }
             });
+        if ui
+            .small_button("Copy")
+            .on_hover_text("Copy as tab-separated values")
+            .clicked()
+        {
+            ui.ctx().copy_text(table.to_tsv());
+        }
+    });
+
+    if let Some(column) = sort_column {
+        let descending = table.sorted_by == Some((column, false));
+        table.sort_by(column, descending);
+        line.text = table.lines().join("\n");
+    }
+}
+
+fn log_filter_ui(ui: &mut egui::Ui, state: &mut ConsoleInformationAreaState) {
+    let filter = &mut state.filter;
+    egui::ComboBox::from_id_salt("console_log_level")
+        .selected_text(format!("Level: {}", filter.level))
+        .show_ui(ui, |ui| {
+            for level in LOG_LEVELS {
+                ui.selectable_value(&mut filter.level, level, level.to_string());
+            }
         });
+    ui.add(
+        egui::TextEdit::singleline(&mut filter.target)
+            .hint_text("Target")
+            .desired_width(160.0),
+    );
+    ui.add(
+        egui::TextEdit::singleline(&mut filter.search)
+            .hint_text("Search")
+            .desired_width(200.0),
+    );
+
+    let shown = state.visible_lines().count();
+    ui.weak(format!("{shown}/{} lines", state.log_history.len()));
+}
+
+/// Lay out `text`, highlighting occurrences of `search` ignoring ASCII case.
+fn highlight_matches(text: &str, search: &str, format: egui::TextFormat) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let highlight = egui::TextFormat {
+        background: egui::Color32::from_rgb(110, 90, 20),
+        ..format.clone()
+    };
+
+    let mut last = 0;
+    if !search.is_empty() {
+        // ASCII lowercasing keeps byte offsets valid for the original text.
+        let lower = text.to_ascii_lowercase();
+        for (start, found) in lower.match_indices(&search.to_ascii_lowercase()) {
+            job.append(&text[last..start], 0.0, format.clone());
+            job.append(&text[start..start + found.len()], 0.0, highlight.clone());
+            last = start + found.len();
+        }
+    }
+    job.append(&text[last..], 0.0, format);
+    job
 }
 
 fn prompt_ui(
@@ -106,11 +260,13 @@ fn prompt_ui(
     // Prevent hotkey character from being added to input
     let mut output = if toggled_info_area {
         egui::TextEdit::singleline(&mut prompt.input_text.clone())
+            .id(egui::Id::new(PROMPT_ID))
             .desired_width(ui.available_width())
             .code_editor()
             .show(ui)
     } else {
         egui::TextEdit::singleline(&mut prompt.input_text)
+            .id(egui::Id::new(PROMPT_ID))
             .desired_width(ui.available_width())
             .code_editor()
             .show(ui)
@@ -141,7 +297,8 @@ fn prompt_ui(
             moved = true;
         }
 
-        if moved {
+        if moved || prompt.move_cursor_to_end {
+            prompt.move_cursor_to_end = false;
             let ccursor = egui::text::CCursor::new(prompt.input_text.chars().count());
             output
                 .state
@@ -160,3 +317,92 @@ fn prompt_ui(
         output.response.request_focus();
     }
 }
+
+fn prompt_has_focus(ui: &egui::Ui) -> bool {
+    ui.memory(|memory| memory.has_focus(egui::Id::new(PROMPT_ID)))
+}
+
+/// Complete the prompt on tab: extend to the candidates' common prefix, or cycle through the
+/// candidates on repeated presses.
+fn complete_prompt(
+    prompt: &mut ConsolePromptState,
+    completions: &ConsoleCompletions,
+    cycle: &mut CompletionCycle,
+) {
+    if let Some(cycled) = &cycle.completions
+        && cycle.applied == prompt.input_text
+    {
+        cycle.index = (cycle.index + 1) % cycled.candidates.len();
+        set_prompt_text(prompt, cycled.apply(&cycled.candidates[cycle.index]));
+        cycle.applied = prompt.input_text.clone();
+        return;
+    }
+    cycle.completions = None;
+
+    if completions.input != prompt.input_text {
+        return;
+    }
+    match completions.candidates.as_slice() {
+        [] => {}
+        [candidate] => set_prompt_text(prompt, format!("{} ", completions.apply(candidate))),
+        [first, ..] => {
+            let prefix = completions.common_prefix().unwrap_or_default();
+            if prefix.chars().count() > completions.partial.chars().count() {
+                set_prompt_text(prompt, completions.apply(&prefix));
+            } else {
+                set_prompt_text(prompt, completions.apply(first));
+                cycle.completions = Some(completions.clone());
+                cycle.index = 0;
+                cycle.applied = prompt.input_text.clone();
+            }
+        }
+    }
+}
+
+fn set_prompt_text(prompt: &mut ConsolePromptState, text: String) {
+    prompt.input_text = text;
+    prompt.move_cursor_to_end = true;
+}
+
+fn completions_ui(
+    ui: &mut egui::Ui,
+    prompt: &mut ConsolePromptState,
+    completions: &ConsoleCompletions,
+    cycle: &CompletionCycle,
+) {
+    let (shown, selected) = match &cycle.completions {
+        Some(cycled) if cycle.applied == prompt.input_text => (cycled, Some(cycle.index)),
+        _ => (completions, None),
+    };
+    if prompt.input_text.is_empty()
+        || shown.input.is_empty()
+        || shown.candidates.is_empty()
+        || (selected.is_none() && shown.input != prompt.input_text)
+    {
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for (index, candidate) in shown
+            .candidates
+            .iter()
+            .take(MAX_VISIBLE_COMPLETIONS)
+            .enumerate()
+        {
+            if ui
+                .selectable_label(selected == Some(index), candidate)
+                .clicked()
+            {
+                set_prompt_text(prompt, format!("{} ", shown.apply(candidate)));
+                prompt.request_focus = true;
+            }
+        }
+        let hidden = shown
+            .candidates
+            .len()
+            .saturating_sub(MAX_VISIBLE_COMPLETIONS);
+        if hidden > 0 {
+            ui.weak(format!("(+{hidden} more)"));
+        }
+    });
+}
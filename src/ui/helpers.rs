@@ -29,6 +29,39 @@ where
     drag_value
 }
 
+/// Like [`add_label_with_drag_value`], but the value steps through the powers of two between
+/// `2^exponents.start()` and `2^exponents.end()`.
+pub fn add_label_with_power_of_two_drag_value(
+    ui: &mut egui::Ui,
+    fill: usize,
+    label: impl Into<egui::WidgetText>,
+    value: u32,
+    exponents: RangeInclusive<u32>,
+) -> u32 {
+    ui.label(label);
+    for _ in 0..fill {
+        skip_grid_column(ui);
+    }
+    let mut exponent = value
+        .max(1)
+        .ilog2()
+        .clamp(*exponents.start(), *exponents.end());
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.add(
+            egui::DragValue::new(&mut exponent)
+                .range(exponents)
+                .speed(0.05)
+                .custom_formatter(|exponent, _| (1u64 << exponent as u32).to_string())
+                .custom_parser(|text| {
+                    let value: u32 = text.parse().ok()?;
+                    value.is_power_of_two().then(|| f64::from(value.ilog2()))
+                }),
+        );
+    });
+    ui.end_row();
+    1 << exponent
+}
+
 pub fn add_label_with_toggle_switch(
     ui: &mut egui::Ui,
     fill: usize,
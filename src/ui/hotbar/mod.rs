@@ -0,0 +1,85 @@
+mod setup;
+mod systems;
+mod ui;
+
+pub use setup::*;
+use systems::*;
+use ui::*;
+
+use bevy::prelude::*;
+
+use crate::{
+    config::HotbarSlotConfig,
+    ui::{ParticleCategoryLabels, ParticleLabel},
+};
+
+/// Number of hotbar slots, bound to the number keys 1 to 9 and 0 by default.
+pub const HOTBAR_SLOTS: usize = 10;
+
+pub(super) struct HotbarPlugin;
+
+impl Plugin for HotbarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((SetupPlugin, SystemsPlugin, UiPlugin));
+    }
+}
+
+/// Particle types assigned to the hotbar, by name so they survive id changes between sessions.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Hotbar {
+    pub slots: [Option<String>; HOTBAR_SLOTS],
+    /// The slot last chosen, which sampled particles replace once every slot is full.
+    pub active: usize,
+}
+
+impl Hotbar {
+    pub fn from_config(config: &[HotbarSlotConfig]) -> Self {
+        let mut hotbar = Self::default();
+        for slot in config.iter().filter(|slot| slot.slot < HOTBAR_SLOTS) {
+            hotbar.slots[slot.slot] = Some(slot.particle.clone());
+        }
+        hotbar
+    }
+
+    pub fn to_config(&self) -> Vec<HotbarSlotConfig> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, particle)| {
+                particle
+                    .clone()
+                    .map(|particle| HotbarSlotConfig { slot, particle })
+            })
+            .collect()
+    }
+
+    /// The slot `name` is assigned to, if any.
+    pub fn slot_of(&self, name: &str) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|particle| particle.as_deref() == Some(name))
+    }
+
+    /// Assign `name` to `slot`, moving it out of any other slot, and make `slot` active.
+    pub fn assign(&mut self, slot: usize, name: String) {
+        for particle in &mut self.slots {
+            if particle.as_deref() == Some(name.as_str()) {
+                *particle = None;
+            }
+        }
+        self.slots[slot] = Some(name);
+        self.active = slot;
+    }
+}
+
+/// Drag-and-drop payload carrying the name of a particle type, dropped onto hotbar slots.
+#[derive(Clone, Debug)]
+pub struct ParticleDragPayload(pub String);
+
+/// The label of the particle type named `name`.
+fn find_label<'a>(labels: &'a ParticleCategoryLabels, name: &str) -> Option<&'a ParticleLabel> {
+    labels
+        .categories()
+        .flat_map(|(_, labels)| labels.iter())
+        .find(|label| label.name == name)
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_persistent::Persistent;
+use leafwing_input_manager::{Actionlike, plugin::InputManagerPlugin, prelude::InputMap};
+use serde::{Deserialize, Serialize};
+
+use super::{HOTBAR_SLOTS, Hotbar};
+use crate::{
+    config::{InputButton, SettingsConfig, WorldConfig},
+    setup::SetupSystems,
+};
+
+pub(super) struct SetupPlugin;
+
+impl Plugin for SetupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<HotbarAction>::default())
+            .init_resource::<Hotbar>()
+            .add_systems(
+                Startup,
+                (load_world_hotbar, load_settings).in_set(SetupSystems::Ui),
+            );
+    }
+}
+
+#[derive(Actionlike, PartialEq, Eq, Hash, Clone, Copy, Debug, Reflect)]
+pub enum HotbarAction {
+    SelectSlot1,
+    SelectSlot2,
+    SelectSlot3,
+    SelectSlot4,
+    SelectSlot5,
+    SelectSlot6,
+    SelectSlot7,
+    SelectSlot8,
+    SelectSlot9,
+    SelectSlot10,
+}
+
+impl HotbarAction {
+    /// The action selecting each hotbar slot, in slot order.
+    pub const SELECT_SLOT: [HotbarAction; HOTBAR_SLOTS] = [
+        HotbarAction::SelectSlot1,
+        HotbarAction::SelectSlot2,
+        HotbarAction::SelectSlot3,
+        HotbarAction::SelectSlot4,
+        HotbarAction::SelectSlot5,
+        HotbarAction::SelectSlot6,
+        HotbarAction::SelectSlot7,
+        HotbarAction::SelectSlot8,
+        HotbarAction::SelectSlot9,
+        HotbarAction::SelectSlot10,
+    ];
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotbarKeyBindings {
+    /// The button selecting each hotbar slot, in slot order.
+    pub select_slot: [InputButton; HOTBAR_SLOTS],
+}
+
+impl Default for HotbarKeyBindings {
+    fn default() -> Self {
+        Self {
+            select_slot: [
+                KeyCode::Digit1.into(),
+                KeyCode::Digit2.into(),
+                KeyCode::Digit3.into(),
+                KeyCode::Digit4.into(),
+                KeyCode::Digit5.into(),
+                KeyCode::Digit6.into(),
+                KeyCode::Digit7.into(),
+                KeyCode::Digit8.into(),
+                KeyCode::Digit9.into(),
+                KeyCode::Digit0.into(),
+            ],
+        }
+    }
+}
+
+impl HotbarKeyBindings {
+    pub fn input_map(&self) -> InputMap<HotbarAction> {
+        let mut input_map = InputMap::default();
+        for (button, action) in self.select_slot.iter().zip(HotbarAction::SELECT_SLOT) {
+            button.insert_into_input_map(&mut input_map, action);
+        }
+        input_map
+    }
+}
+
+fn load_world_hotbar(mut commands: Commands, world_config: Res<Persistent<WorldConfig>>) {
+    commands.insert_resource(Hotbar::from_config(&world_config.get().hotbar));
+}
+
+fn load_settings(mut commands: Commands, settings_config: Res<Persistent<SettingsConfig>>) {
+    commands.spawn(settings_config.get().keys.ui.hotbar.input_map());
+}
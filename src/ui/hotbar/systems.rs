@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use leafwing_input_manager::{common_conditions::action_just_pressed, prelude::ActionState};
+
+use super::{Hotbar, HotbarAction, find_label};
+use crate::{
+    particles::{HoveredParticle, ParticleTypeRenamedEvent},
+    tools::painter::SelectedParticle,
+    ui::{ParticleCategoryLabels, QuickAction, UiState},
+};
+
+pub(super) struct SystemsPlugin;
+
+impl Plugin for SystemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                select_hotbar_slot_by_key,
+                assign_sampled_particle
+                    .run_if(action_just_pressed(QuickAction::SampleHoveredParticle)),
+            )
+                .run_if(in_state(UiState::Canvas)),
+        )
+        .add_observer(on_particle_type_renamed);
+    }
+}
+
+/// Slots are stored by name, so they follow the rename.
+fn on_particle_type_renamed(trigger: On<ParticleTypeRenamedEvent>, mut hotbar: ResMut<Hotbar>) {
+    for particle in &mut hotbar.slots {
+        if particle.as_deref() == Some(trigger.old.as_str()) {
+            *particle = Some(trigger.new.clone());
+        }
+    }
+}
+
+/// Make `slot` active and switch the brush to its particle type, if it has one.
+pub(super) fn select_hotbar_slot(
+    slot: usize,
+    hotbar: &mut Hotbar,
+    labels: &ParticleCategoryLabels,
+    selected_particle: &mut SelectedParticle,
+) {
+    hotbar.active = slot;
+    if let Some(label) = hotbar.slots[slot]
+        .as_deref()
+        .and_then(|name| find_label(labels, name))
+    {
+        selected_particle.0 = label.id;
+    }
+}
+
+fn select_hotbar_slot_by_key(
+    actions: Single<&ActionState<HotbarAction>>,
+    mut hotbar: ResMut<Hotbar>,
+    labels: Res<ParticleCategoryLabels>,
+    mut selected_particle: Single<&mut SelectedParticle>,
+) {
+    if let Some(slot) = HotbarAction::SELECT_SLOT
+        .iter()
+        .position(|action| actions.just_pressed(action))
+    {
+        select_hotbar_slot(slot, &mut hotbar, &labels, &mut selected_particle);
+    }
+}
+
+/// Put a sampled particle type on the hotbar: in its slot if it has one, otherwise in the first
+/// empty slot, otherwise in the active slot.
+fn assign_sampled_particle(
+    hovered_particle: Res<HoveredParticle>,
+    labels: Res<ParticleCategoryLabels>,
+    mut hotbar: ResMut<Hotbar>,
+) {
+    let Some(label) = hovered_particle.particle.and_then(|id| {
+        labels
+            .categories()
+            .flat_map(|(_, labels)| labels.iter())
+            .find(|label| label.id == id)
+    }) else {
+        return;
+    };
+    if let Some(slot) = hotbar.slot_of(&label.name) {
+        hotbar.active = slot;
+        return;
+    }
+    let slot = hotbar
+        .slots
+        .iter()
+        .position(Option::is_none)
+        .unwrap_or(hotbar.active);
+    hotbar.assign(slot, label.name.clone());
+}
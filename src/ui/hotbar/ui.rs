@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_falling_sand::prelude::{ColorProfile, ColorSource};
+
+use super::{HOTBAR_SLOTS, Hotbar, ParticleDragPayload, find_label, select_hotbar_slot};
+use crate::{
+    config::InputButton,
+    tools::painter::SelectedParticle,
+    ui::{ParticleCategoryLabels, ShowUi, UiKeyBindings, UiSystems},
+};
+
+const SLOT_SIZE: egui::Vec2 = egui::vec2(64.0, 56.0);
+const SWATCH_SIZE: egui::Vec2 = egui::vec2(40.0, 14.0);
+
+pub(super) struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            show.run_if(resource_exists::<ShowUi>)
+                .in_set(UiSystems::Hotbar),
+        );
+    }
+}
+
+fn show(
+    mut contexts: EguiContexts,
+    mut hotbar: ResMut<Hotbar>,
+    labels: Res<ParticleCategoryLabels>,
+    keys: Res<UiKeyBindings>,
+    color_profiles: Query<&ColorProfile>,
+    mut selected_particle: Single<&mut SelectedParticle>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let mut clicked: Option<usize> = None;
+    let mut cleared: Option<usize> = None;
+    let mut dropped: Option<(usize, String)> = None;
+
+    egui::Area::new(egui::Id::new("hotbar"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -10.0])
+        .show(ctx, |ui| {
+            egui::Frame::window(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for slot in 0..HOTBAR_SLOTS {
+                        let label = hotbar.slots[slot]
+                            .as_deref()
+                            .and_then(|name| find_label(&labels, name));
+                        let is_selected =
+                            label.is_some_and(|label| label.id == selected_particle.0);
+
+                        let (rect, response) =
+                            ui.allocate_exact_size(SLOT_SIZE, egui::Sense::click());
+                        let visuals = ui.style().interact_selectable(&response, is_selected);
+                        let stroke = if response
+                            .dnd_hover_payload::<ParticleDragPayload>()
+                            .is_some()
+                        {
+                            ui.visuals().selection.stroke
+                        } else {
+                            visuals.bg_stroke
+                        };
+                        let painter = ui.painter();
+                        painter.rect(
+                            rect,
+                            4.0,
+                            visuals.weak_bg_fill,
+                            stroke,
+                            egui::StrokeKind::Inside,
+                        );
+                        painter.text(
+                            rect.left_top() + egui::vec2(4.0, 2.0),
+                            egui::Align2::LEFT_TOP,
+                            key_label(&keys.hotbar.select_slot[slot]),
+                            egui::FontId::monospace(10.0),
+                            visuals.text_color(),
+                        );
+
+                        let response = match (label, hotbar.slots[slot].as_deref()) {
+                            (Some(label), _) => {
+                                let swatch = egui::Rect::from_center_size(
+                                    rect.center() - egui::vec2(0.0, 4.0),
+                                    SWATCH_SIZE,
+                                );
+                                paint_swatch(
+                                    painter,
+                                    swatch,
+                                    color_profiles.get(label.entity).ok(),
+                                );
+                                painter.text(
+                                    rect.center_bottom() - egui::vec2(0.0, 3.0),
+                                    egui::Align2::CENTER_BOTTOM,
+                                    truncate(&label.name),
+                                    egui::FontId::proportional(11.0),
+                                    visuals.text_color(),
+                                );
+                                response.on_hover_text(&label.name)
+                            }
+                            // Assigned in a previous session, but the particle type is gone.
+                            (None, Some(name)) => {
+                                painter.text(
+                                    rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    truncate(name),
+                                    egui::FontId::proportional(11.0),
+                                    ui.visuals().weak_text_color(),
+                                );
+                                response.on_hover_text(format!("'{name}' no longer exists"))
+                            }
+                            (None, None) => {
+                                response.on_hover_text("Drag a particle type here, or sample one")
+                            }
+                        };
+
+                        if response.clicked() {
+                            clicked = Some(slot);
+                        }
+                        if hotbar.slots[slot].is_some() {
+                            response.context_menu(|ui| {
+                                if ui.button("Clear slot").clicked() {
+                                    cleared = Some(slot);
+                                    ui.close();
+                                }
+                            });
+                        }
+                        if let Some(payload) = response.dnd_release_payload::<ParticleDragPayload>()
+                        {
+                            dropped = Some((slot, payload.0.clone()));
+                        }
+                    }
+                });
+            });
+        });
+
+    if let Some(slot) = cleared {
+        hotbar.slots[slot] = None;
+    }
+    if let Some((slot, name)) = dropped {
+        hotbar.assign(slot, name);
+        clicked = Some(slot);
+    }
+    if let Some(slot) = clicked {
+        select_hotbar_slot(slot, &mut hotbar, &labels, &mut selected_particle);
+    }
+
+    Ok(())
+}
+
+/// Paint the colors of `profile` side by side, or a neutral swatch for textures.
+fn paint_swatch(painter: &egui::Painter, rect: egui::Rect, profile: Option<&ColorProfile>) {
+    let colors: &[Color] = match profile.map(|profile| &profile.source) {
+        Some(ColorSource::Palette(palette)) => &palette.colors,
+        Some(ColorSource::Gradient(gradient)) => &gradient.colors,
+        Some(ColorSource::Texture(_)) | None => &[],
+    };
+    if colors.is_empty() {
+        painter.rect_filled(rect, 2.0, egui::Color32::GRAY);
+        return;
+    }
+    let width = rect.width() / colors.len() as f32;
+    for (i, color) in colors.iter().enumerate() {
+        let srgba = color.to_srgba();
+        let color32 = egui::Color32::from_rgba_unmultiplied(
+            (srgba.red * 255.0) as u8,
+            (srgba.green * 255.0) as u8,
+            (srgba.blue * 255.0) as u8,
+            (srgba.alpha * 255.0) as u8,
+        );
+        let min = rect.min + egui::vec2(width * i as f32, 0.0);
+        painter.rect_filled(
+            egui::Rect::from_min_size(min, egui::vec2(width, rect.height())),
+            0.0,
+            color32,
+        );
+    }
+}
+
+/// Shorten `name` to fit a slot.
+fn truncate(name: &str) -> String {
+    const MAX_CHARS: usize = 9;
+    if name.chars().count() <= MAX_CHARS {
+        name.to_string()
+    } else {
+        let mut short: String = name.chars().take(MAX_CHARS - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Short name of the button selecting a slot, drawn in the slot's corner.
+fn key_label(button: &InputButton) -> String {
+    match button {
+        InputButton::Key(key) => {
+            let name = format!("{key:?}");
+            name.strip_prefix("Digit")
+                .or_else(|| name.strip_prefix("Key"))
+                .unwrap_or(&name)
+                .to_string()
+        }
+        InputButton::Mouse(_) => button.to_string(),
+    }
+}
@@ -1,6 +1,7 @@
 mod action_panel;
 mod console;
 mod helpers;
+mod hotbar;
 mod popups;
 mod quick_actions;
 mod setup;
@@ -14,6 +15,7 @@ pub use action_panel::*;
 use bevy_egui::{EguiPlugin, EguiPrimaryContextPass};
 pub use console::*;
 pub use helpers::*;
+pub use hotbar::*;
 pub use popups::*;
 pub use quick_actions::*;
 pub use setup::*;
@@ -33,6 +35,7 @@ impl Plugin for UiPlugin {
             ConsolePlugin,
             UiStatePlugin,
             SignalsPlugin,
+            HotbarPlugin,
         ))
         .configure_sets(
             EguiPrimaryContextPass,
@@ -44,6 +47,7 @@ impl Plugin for UiPlugin {
                 UiSystems::ParticleEditor,
                 UiSystems::Settings,
                 UiSystems::CursorInfoOverlay,
+                UiSystems::Hotbar,
             )
                 .chain(),
         )
@@ -62,4 +66,5 @@ pub enum UiSystems {
     Settings,
     ToolOptions,
     CursorInfoOverlay,
+    Hotbar,
 }
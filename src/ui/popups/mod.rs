@@ -1,5 +1,6 @@
 mod cursor_overlay;
 mod particle_editor;
+mod reaction_graph;
 mod settings;
 mod states;
 mod tool_options;
@@ -8,6 +9,7 @@ use bevy::prelude::*;
 
 pub use cursor_overlay::*;
 pub use particle_editor::*;
+pub use reaction_graph::*;
 pub use settings::*;
 pub use states::*;
 pub use tool_options::*;
@@ -18,6 +20,7 @@ impl Plugin for PopupsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             ParticleEditorPlugin,
+            ReactionGraphPlugin,
             SettingsPlugin,
             ToolOptionsPlugin,
             CursorOverlayPlugin,
@@ -1,11 +1,19 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeSet, VecDeque},
+    path::PathBuf,
+    time::Duration,
+};
 
 use bevy::{ecs::query::QueryData, platform::collections::HashMap, prelude::*};
 use bevy_egui::EguiPrimaryContextPass;
 use bevy_falling_sand::prelude::*;
 
 use crate::chunk_effects::{BurnEffect, GasEffect, GlowEffect, LiquidEffect};
-use crate::particles::{ParticleCategory, ParticleName};
+use crate::particles::{
+    ImportResolution, ImportedParticleType, ParticleCategory, ParticleName, ParticleTemplate,
+    ParticleTypeRenamedEvent, movement_class,
+};
+use crate::tools::painter::SelectedParticleType;
 use crate::ui::UiSystems;
 
 pub struct ResourcesPlugin;
@@ -13,7 +21,12 @@ pub struct ResourcesPlugin;
 impl Plugin for ResourcesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<NameDraft>()
+            .init_resource::<PendingParticleDeletion>()
+            .init_resource::<ParticleImportDraft>()
             .init_resource::<ParticleCategoryLabels>()
+            .init_resource::<FavoriteParticles>()
+            .init_resource::<RecentParticles>()
+            .init_resource::<ParticleListFilter>()
             .add_systems(
                 EguiPrimaryContextPass,
                 (
@@ -29,8 +42,13 @@ impl Plugin for ResourcesPlugin {
             )
             .add_systems(
                 Update,
-                refresh_particle_labels.run_if(condition_particle_movement_changed),
-            );
+                (
+                    refresh_particle_labels.run_if(condition_particle_labels_changed),
+                    track_recent_particles,
+                    prune_recent_particles.run_if(resource_changed::<ParticleTypeRegistry>),
+                ),
+            )
+            .add_observer(on_particle_type_renamed);
     }
 }
 
@@ -94,11 +112,103 @@ pub struct ParticleLabel {
     pub entity: Entity,
     pub id: ParticleTypeId,
     pub name: String,
+    /// Broad movement class, as given by [`movement_class`].
+    pub movement: &'static str,
+    /// Names of the chunk effects the type has.
+    pub effects: Vec<&'static str>,
+}
+
+/// Number of recently used particle types listed in the editor.
+const RECENT_PARTICLES_LEN: usize = 8;
+
+/// Names of the particle types pinned to the top of the editor list, persisted in the settings.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct FavoriteParticles(pub BTreeSet<String>);
+
+/// Particle types recently used by the brush, most recent first.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RecentParticles(pub VecDeque<Entity>);
+
+/// Search text and filters narrowing the editor list.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ParticleListFilter {
+    pub search: String,
+    pub movement: Option<&'static str>,
+    pub effect: Option<&'static str>,
+}
+
+impl ParticleListFilter {
+    pub fn is_active(&self) -> bool {
+        !self.search.is_empty() || self.movement.is_some() || self.effect.is_some()
+    }
+
+    /// How well `label` matches, higher being better, or `None` if it is filtered out.
+    pub fn score(&self, label: &ParticleLabel) -> Option<i32> {
+        if self
+            .movement
+            .is_some_and(|movement| movement != label.movement)
+            || self
+                .effect
+                .is_some_and(|effect| !label.effects.contains(&effect))
+        {
+            return None;
+        }
+        fuzzy_score(&self.search, &label.name)
+    }
+}
+
+/// Score `text` against `query` when every character of `query` appears in `text` in order,
+/// ignoring case. Consecutive matches and matches at word starts score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.chars().flat_map(char::to_lowercase) {
+        if c.is_whitespace() {
+            continue;
+        }
+        let found = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 4;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
 }
 
 #[derive(Resource, Copy, Clone, PartialEq, Debug, Reflect)]
 pub struct SelectedParticle(pub Entity);
 
+/// A particle type the user asked to remove, awaiting confirmation in the editor.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct PendingParticleDeletion {
+    pub entity: Option<Entity>,
+    /// Type that references to the removed type and its live particles are moved to.
+    pub replacement: Option<ParticleTypeId>,
+}
+
+/// The particle set file being imported from and the choices made for each of its types.
+#[derive(Resource, Default)]
+pub struct ParticleImportDraft {
+    pub path: Option<PathBuf>,
+    pub types: Vec<ImportedParticleType>,
+    pub rows: Vec<ParticleImportRow>,
+    pub error: Option<String>,
+}
+
+pub struct ParticleImportRow {
+    pub selected: bool,
+    /// Whether a particle type with the same name already exists.
+    pub collides: bool,
+    pub resolution: ImportResolution,
+}
+
 #[derive(Resource, Default, Clone, Debug, Reflect)]
 pub struct EditorState {
     pub map: HashMap<Entity, ParticleData>,
@@ -213,6 +323,7 @@ pub(crate) struct CoreQuery {
     pub chance_lifetime: Option<&'static mut ChanceLifetime>,
     pub timed_mutation: Option<&'static mut TimedMutation>,
     pub chance_mutation: Option<&'static mut ChanceMutation>,
+    pub template: Option<&'static ParticleTemplate>,
 }
 
 #[derive(QueryData)]
@@ -390,10 +501,17 @@ fn refresh_particle_labels(
         &ParticleType,
         Option<&ParticleName>,
         Option<&ParticleCategory>,
+        Option<&Movement>,
+        (
+            Has<LiquidEffect>,
+            Has<GasEffect>,
+            Has<GlowEffect>,
+            Has<BurnEffect>,
+        ),
     )>,
 ) {
     let mut labels = ParticleCategoryLabels::default();
-    for (entity, ptype, name, category) in &particles {
+    for (entity, ptype, name, category, movement, (liquid, gas, glow, burn)) in &particles {
         let cat = category.map(|c| c.0.as_str()).unwrap_or("Other");
         labels.push(
             cat,
@@ -403,6 +521,16 @@ fn refresh_particle_labels(
                 name: name
                     .map(|n| n.0.clone())
                     .unwrap_or_else(|| format!("Particle {}", ptype.id().get())),
+                movement: movement_class(movement),
+                effects: [
+                    (liquid, "liquid"),
+                    (gas, "gas"),
+                    (glow, "glow"),
+                    (burn, "burn"),
+                ]
+                .into_iter()
+                .filter_map(|(enabled, effect)| enabled.then_some(effect))
+                .collect(),
             },
         );
     }
@@ -413,12 +541,68 @@ fn refresh_particle_labels(
     commands.insert_resource(labels);
 }
 
-fn condition_particle_movement_changed(
+fn condition_particle_labels_changed(
     movement: Query<Entity, Changed<Movement>>,
     categories: Query<Entity, Changed<ParticleCategory>>,
     names: Query<Entity, Changed<ParticleName>>,
+    effects: Query<
+        Entity,
+        Or<(
+            Changed<LiquidEffect>,
+            Changed<GasEffect>,
+            Changed<GlowEffect>,
+            Changed<BurnEffect>,
+        )>,
+    >,
+    mut removed_movement: RemovedComponents<Movement>,
+    mut removed_liquid: RemovedComponents<LiquidEffect>,
+    mut removed_gas: RemovedComponents<GasEffect>,
+    mut removed_glow: RemovedComponents<GlowEffect>,
+    mut removed_burn: RemovedComponents<BurnEffect>,
 ) -> bool {
-    !movement.is_empty() || !categories.is_empty() || !names.is_empty()
+    let removed = removed_movement.read().count()
+        + removed_liquid.read().count()
+        + removed_gas.read().count()
+        + removed_glow.read().count()
+        + removed_burn.read().count();
+    !movement.is_empty()
+        || !categories.is_empty()
+        || !names.is_empty()
+        || !effects.is_empty()
+        || removed > 0
+}
+
+/// Move the brush's particle type to the front of the recently used types, however it was
+/// chosen.
+fn track_recent_particles(
+    brush: Query<&SelectedParticleType, Changed<SelectedParticleType>>,
+    mut recent_particles: ResMut<RecentParticles>,
+) {
+    for selected in &brush {
+        let recent = &mut recent_particles.0;
+        recent.retain(|entity| *entity != selected.0);
+        recent.push_front(selected.0);
+        recent.truncate(RECENT_PARTICLES_LEN);
+    }
+}
+
+fn prune_recent_particles(
+    particle_types: Query<(), With<ParticleType>>,
+    mut recent_particles: ResMut<RecentParticles>,
+) {
+    recent_particles
+        .0
+        .retain(|entity| particle_types.contains(*entity));
+}
+
+/// Favorites are stored by name, so they follow the rename.
+fn on_particle_type_renamed(
+    trigger: On<ParticleTypeRenamedEvent>,
+    mut favorites: ResMut<FavoriteParticles>,
+) {
+    if favorites.0.remove(&trigger.old) {
+        favorites.0.insert(trigger.new.clone());
+    }
 }
 
 /// Buffered name for the currently-selected particle.
@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 use bevy_falling_sand::prelude::*;
+use bevy_persistent::Persistent;
 
 use crate::{
+    config::SettingsConfig,
     particles::DefaultParticleIds,
+    setup::SetupSystems,
     ui::{
-        EditorState, ParticleCategoryLabels, ParticleTypesSavedMessageConfiguration,
-        SelectedParticle,
+        EditorState, FavoriteParticles, ParticleCategoryLabels,
+        ParticleTypesSavedMessageConfiguration, SelectedParticle,
     },
 };
 
@@ -16,6 +19,7 @@ impl Plugin for SetupPlugin {
         app.init_resource::<ParticleTypesSavedMessageConfiguration>()
             .init_resource::<ParticleCategoryLabels>()
             .init_resource::<EditorState>()
+            .add_systems(Startup, load_settings.in_set(SetupSystems::Ui))
             .add_systems(
                 Update,
                 set_initial_selected_particle
@@ -25,6 +29,12 @@ impl Plugin for SetupPlugin {
     }
 }
 
+fn load_settings(mut commands: Commands, settings_config: Res<Persistent<SettingsConfig>>) {
+    commands.insert_resource(FavoriteParticles(
+        settings_config.get().particle_editor.favorites.clone(),
+    ));
+}
+
 fn set_initial_selected_particle(
     mut commands: Commands,
     registry: Res<ParticleTypeRegistry>,
@@ -10,6 +10,7 @@ impl Plugin for StatesPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<PopupState<ParticleEditorWindowState>>()
             .init_state::<PopupState<LoadParticlesWindowState>>()
+            .init_state::<PopupState<ImportParticlesWindowState>>()
             .init_state::<SynchronizeWithBrush>()
             .add_systems(
                 Update,
@@ -36,6 +37,13 @@ pub enum LoadParticlesWindowState {
     Open,
 }
 
+#[derive(Reflect, Default, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ImportParticlesWindowState {
+    #[default]
+    Closed,
+    Open,
+}
+
 #[derive(States, Reflect, Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum SynchronizeWithBrush {
     #[default]
@@ -1,4 +1,9 @@
-use bevy::{ecs::system::SystemParam, prelude::*, reflect::enums::Enum};
+use bevy::{
+    ecs::system::SystemParam,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::enums::Enum,
+};
 use bevy_egui::{
     EguiContexts, EguiPrimaryContextPass,
     egui::{self},
@@ -9,10 +14,22 @@ use std::{fs, path::Path, time::Duration};
 use crate::{
     chunk_effects::{BurnEffect, GasEffect, GlowEffect, LiquidEffect},
     config::{ActiveWorldPath, ParticleTypesFile},
-    particles::{ParticleCategory, ParticleName},
+    particles::{
+        ImportResolution, InheritableComponents, ParticleCategory, ParticleDiagnostics,
+        ParticleName, ParticleReferences, ParticleTemplate, ParticleTypesHotReload, Severity,
+        delete_particle_type, existing_particle_type, import_particle_types, particle_set_files,
+        read_particle_set, refresh_particle_diagnostics, rename_particle_type,
+        set_particle_template, set_template_override, spawn_particle_type,
+        spawn_particle_type_from, template_ancestors, unique_particle_name,
+    },
     ui::*,
 };
 
+/// Movement classes the particle list can be filtered by.
+const PARTICLE_LIST_MOVEMENTS: &[&str] = &["Static", "Solid", "Liquid", "Gas"];
+/// Chunk effects the particle list can be filtered by.
+const PARTICLE_LIST_EFFECTS: &[&str] = &["liquid", "gas", "glow", "burn"];
+
 pub(super) struct UiPlugin;
 
 impl Plugin for UiPlugin {
@@ -26,6 +43,9 @@ impl Plugin for UiPlugin {
                 show_load_particle_types_popup
                     .run_if(resource_exists::<ShowUi>)
                     .run_if(in_state(PopupState::<LoadParticlesWindowState>::Open)),
+                show_import_particle_types_popup
+                    .run_if(resource_exists::<ShowUi>)
+                    .run_if(in_state(PopupState::<ImportParticlesWindowState>::Open)),
             ),
         );
     }
@@ -40,6 +60,13 @@ pub struct ParticleEditorParams<'w, 's> {
     pub particle_registry: Res<'w, ParticleTypeRegistry>,
     pub editor_state: ResMut<'w, EditorState>,
     pub name_draft: ResMut<'w, NameDraft>,
+    pub pending_deletion: ResMut<'w, PendingParticleDeletion>,
+    pub diagnostics: Res<'w, ParticleDiagnostics>,
+    pub hot_reload: ResMut<'w, ParticleTypesHotReload>,
+    pub inheritable_components: Res<'w, InheritableComponents>,
+    pub favorites: ResMut<'w, FavoriteParticles>,
+    pub recent_particles: Res<'w, RecentParticles>,
+    pub list_filter: ResMut<'w, ParticleListFilter>,
     pub msgw_reset_particle_type: MessageWriter<'w, SyncParticleTypeChildrenSignal>,
     pub particle_types_file: ResMut<'w, ParticleTypesFile>,
     pub msgw_save_particle: MessageWriter<'w, PersistParticleTypesSignal>,
@@ -48,6 +75,10 @@ pub struct ParticleEditorParams<'w, 's> {
     pub current_load_particles_window_state: Res<'w, State<PopupState<LoadParticlesWindowState>>>,
     pub next_load_particles_window_state:
         ResMut<'w, NextState<PopupState<LoadParticlesWindowState>>>,
+    pub current_import_particles_window_state:
+        Res<'w, State<PopupState<ImportParticlesWindowState>>>,
+    pub next_import_particles_window_state:
+        ResMut<'w, NextState<PopupState<ImportParticlesWindowState>>>,
 }
 
 fn show(
@@ -122,18 +153,9 @@ fn show_top_options(
                     particle_query,
                 );
             } else {
-                let entity = editor_params
-                    .commands
-                    .spawn((
-                        ParticleType::new(),
-                        ParticleName(unique_new_particle_name(
-                            &editor_params.particle_registry,
-                            particle_query,
-                        )),
-                        ParticleCategory(String::from("Other")),
-                        ColorProfile::default(),
-                    ))
-                    .id();
+                let name =
+                    unique_new_particle_name(&editor_params.particle_registry, particle_query);
+                let entity = spawn_particle_type(&mut editor_params.commands, name);
                 editor_params
                     .commands
                     .insert_resource(SelectedParticle(entity));
@@ -147,7 +169,10 @@ fn show_top_options(
             )
             .clicked();
         if remove_particle_clicked && let Some(entity) = selected_entity {
-            editor_params.commands.entity(entity).despawn();
+            *editor_params.pending_deletion = PendingParticleDeletion {
+                entity: Some(entity),
+                replacement: None,
+            };
         }
 
         if let Some(entity) = selected_entity
@@ -175,6 +200,117 @@ fn show_top_options(
             );
         }
     });
+
+    show_pending_deletion(ui, editor_params, particle_query);
+}
+
+/// Confirm removal of a particle type, listing the types that refer to it and letting the user
+/// pick a replacement for those references and for its live particles.
+fn show_pending_deletion(
+    ui: &mut egui::Ui,
+    editor_params: &mut ParticleEditorParams,
+    particle_query: &mut Query<ParticleDataQuery>,
+) {
+    let Some(entity) = editor_params.pending_deletion.entity else {
+        return;
+    };
+    let Ok(data) = particle_query.get(entity) else {
+        *editor_params.pending_deletion = PendingParticleDeletion::default();
+        return;
+    };
+    let id = data.core.particle_type.id();
+    let name = data
+        .core
+        .name
+        .as_ref()
+        .map(|name| name.0.clone())
+        .unwrap_or_else(|| format!("Particle {}", id.get()));
+
+    let mut references = Vec::new();
+    for other in editor_params.particle_registry.entities() {
+        if *other == entity {
+            continue;
+        }
+        let Ok(other_data) = particle_query.get(*other) else {
+            continue;
+        };
+        let components = ParticleReferences {
+            chance_mutation: other_data.core.chance_mutation,
+            timed_mutation: other_data.core.timed_mutation,
+            contact_reaction: other_data.reactions.contact_reaction,
+            burns: other_data.reactions.burns,
+            template: other_data.core.template,
+        };
+        let other_name = other_data
+            .core
+            .name
+            .as_ref()
+            .map(|name| name.0.clone())
+            .unwrap_or_else(|| format!("Particle {}", other_data.core.particle_type.id().get()));
+        references.extend(
+            components
+                .to(id)
+                .into_iter()
+                .map(|kind| format!("{other_name}: {kind}")),
+        );
+    }
+    references.sort();
+
+    let mut options: Vec<_> = particle_options(&editor_params.particle_registry, particle_query)
+        .into_iter()
+        .filter(|(_, option)| *option != id)
+        .collect();
+    options.sort_by(|a, b| a.0.cmp(&b.0));
+
+    ui.group(|ui| {
+        ui.label(format!("Remove '{name}'?"));
+        if references.is_empty() {
+            ui.weak("No other particle types refer to it.");
+        } else {
+            ui.label(format!("Referenced {} time(s):", references.len()));
+            for reference in &references {
+                ui.label(format!("    {reference}"));
+            }
+        }
+
+        let pending = &mut editor_params.pending_deletion;
+        ui.horizontal(|ui| {
+            ui.label("Replace with");
+            let selected_text = pending
+                .replacement
+                .and_then(|replacement| {
+                    options
+                        .iter()
+                        .find_map(|(name, id)| (*id == replacement).then(|| name.clone()))
+                })
+                .unwrap_or_else(|| "None".to_string());
+            egui::ComboBox::from_id_salt("pending_deletion_replacement")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut pending.replacement, None, "None");
+                    for (name, id) in &options {
+                        ui.selectable_value(&mut pending.replacement, Some(*id), name);
+                    }
+                });
+        });
+        ui.weak(if pending.replacement.is_some() {
+            "References are rewritten and live particles are converted to the replacement."
+        } else {
+            "References are removed and live particles are despawned."
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("Remove").clicked() {
+                let replacement = pending.replacement;
+                editor_params.commands.queue(move |world: &mut World| {
+                    delete_particle_type(world, entity, replacement);
+                });
+                **pending = PendingParticleDeletion::default();
+            } else if ui.button("Cancel").clicked() {
+                **pending = PendingParticleDeletion::default();
+            }
+        });
+    });
 }
 
 /// Commit the buffered name in `NameDraft` to the editor-owned [`ParticleName`] component.
@@ -202,8 +338,7 @@ fn save_particle_name(
 
     editor_params
         .commands
-        .entity(entity)
-        .insert(ParticleName(new_name.clone()));
+        .queue(move |world: &mut World| rename_particle_type(world, entity, new_name));
 
     if synchronize_brush_state.get() == &SynchronizeWithBrush::Enabled
         && let Ok(data) = particle_query.get(entity)
@@ -237,24 +372,15 @@ fn spawn_new_particle_from(
     particle_query: &mut Query<ParticleDataQuery>,
 ) {
     let new_name = unique_new_particle_name(&editor_params.particle_registry, particle_query);
-    let particle_type = ParticleType::new();
-
-    let new_entity = editor_params
-        .commands
-        .entity(source_entity)
-        .clone_and_spawn_with_opt_out(|builder| {
-            builder.deny::<ParticleType>();
-        })
-        .insert(particle_type.clone())
-        .insert(ParticleName(new_name))
-        .id();
+    let (new_entity, new_id) =
+        spawn_particle_type_from(&mut editor_params.commands, source_entity, new_name);
 
     editor_params
         .commands
         .insert_resource(SelectedParticle(new_entity));
 
     if synchronize_brush_state.get() == &SynchronizeWithBrush::Enabled {
-        editor_params.brush.0 = particle_type.id();
+        editor_params.brush.0 = new_id;
     }
 }
 
@@ -314,6 +440,17 @@ fn show_editor(
                 _ => unreachable!(),
             }
         }
+        if ui.button("Import").clicked() {
+            editor_params.next_import_particles_window_state.set(
+                editor_params
+                    .current_import_particles_window_state
+                    .get()
+                    .get_next(),
+            );
+        }
+        if ui.button("Graph").clicked() {
+            editor_params.commands.trigger(UiToggleReactionGraphEvent);
+        }
         let full_name = editor_params
             .particle_types_file
             .0
@@ -350,6 +487,105 @@ fn show_editor(
             );
         }
     });
+    if let Some(error) = &editor_params.hot_reload.error {
+        ui.label(
+            egui::RichText::new(format!("Particle set not reloaded: {error}"))
+                .color(egui::Color32::from_rgb(230, 80, 80)),
+        );
+    }
+    if !editor_params.hot_reload.missing.is_empty() {
+        show_missing_particle_types(
+            ui,
+            &mut editor_params.hot_reload,
+            &mut editor_params.commands,
+        );
+    }
+    show_diagnostics(ui, &editor_params.diagnostics, &mut editor_params.commands);
+}
+
+/// Ask whether to delete the particle types that a reload found missing from the file.
+fn show_missing_particle_types(
+    ui: &mut egui::Ui,
+    hot_reload: &mut ParticleTypesHotReload,
+    commands: &mut Commands,
+) {
+    ui.label(
+        egui::RichText::new(format!(
+            "No longer in the particle set file: {}",
+            hot_reload.missing.join(", ")
+        ))
+        .color(egui::Color32::YELLOW),
+    );
+    ui.horizontal(|ui| {
+        if ui
+            .button("Delete them")
+            .on_hover_text("Delete these particle types and despawn their particles")
+            .clicked()
+        {
+            let names = std::mem::take(&mut hot_reload.missing);
+            commands.queue(move |world: &mut World| {
+                for name in names {
+                    if let Some((entity, _)) = existing_particle_type(world, &name) {
+                        delete_particle_type(world, entity, None);
+                    }
+                }
+            });
+        }
+        if ui
+            .button("Keep them")
+            .on_hover_text("They are written back to the file on the next save")
+            .clicked()
+        {
+            hot_reload.missing.clear();
+        }
+    });
+}
+
+/// List the problems found by the last validation, selecting a particle type when clicked.
+fn show_diagnostics(ui: &mut egui::Ui, diagnostics: &ParticleDiagnostics, commands: &mut Commands) {
+    let heading = format!(
+        "Diagnostics ({} errors, {} warnings)",
+        diagnostics.count(Severity::Error),
+        diagnostics.count(Severity::Warning)
+    );
+    egui::CollapsingHeader::new(heading)
+        .id_salt("particle_diagnostics")
+        .show(ui, |ui| {
+            if ui.button("Validate").clicked() {
+                commands.queue(|world: &mut World| {
+                    refresh_particle_diagnostics(world);
+                });
+            }
+            if diagnostics.diagnostics.is_empty() {
+                ui.label("No problems found");
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .id_salt("particle_diagnostics_scroll")
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for diagnostic in &diagnostics.diagnostics {
+                        let color = match diagnostic.severity {
+                            Severity::Warning => egui::Color32::YELLOW,
+                            Severity::Error => egui::Color32::from_rgb(230, 80, 80),
+                        };
+                        let text = egui::RichText::new(format!(
+                            "{}: {}",
+                            diagnostic.particle, diagnostic.message
+                        ))
+                        .color(color);
+                        if ui.selectable_label(false, text).clicked() {
+                            // Diagnostics can outlive the particle types they were found on.
+                            let entity = diagnostic.entity;
+                            commands.queue(move |world: &mut World| {
+                                if world.get_entity(entity).is_ok() {
+                                    world.insert_resource(SelectedParticle(entity));
+                                }
+                            });
+                        }
+                    }
+                });
+        });
 }
 
 fn show_load_particle_types_popup(
@@ -412,36 +648,300 @@ fn show_load_particle_types_popup(
     Ok(())
 }
 
+fn show_import_particle_types_popup(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    active_world_path: Res<ActiveWorldPath>,
+    app_type_registry: Res<AppTypeRegistry>,
+    mut draft: ResMut<ParticleImportDraft>,
+    mut next_import_particles_window_state: ResMut<
+        NextState<PopupState<ImportParticlesWindowState>>,
+    >,
+    particle_names: Query<&ParticleName>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+    let taken: HashSet<String> = particle_names
+        .iter()
+        .map(|name| name.0.to_lowercase())
+        .collect();
+
+    egui::Window::new("Import Particle Types")
+        .constrain_to(ctx.content_rect())
+        .show(ctx, |ui| {
+            let files = particle_set_files(&active_world_path.0);
+            let selected_label = draft
+                .path
+                .as_ref()
+                .and_then(|path| files.iter().find(|(_, file)| file == path))
+                .map_or("Choose a particle set", |(label, _)| label.as_str());
+            let mut chosen = None;
+            egui::ComboBox::from_id_salt("import_particle_set_file")
+                .selected_text(selected_label)
+                .show_ui(ui, |ui| {
+                    for (label, path) in &files {
+                        if ui
+                            .selectable_label(draft.path.as_ref() == Some(path), label)
+                            .clicked()
+                        {
+                            chosen = Some(path.clone());
+                        }
+                    }
+                });
+            if let Some(path) = chosen {
+                *draft = match read_particle_set(&path, &app_type_registry.read()) {
+                    Ok(types) => {
+                        let rows = types
+                            .iter()
+                            .map(|imported| {
+                                let collides = taken.contains(&imported.name.to_lowercase());
+                                ParticleImportRow {
+                                    selected: !collides,
+                                    collides,
+                                    resolution: ImportResolution::Skip,
+                                }
+                            })
+                            .collect();
+                        ParticleImportDraft {
+                            path: Some(path),
+                            types,
+                            rows,
+                            error: None,
+                        }
+                    }
+                    Err(error) => ParticleImportDraft {
+                        path: Some(path),
+                        error: Some(error),
+                        ..default()
+                    },
+                };
+            }
+
+            if let Some(error) = &draft.error {
+                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), error);
+            }
+            ui.separator();
+
+            let ParticleImportDraft { types, rows, .. } = &mut *draft;
+            // Renamed types must avoid the names of every type in the file and every other
+            // rename, not just the existing types.
+            let reserved: HashSet<String> = taken
+                .iter()
+                .cloned()
+                .chain(types.iter().map(|imported| imported.name.to_lowercase()))
+                .chain(rows.iter().filter_map(|row| match &row.resolution {
+                    ImportResolution::Rename(name) => Some(name.to_lowercase()),
+                    _ => None,
+                }))
+                .collect();
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("import_particle_types_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (index, (imported, row)) in types.iter().zip(rows).enumerate() {
+                                ui.checkbox(&mut row.selected, &imported.name);
+                                ui.label(&imported.category);
+                                if row.collides {
+                                    show_import_resolution(ui, index, imported.name.as_str(), row, &reserved);
+                                } else {
+                                    ui.label("New");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                let selected = draft.rows.iter().filter(|row| row.selected).count();
+                if ui
+                    .add_enabled(selected > 0, egui::Button::new(format!("Import {selected}")))
+                    .clicked()
+                {
+                    let selection: Vec<(usize, ImportResolution)> = draft
+                        .rows
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, row)| row.selected)
+                        .map(|(index, row)| (index, row.resolution.clone()))
+                        .collect();
+                    let types = std::mem::take(&mut draft.types);
+                    *draft = ParticleImportDraft::default();
+                    commands.queue(move |world: &mut World| {
+                        match import_particle_types(world, &types, &selection) {
+                            Ok(import) => info!(
+                                "Imported {}, overwrote {} and skipped {} particle type(s); removed {} reference(s) to types that aren't present",
+                                import.imported.len(),
+                                import.overwritten.len(),
+                                import.skipped.len(),
+                                import.dropped_references
+                            ),
+                            Err(error) => error!("Failed to import particle types: {error}"),
+                        }
+                    });
+                    next_import_particles_window_state
+                        .set(PopupState::<ImportParticlesWindowState>::Closed);
+                }
+                if ui.button("Cancel").clicked() {
+                    *draft = ParticleImportDraft::default();
+                    next_import_particles_window_state
+                        .set(PopupState::<ImportParticlesWindowState>::Closed);
+                }
+            });
+        });
+
+    Ok(())
+}
+
+/// Choose what happens to an imported particle type whose name is already taken.
+fn show_import_resolution(
+    ui: &mut egui::Ui,
+    index: usize,
+    name: &str,
+    row: &mut ParticleImportRow,
+    reserved: &HashSet<String>,
+) {
+    ui.horizontal(|ui| {
+        let is_rename = matches!(row.resolution, ImportResolution::Rename(_));
+        let selected_text = match row.resolution {
+            ImportResolution::Skip => "Skip",
+            ImportResolution::Rename(_) => "Rename",
+            ImportResolution::Overwrite => "Overwrite",
+        };
+        egui::ComboBox::from_id_salt(("import_resolution", index))
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                if ui
+                    .selectable_label(row.resolution == ImportResolution::Skip, "Skip")
+                    .clicked()
+                {
+                    row.resolution = ImportResolution::Skip;
+                }
+                if ui.selectable_label(is_rename, "Rename").clicked() && !is_rename {
+                    row.resolution = ImportResolution::Rename(unique_particle_name(reserved, name));
+                }
+                if ui
+                    .selectable_label(row.resolution == ImportResolution::Overwrite, "Overwrite")
+                    .clicked()
+                {
+                    row.resolution = ImportResolution::Overwrite;
+                }
+            });
+        if let ImportResolution::Rename(name) = &mut row.resolution {
+            ui.text_edit_singleline(name);
+        }
+    });
+}
+
 fn show_category_labels(
     ui: &mut egui::Ui,
     editor_params: &mut ParticleEditorParams,
     synchronize_brush_selection: &Res<State<SynchronizeWithBrush>>,
     particle_query: &mut Query<ParticleDataQuery>,
 ) {
-    let categories: Vec<_> = editor_params
+    show_particle_list_filter(ui, &mut editor_params.list_filter);
+
+    let filter = editor_params.list_filter.clone();
+    let is_filtered = filter.is_active();
+    let all_labels: Vec<&ParticleLabel> = editor_params
+        .category_labels
+        .categories()
+        .flat_map(|(_, items)| items.iter())
+        .collect();
+    let favorites = matching_labels(
+        &filter,
+        all_labels
+            .iter()
+            .copied()
+            .filter(|label| editor_params.favorites.0.contains(&label.name)),
+    );
+    let recent = matching_labels(
+        &filter,
+        editor_params
+            .recent_particles
+            .0
+            .iter()
+            .filter_map(|entity| {
+                all_labels
+                    .iter()
+                    .copied()
+                    .find(|label| label.entity == *entity)
+            }),
+    );
+    let categories: Vec<(String, Vec<ParticleLabel>)> = editor_params
         .category_labels
         .categories()
-        .map(|(h, items)| (h.to_string(), items.clone()))
+        .map(|(heading, items)| (heading.to_string(), matching_labels(&filter, items.iter())))
+        .filter(|(_, items)| !items.is_empty())
         .collect();
 
     let mut selected_label: Option<ParticleLabel> = None;
+    let mut toggled_favorite: Option<String> = None;
 
     egui::ScrollArea::vertical()
         .id_salt("category_labels")
         .show(ui, |ui| {
-            for (heading, items) in &categories {
-                egui::CollapsingHeader::new(heading)
-                    .default_open(false)
+            let mut show_group = |ui: &mut egui::Ui,
+                                  heading: &str,
+                                  items: &[ParticleLabel],
+                                  default_open: bool| {
+                egui::CollapsingHeader::new(format!("{heading} ({})", items.len()))
+                    .id_salt(("particle_list_group", heading))
+                    .default_open(default_open)
+                    .open(is_filtered.then_some(true))
                     .show(ui, |ui| {
                         for label in items {
-                            if ui.button(&label.name).clicked() {
-                                selected_label = Some(label.clone());
-                            }
+                            ui.horizontal(|ui| {
+                                let is_favorite = editor_params.favorites.0.contains(&label.name);
+                                let star = if is_favorite { "★" } else { "☆" };
+                                if ui
+                                    .small_button(star)
+                                    .on_hover_text(if is_favorite {
+                                        "Unpin from favorites"
+                                    } else {
+                                        "Pin to favorites"
+                                    })
+                                    .clicked()
+                                {
+                                    toggled_favorite = Some(label.name.clone());
+                                }
+                                // Dragging a particle type onto a hotbar slot assigns it.
+                                let response = ui.add(
+                                    egui::Button::new(&label.name)
+                                        .sense(egui::Sense::click_and_drag()),
+                                );
+                                response
+                                    .dnd_set_drag_payload(ParticleDragPayload(label.name.clone()));
+                                if response.clicked() {
+                                    selected_label = Some(label.clone());
+                                }
+                            });
                         }
                     });
+            };
+
+            if !favorites.is_empty() {
+                show_group(ui, "Favorites", &favorites, true);
+            }
+            if !recent.is_empty() && !is_filtered {
+                show_group(ui, "Recently Used", &recent, false);
+            }
+            for (heading, items) in &categories {
+                show_group(ui, heading, items, false);
+            }
+            if is_filtered && categories.is_empty() {
+                ui.weak("No particle types match.");
             }
         });
 
+    if let Some(name) = toggled_favorite
+        && !editor_params.favorites.0.remove(&name)
+    {
+        editor_params.favorites.0.insert(name);
+    }
+
     if let Some(label) = selected_label {
         let entity = label.entity;
         editor_params
@@ -455,6 +955,58 @@ fn show_category_labels(
     }
 }
 
+/// The labels that pass `filter`, best matches first while it is active.
+fn matching_labels<'a>(
+    filter: &ParticleListFilter,
+    labels: impl Iterator<Item = &'a ParticleLabel>,
+) -> Vec<ParticleLabel> {
+    let mut matches: Vec<(i32, &ParticleLabel)> = labels
+        .filter_map(|label| filter.score(label).map(|score| (score, label)))
+        .collect();
+    if filter.is_active() {
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    }
+    matches
+        .into_iter()
+        .map(|(_, label)| label.clone())
+        .collect()
+}
+
+/// Search box and movement and effect filters for the particle list.
+fn show_particle_list_filter(ui: &mut egui::Ui, filter: &mut ParticleListFilter) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut filter.search)
+                .hint_text("Search")
+                .desired_width(120.0),
+        );
+        if filter.is_active() && ui.small_button("✖").on_hover_text("Clear").clicked() {
+            *filter = ParticleListFilter::default();
+        }
+    });
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("particle_list_movement")
+            .selected_text(filter.movement.unwrap_or("Any movement"))
+            .width(100.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut filter.movement, None, "Any movement");
+                for movement in PARTICLE_LIST_MOVEMENTS {
+                    ui.selectable_value(&mut filter.movement, Some(*movement), *movement);
+                }
+            });
+        egui::ComboBox::from_id_salt("particle_list_effect")
+            .selected_text(filter.effect.unwrap_or("Any effect"))
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut filter.effect, None, "Any effect");
+                for effect in PARTICLE_LIST_EFFECTS {
+                    ui.selectable_value(&mut filter.effect, Some(*effect), *effect);
+                }
+            });
+    });
+    ui.separator();
+}
+
 fn show_editing_area(
     ui: &mut egui::Ui,
     selected_particle: &mut Option<ResMut<SelectedParticle>>,
@@ -477,6 +1029,14 @@ fn show_editing_area(
 
                     let particle_options =
                         particle_options(&editor_params.particle_registry, particle_query);
+                    let template_parents: HashMap<ParticleTypeId, ParticleTypeId> = particle_query
+                        .iter()
+                        .filter_map(|data| {
+                            data.core
+                                .template
+                                .map(|template| (data.core.particle_type.id(), template.parent))
+                        })
+                        .collect();
 
                     if let Ok(data) = particle_query.get_mut(selected_particle.0) {
                         let (timed_lifetime, chance_lifetime, timed_mutation, chance_mutation) = (
@@ -536,6 +1096,17 @@ fn show_editing_area(
                                 );
                             });
 
+                        show_template(
+                            ui,
+                            &mut editor_params.commands,
+                            selected_particle.0,
+                            data.core.particle_type.id(),
+                            data.core.template,
+                            &editor_params.inheritable_components,
+                            &template_parents,
+                            &particle_options,
+                        );
+
                         egui::CollapsingHeader::new("Movement")
                             .default_open(false)
                             .show(ui, |ui| {
@@ -732,6 +1303,89 @@ fn show_particle_type_text_edit(ui: &mut egui::Ui, entity: Entity, name_draft: &
     ui.end_row();
 }
 
+/// Choose the template the particle type inherits from, and which inherited components it
+/// overrides.
+fn show_template(
+    ui: &mut egui::Ui,
+    commands: &mut Commands,
+    entity: Entity,
+    id: ParticleTypeId,
+    template: Option<&ParticleTemplate>,
+    inheritable_components: &InheritableComponents,
+    template_parents: &HashMap<ParticleTypeId, ParticleTypeId>,
+    particle_options: &[(String, ParticleTypeId)],
+) {
+    let name_of = |parent: ParticleTypeId| {
+        particle_options
+            .iter()
+            .find_map(|(name, option)| (*option == parent).then(|| name.clone()))
+            .unwrap_or_else(|| format!("Particle {}", parent.get()))
+    };
+    // Types that inherit from this one can't be its template.
+    let mut options: Vec<&(String, ParticleTypeId)> = particle_options
+        .iter()
+        .filter(|(_, option)| {
+            *option != id && !template_ancestors(template_parents, *option).contains(&id)
+        })
+        .collect();
+    options.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let heading = match template {
+        Some(template) => format!("Template ({})", name_of(template.parent)),
+        None => String::from("Template"),
+    };
+    egui::CollapsingHeader::new(heading)
+        .id_salt("particle_template")
+        .default_open(false)
+        .show(ui, |ui| {
+            let current = template.map(|template| template.parent);
+            let mut selected = current;
+            ui.horizontal(|ui| {
+                ui.label("Inherits from:");
+                egui::ComboBox::from_id_salt("particle_template_parent")
+                    .selected_text(current.map_or_else(|| String::from("<none>"), name_of))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected, None, "<none>");
+                        for (name, option) in options {
+                            ui.selectable_value(&mut selected, Some(*option), name);
+                        }
+                    });
+            });
+            if selected != current {
+                commands.queue(move |world: &mut World| {
+                    set_particle_template(world, entity, selected);
+                });
+            }
+
+            let Some(template) = template else {
+                ui.weak("Components can be inherited from another particle type.");
+                return;
+            };
+            let parent = name_of(template.parent);
+            egui::Grid::new("particle_template_grid")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    for component in &inheritable_components.0 {
+                        let overridden = template.overrides(component);
+                        ui.label(*component);
+                        if overridden {
+                            ui.label("overridden");
+                        } else {
+                            ui.weak(format!("inherited from {parent}"));
+                        }
+                        let toggle = if overridden { "Inherit" } else { "Override" };
+                        if ui.small_button(toggle).clicked() {
+                            let component = component.to_string();
+                            commands.queue(move |world: &mut World| {
+                                set_template_override(world, entity, &component, !overridden);
+                            });
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
 fn show_category(
     commands: &mut Commands,
     entity: Entity,
@@ -0,0 +1,25 @@
+mod signals;
+mod ui;
+
+pub use signals::*;
+use ui::*;
+
+use bevy::prelude::*;
+
+use crate::ui::PopupState;
+
+pub(super) struct ReactionGraphPlugin;
+
+impl Plugin for ReactionGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((UiPlugin, SignalsPlugin))
+            .init_state::<PopupState<ReactionGraphWindowState>>();
+    }
+}
+
+#[derive(Reflect, Default, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ReactionGraphWindowState {
+    #[default]
+    Closed,
+    Open,
+}
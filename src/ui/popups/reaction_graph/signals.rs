@@ -0,0 +1,21 @@
+use crate::ui::{PopupState, ReactionGraphWindowState};
+use bevy::prelude::*;
+
+pub(super) struct SignalsPlugin;
+
+impl Plugin for SignalsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_toggle_reaction_graph);
+    }
+}
+
+#[derive(Event)]
+pub struct UiToggleReactionGraphEvent;
+
+fn on_toggle_reaction_graph(
+    _trigger: On<UiToggleReactionGraphEvent>,
+    current_reaction_graph_state: Res<State<PopupState<ReactionGraphWindowState>>>,
+    mut next_reaction_graph_state: ResMut<NextState<PopupState<ReactionGraphWindowState>>>,
+) {
+    next_reaction_graph_state.set(current_reaction_graph_state.get_next());
+}
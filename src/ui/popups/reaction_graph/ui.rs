@@ -0,0 +1,307 @@
+use std::f32::consts::TAU;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use bevy_falling_sand::{
+    core::particle::ParticleType,
+    prelude::{ChanceMutation, ContactReaction, Corrodible, Corrosive, Flammable, TimedMutation},
+};
+
+use crate::{
+    particles::{ParticleCategory, ParticleGraph, ParticleName, ParticleTransformation},
+    ui::{
+        ParticleEditorWindowState, PopupState, ReactionGraphWindowState, SelectedParticle, ShowUi,
+        UiSystems,
+    },
+};
+
+/// Size of the arrowheads drawn at the end of edges.
+const ARROW_SIZE: f32 = 8.0;
+/// Distance between edges connecting the same pair of nodes.
+const EDGE_SPACING: f32 = 10.0;
+
+pub(super) struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleGraph>()
+            .init_resource::<ReactionGraphLayout>()
+            .add_systems(
+                Update,
+                refresh_particle_graph
+                    .run_if(in_state(PopupState::<ReactionGraphWindowState>::Open))
+                    .run_if(condition_particle_graph_changed),
+            )
+            .add_systems(
+                OnEnter(PopupState::<ReactionGraphWindowState>::Open),
+                refresh_particle_graph,
+            )
+            .add_systems(
+                EguiPrimaryContextPass,
+                show.run_if(resource_exists::<ShowUi>)
+                    .run_if(in_state(PopupState::<ReactionGraphWindowState>::Open))
+                    .after(UiSystems::ParticleEditor),
+            );
+    }
+}
+
+/// Where each node is drawn, as a fraction of the graph area, and display options.
+#[derive(Resource)]
+struct ReactionGraphLayout {
+    positions: HashMap<Entity, egui::Vec2>,
+    show_labels: bool,
+}
+
+impl Default for ReactionGraphLayout {
+    fn default() -> Self {
+        Self {
+            positions: HashMap::default(),
+            show_labels: true,
+        }
+    }
+}
+
+fn refresh_particle_graph(world: &mut World) {
+    let graph = ParticleGraph::build(world);
+    world.insert_resource(graph);
+}
+
+fn condition_particle_graph_changed(
+    particle_types: Query<
+        Entity,
+        (
+            With<ParticleType>,
+            Or<(
+                Changed<ParticleType>,
+                Changed<ParticleName>,
+                Changed<ParticleCategory>,
+                Changed<ChanceMutation>,
+                Changed<TimedMutation>,
+                Changed<ContactReaction>,
+                Changed<Flammable>,
+                Changed<Corrosive>,
+                Changed<Corrodible>,
+            )>,
+        ),
+    >,
+    mut removed_particle_type: RemovedComponents<ParticleType>,
+    mut removed_name: RemovedComponents<ParticleName>,
+    mut removed_category: RemovedComponents<ParticleCategory>,
+    mut removed_chance_mutation: RemovedComponents<ChanceMutation>,
+    mut removed_timed_mutation: RemovedComponents<TimedMutation>,
+    mut removed_contact_reaction: RemovedComponents<ContactReaction>,
+    mut removed_flammable: RemovedComponents<Flammable>,
+    mut removed_corrosive: RemovedComponents<Corrosive>,
+    mut removed_corrodible: RemovedComponents<Corrodible>,
+) -> bool {
+    let removed = removed_particle_type.read().count()
+        + removed_name.read().count()
+        + removed_category.read().count()
+        + removed_chance_mutation.read().count()
+        + removed_timed_mutation.read().count()
+        + removed_contact_reaction.read().count()
+        + removed_flammable.read().count()
+        + removed_corrosive.read().count()
+        + removed_corrodible.read().count();
+    !particle_types.is_empty() || removed > 0
+}
+
+fn show(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    graph: Res<ParticleGraph>,
+    mut layout: ResMut<ReactionGraphLayout>,
+    selected_particle: Option<Res<SelectedParticle>>,
+    mut next_particle_editor_state: ResMut<NextState<PopupState<ParticleEditorWindowState>>>,
+    mut next_reaction_graph_state: ResMut<NextState<PopupState<ReactionGraphWindowState>>>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    let mut is_open = true;
+    egui::Window::new("Reaction Graph")
+        .open(&mut is_open)
+        .default_size([640.0, 480.0])
+        .constrain_to(ctx.content_rect())
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut layout.show_labels, "Edge labels");
+                if ui.button("Reset layout").clicked() {
+                    layout.positions.clear();
+                }
+                ui.label(format!(
+                    "{} types, {} transformations",
+                    graph.nodes.len(),
+                    graph.edges.len()
+                ));
+            });
+            ui.horizontal(|ui| {
+                for transformation in [
+                    ParticleTransformation::ChanceMutation,
+                    ParticleTransformation::Contact,
+                    ParticleTransformation::Burns,
+                    ParticleTransformation::Corrodes,
+                ] {
+                    ui.label(
+                        egui::RichText::new(transformation.to_string())
+                            .color(edge_color(transformation)),
+                    );
+                }
+            });
+            ui.separator();
+
+            let clicked = show_graph(
+                ui,
+                &graph,
+                &mut layout,
+                selected_particle.map(|selected| selected.0),
+            );
+            if let Some(entity) = clicked {
+                commands.insert_resource(SelectedParticle(entity));
+                next_particle_editor_state.set(PopupState::<ParticleEditorWindowState>::Open);
+            }
+        });
+
+    if !is_open {
+        next_reaction_graph_state.set(PopupState::<ReactionGraphWindowState>::Closed);
+    }
+    Ok(())
+}
+
+/// Draw the graph, returning the particle type whose node was clicked.
+fn show_graph(
+    ui: &mut egui::Ui,
+    graph: &ParticleGraph,
+    layout: &mut ReactionGraphLayout,
+    selected: Option<Entity>,
+) -> Option<Entity> {
+    let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::hover());
+    let area = response.rect.shrink(40.0);
+    let node_count = graph.nodes.len().max(1) as f32;
+
+    let mut clicked = None;
+    let mut focused = selected;
+    let mut node_rects = Vec::with_capacity(graph.nodes.len());
+    for (i, node) in graph.nodes.iter().enumerate() {
+        // New nodes start on a circle, in category order.
+        let position = layout.positions.entry(node.entity).or_insert_with(|| {
+            let angle = i as f32 / node_count * TAU;
+            egui::vec2(0.5 + 0.5 * angle.cos(), 0.5 + 0.5 * angle.sin())
+        });
+        let center = area.min + *position * area.size();
+        let galley = painter.layout_no_wrap(
+            node.name.clone(),
+            egui::FontId::proportional(13.0),
+            ui.visuals().text_color(),
+        );
+        let rect = egui::Rect::from_center_size(center, galley.size() + egui::vec2(12.0, 6.0));
+
+        let node_response = ui
+            .interact(
+                rect,
+                ui.id().with(("reaction_graph_node", node.entity)),
+                egui::Sense::click_and_drag(),
+            )
+            .on_hover_text(format!(
+                "{} ({})\nClick to edit, drag to move",
+                node.name, node.category
+            ));
+        if node_response.dragged() {
+            *position = (*position + node_response.drag_delta() / area.size())
+                .clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0));
+        }
+        if node_response.clicked() {
+            clicked = Some(node.entity);
+        }
+        if node_response.hovered() {
+            focused = Some(node.entity);
+        }
+        node_rects.push((rect, galley));
+    }
+
+    let mut pair_counts: HashMap<(usize, usize), usize> = HashMap::default();
+    for edge in graph.edges.iter().filter(|edge| edge.from != edge.to) {
+        let pair = pair_counts.entry((edge.from, edge.to)).or_default();
+        let offset = (*pair as f32 + 0.5) * EDGE_SPACING;
+        *pair += 1;
+
+        let (from, to) = (node_rects[edge.from].0, node_rects[edge.to].0);
+        let direction = (to.center() - from.center()).normalized();
+        let normal = egui::vec2(-direction.y, direction.x) * offset;
+        let start = border_point(from, direction) + normal;
+        let end = border_point(to, -direction) + normal;
+
+        let is_focused = focused.is_some_and(|entity| {
+            graph.nodes[edge.from].entity == entity || graph.nodes[edge.to].entity == entity
+        });
+        let mut color = edge_color(edge.transformation);
+        if focused.is_some() && !is_focused {
+            color = color.gamma_multiply(0.25);
+        }
+        let stroke = egui::Stroke::new(if is_focused { 2.5 } else { 1.5 }, color);
+
+        painter.line_segment([start, end], stroke);
+        for angle in [0.4, -0.4] {
+            let back = egui::emath::Rot2::from_angle(angle) * -direction * ARROW_SIZE;
+            painter.line_segment([end, end + back], stroke);
+        }
+        if layout.show_labels || is_focused {
+            painter.text(
+                start + (end - start) / 2.0 + normal.normalized() * 6.0,
+                egui::Align2::CENTER_CENTER,
+                &edge.label,
+                egui::FontId::proportional(10.0),
+                color,
+            );
+        }
+    }
+
+    for (node, (rect, galley)) in graph.nodes.iter().zip(node_rects) {
+        let visuals = ui.visuals();
+        let stroke = if Some(node.entity) == selected {
+            visuals.selection.stroke
+        } else {
+            visuals.widgets.inactive.bg_stroke
+        };
+        painter.rect(
+            rect,
+            4.0,
+            visuals.widgets.inactive.bg_fill,
+            stroke,
+            egui::StrokeKind::Inside,
+        );
+        painter.galley(
+            rect.center() - galley.size() / 2.0,
+            galley,
+            visuals.text_color(),
+        );
+    }
+
+    clicked
+}
+
+/// Where a line leaving the center of `rect` in `direction` crosses its border.
+fn border_point(rect: egui::Rect, direction: egui::Vec2) -> egui::Pos2 {
+    let half = rect.size() / 2.0;
+    let scale_x = if direction.x == 0.0 {
+        f32::INFINITY
+    } else {
+        half.x / direction.x.abs()
+    };
+    let scale_y = if direction.y == 0.0 {
+        f32::INFINITY
+    } else {
+        half.y / direction.y.abs()
+    };
+    rect.center() + direction * scale_x.min(scale_y)
+}
+
+fn edge_color(transformation: ParticleTransformation) -> egui::Color32 {
+    match transformation {
+        ParticleTransformation::ChanceMutation | ParticleTransformation::TimedMutation => {
+            egui::Color32::from_rgb(70, 130, 180)
+        }
+        ParticleTransformation::Contact => egui::Color32::from_rgb(230, 150, 50),
+        ParticleTransformation::Burns => egui::Color32::from_rgb(220, 70, 60),
+        ParticleTransformation::Corrodes => egui::Color32::from_rgb(110, 190, 80),
+    }
+}
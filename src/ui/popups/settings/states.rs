@@ -51,6 +51,7 @@ pub enum SettingsApplicationState {
 pub enum SettingsCategory {
     #[default]
     Painter,
+    Simulation,
     Debug,
     Keybinds,
 }
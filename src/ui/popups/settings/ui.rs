@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use avian2d::prelude::PhysicsGizmos;
 use bevy::{ecs::system::SystemParam, prelude::*, reflect::enums::Enum};
 use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
@@ -8,15 +10,30 @@ use super::{KeybindsListeningState, ListeningForKeybind};
 use crate::{
     ToolAction, ToolStateActions,
     camera::{CameraAction, CameraKeyBindings},
-    config::{AvianDebugConfig, InputButton, OptionalColor},
+    config::{AvianDebugConfig, FrameLimitConfig, InputButton, OptionalColor, SimulationSettings},
     tools::painter::{PainterAction, PainterKeyBindings, PainterOptions},
     ui::{
-        ConsoleAction, QuickAction, SettingsApplicationState, SettingsCategory, ShowUi,
-        UiKeyBindings, UiSystems, add_label_with_toggle_switch, add_major_grid_separator,
-        show_painter_options,
+        ConsoleAction, HOTBAR_SLOTS, HotbarAction, QuickAction, SettingsApplicationState,
+        SettingsCategory, ShowUi, UiKeyBindings, UiSystems, add_label_with_drag_value,
+        add_label_with_power_of_two_drag_value, add_label_with_toggle_switch,
+        add_major_grid_separator, show_painter_options,
     },
 };
 
+/// Binding ids of the buttons selecting each hotbar slot, in slot order.
+const HOTBAR_BINDING_IDS: [&str; HOTBAR_SLOTS] = [
+    "hotbar.select_slot_1",
+    "hotbar.select_slot_2",
+    "hotbar.select_slot_3",
+    "hotbar.select_slot_4",
+    "hotbar.select_slot_5",
+    "hotbar.select_slot_6",
+    "hotbar.select_slot_7",
+    "hotbar.select_slot_8",
+    "hotbar.select_slot_9",
+    "hotbar.select_slot_10",
+];
+
 /// System param to fetch particle types by material type.
 #[derive(SystemParam)]
 struct SettingsParam<'w, 's> {
@@ -24,6 +41,7 @@ struct SettingsParam<'w, 's> {
     pub current_settings_category: ResMut<'w, State<SettingsCategory>>,
     pub next_settings_category: ResMut<'w, NextState<SettingsCategory>>,
     pub painter: PainterOptions<'w, 's>,
+    pub simulation: ResMut<'w, SimulationSettings>,
     pub debug_falling_sand: BevyFallingSandDebugSettingsParam<'w>,
     pub avian: AvianDebugSettingsParam<'w>,
     pub keybinds: KeybindsSettingsParam<'w>,
@@ -74,6 +92,7 @@ fn show(mut contexts: EguiContexts, mut settings_param: SettingsParam) -> Result
             ui.horizontal(|ui| {
                 for variant in [
                     SettingsCategory::Painter,
+                    SettingsCategory::Simulation,
                     SettingsCategory::Debug,
                     SettingsCategory::Keybinds,
                 ] {
@@ -90,6 +109,9 @@ fn show(mut contexts: EguiContexts, mut settings_param: SettingsParam) -> Result
                 ui.separator();
                 match *settings_param.current_settings_category.get() {
                     SettingsCategory::Painter => show_painter_options(ui, settings_param.painter),
+                    SettingsCategory::Simulation => {
+                        show_simulation_settings(ui, &mut settings_param)
+                    }
                     SettingsCategory::Debug => show_debug_settings(ui, &mut settings_param),
                     SettingsCategory::Keybinds => show_keybinds_settings(ui, &mut settings_param),
                 };
@@ -99,6 +121,120 @@ fn show(mut contexts: EguiContexts, mut settings_param: SettingsParam) -> Result
     Ok(())
 }
 
+/// Chunk sizes from 8 to 1024 particles.
+const CHUNK_SIZE_EXPONENTS: RangeInclusive<u32> = 3..=10;
+/// Map sizes from 1 to 256 chunks.
+const MAP_SIZE_EXPONENTS: RangeInclusive<u32> = 0..=8;
+
+fn show_simulation_settings(ui: &mut egui::Ui, settings_param: &mut SettingsParam) {
+    // Only write through when something changed so `SimulationSettings` change detection stays
+    // meaningful for the frame limiter.
+    let mut simulation = settings_param.simulation.clone();
+
+    egui::Grid::new("simulation_grid")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.heading("Defaults");
+            ui.end_row();
+            simulation.defaults.chunk_size = add_label_with_power_of_two_drag_value(
+                ui,
+                0,
+                "Chunk Size",
+                simulation.defaults.chunk_size,
+                CHUNK_SIZE_EXPONENTS,
+            );
+            simulation.defaults.map_size = add_label_with_power_of_two_drag_value(
+                ui,
+                0,
+                "Map Size (chunks)",
+                simulation.defaults.map_size,
+                MAP_SIZE_EXPONENTS,
+            );
+            show_frame_limit(ui, "Frame Limit", &mut simulation.defaults.frame_limit);
+
+            add_major_grid_separator(ui);
+
+            ui.heading("World Overrides");
+            ui.end_row();
+            let defaults = simulation.defaults.clone();
+            show_optional_override(
+                ui,
+                "Chunk Size",
+                &mut simulation.world.chunk_size,
+                defaults.chunk_size,
+                |ui, value| {
+                    *value = add_label_with_power_of_two_drag_value(
+                        ui,
+                        0,
+                        "    Value",
+                        *value,
+                        CHUNK_SIZE_EXPONENTS,
+                    )
+                },
+            );
+            show_optional_override(
+                ui,
+                "Map Size (chunks)",
+                &mut simulation.world.map_size,
+                defaults.map_size,
+                |ui, value| {
+                    *value = add_label_with_power_of_two_drag_value(
+                        ui,
+                        0,
+                        "    Value",
+                        *value,
+                        MAP_SIZE_EXPONENTS,
+                    )
+                },
+            );
+            show_optional_override(
+                ui,
+                "Frame Limit",
+                &mut simulation.world.frame_limit,
+                defaults.frame_limit,
+                |ui, value| show_frame_limit(ui, "    Value", value),
+            );
+        });
+
+    if simulation.requires_restart() {
+        ui.label(
+            egui::RichText::new("Chunk and map size changes take effect after a save and restart.")
+                .color(egui::Color32::YELLOW),
+        );
+    }
+
+    if simulation.defaults != settings_param.simulation.defaults
+        || simulation.world != settings_param.simulation.world
+    {
+        *settings_param.simulation = simulation;
+    }
+}
+
+fn show_frame_limit(ui: &mut egui::Ui, label: &str, frame_limit: &mut FrameLimitConfig) {
+    frame_limit.enabled = add_label_with_toggle_switch(ui, 0, label, frame_limit.enabled);
+    if frame_limit.enabled {
+        frame_limit.fps =
+            add_label_with_drag_value(ui, 0, "    FPS", frame_limit.fps, 1.0..=1000.0, 1.0);
+    }
+}
+
+fn show_optional_override<T: Clone>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<T>,
+    default: T,
+    show_value: impl FnOnce(&mut egui::Ui, &mut T),
+) {
+    let enabled = value.is_some();
+    let new_enabled = add_label_with_toggle_switch(ui, 0, label, enabled);
+    if new_enabled != enabled {
+        *value = new_enabled.then_some(default);
+    }
+    if let Some(value) = value {
+        show_value(ui, value);
+    }
+}
+
 fn show_debug_settings(ui: &mut egui::Ui, settings_param: &mut SettingsParam) {
     egui::Grid::new("debug_grid").num_columns(2).show(ui, |ui| {
         ui.heading("Falling Sand Debug");
@@ -275,6 +411,12 @@ fn show_keybinds_settings(ui: &mut egui::Ui, settings_param: &mut SettingsParam)
 
             add_major_grid_separator(ui);
 
+            ui.heading("Hotbar");
+            ui.end_row();
+            show_hotbar_keybinds(ui, settings_param);
+
+            add_major_grid_separator(ui);
+
             ui.heading("Console");
             ui.end_row();
             show_console_keybinds(ui, settings_param);
@@ -452,6 +594,21 @@ fn show_quick_actions_keybinds(ui: &mut egui::Ui, settings_param: &mut SettingsP
     }
 }
 
+fn show_hotbar_keybinds(ui: &mut egui::Ui, settings_param: &mut SettingsParam) {
+    let keys = settings_param.keybinds.ui_keys.hotbar.clone();
+    for (slot, binding_id) in HOTBAR_BINDING_IDS.into_iter().enumerate() {
+        if show_keybind_row(
+            ui,
+            &format!("Slot {}", slot + 1),
+            binding_id,
+            &keys.select_slot[slot],
+            &settings_param.keybinds.listening,
+        ) {
+            start_listening(settings_param, binding_id);
+        }
+    }
+}
+
 fn show_console_keybinds(ui: &mut egui::Ui, settings_param: &mut SettingsParam) {
     let keys = settings_param.keybinds.ui_keys.console.clone();
     if show_keybind_row(
@@ -490,6 +647,7 @@ pub fn listen_for_keybind(
     mut camera_input_map: Query<&mut InputMap<CameraAction>>,
     mut brush_input_map: Query<&mut InputMap<PainterAction>>,
     mut quick_action_input_map: Query<&mut InputMap<QuickAction>>,
+    mut hotbar_input_map: Query<&mut InputMap<HotbarAction>>,
     mut console_input_map: Query<&mut InputMap<ConsoleAction>>,
     mut tool_action_input_map: Query<&mut InputMap<ToolAction>>,
     mut tool_state_action_input_map: Query<&mut InputMap<ToolStateActions>>,
@@ -541,7 +699,11 @@ pub fn listen_for_keybind(
         }
         "console.toggle_information_area" => ui_keys.console.toggle_information_area = new_button,
         "general.resize_tool" => ui_keys.general.resize_tool = new_button,
-        _ => {}
+        id => {
+            if let Some(slot) = HOTBAR_BINDING_IDS.iter().position(|binding| *binding == id) {
+                ui_keys.hotbar.select_slot[slot] = new_button;
+            }
+        }
     }
 
     // Rebuild the affected InputMap
@@ -597,6 +759,11 @@ pub fn listen_for_keybind(
                     .insert_into_input_map(&mut map, QuickAction::SampleHoveredParticle);
             }
         }
+        id if id.starts_with("hotbar.") => {
+            if let Ok(mut map) = hotbar_input_map.single_mut() {
+                *map = ui_keys.hotbar.input_map();
+            }
+        }
         id if id.starts_with("console.") => {
             if let Ok(mut map) = console_input_map.single_mut() {
                 *map = InputMap::default();
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     config::InputButton,
-    ui::{ConsoleKeyBindings, QuickActionsKeyBindings},
+    ui::{ConsoleKeyBindings, HotbarKeyBindings, QuickActionsKeyBindings},
 };
 
 pub(super) struct SetupPlugin;
@@ -20,6 +20,7 @@ pub struct UiKeyBindings {
     pub console: ConsoleKeyBindings,
     pub quick_actions: QuickActionsKeyBindings,
     pub general: GeneralKeyBindings,
+    pub hotbar: HotbarKeyBindings,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]